@@ -2,8 +2,10 @@ use crate::{ConfigFile, Result};
 
 use clap::{Parser, Subcommand};
 use log::LevelFilter;
-use minty::{text, PostSort, Url, Uuid};
-use std::path::PathBuf;
+use minty::{
+    text, DateRange, NumberRange, Orientation, PostSort, TokenScope, Url, Uuid,
+};
+use std::{path::PathBuf, result, str::FromStr};
 
 mod env {
     macro_rules! var {
@@ -22,6 +24,7 @@ mod env {
     var!(SERVER);
     var!(TAG);
     var!(TAGS);
+    var!(TOKEN);
 }
 
 #[derive(Debug, Parser)]
@@ -66,6 +69,12 @@ pub struct Cli {
     /// Level of log verbosity
     pub log_level: Option<LevelFilter>,
 
+    #[arg(long, value_name = "TOKEN", env = env::TOKEN, global = true)]
+    /// Personal access token to authenticate with instead of a session
+    ///
+    /// Tokens can be created with 'minty me token create'.
+    pub token: Option<String>,
+
     #[command(subcommand)]
     pub command: Command,
 }
@@ -98,6 +107,12 @@ pub enum Command {
         /// Maximum number of hits to return
         size: u32,
 
+        #[arg(short, long, global = true)]
+        /// Resume from the cursor returned by a previous search
+        ///
+        /// When a cursor is given, the starting offset is ignored.
+        cursor: Option<String>,
+
         #[command(subcommand)]
         command: Find,
     },
@@ -160,6 +175,12 @@ pub enum Command {
         command: Option<Tag>,
     },
 
+    /// List tag categories or delete a category
+    Categories {
+        #[command(subcommand)]
+        command: Option<Categories>,
+    },
+
     /// Display the given tags
     Tags {
         #[arg(value_delimiter = ' ', env = env::TAGS)]
@@ -284,12 +305,84 @@ pub enum Find {
         /// Search for posts with the given tags
         tag: Vec<Uuid>,
 
+        #[arg(short = 'x', long, value_name = "ID", value_delimiter = ' ')]
+        /// Exclude posts with the given tags
+        exclude: Vec<Uuid>,
+
+        #[arg(short, long, value_name = "IDS")]
+        /// Search for posts with at least one of the given tags
+        ///
+        /// Tag IDs within a group are separated by commas. This option may be
+        /// given multiple times; posts must match every group.
+        any: Vec<TagGroup>,
+
+        #[arg(long, value_name = "RANGE")]
+        /// Only search for posts created within a date range
+        ///
+        /// A range is written as START..END, where either bound may be
+        /// omitted. Bounds are dates (YYYY-MM-DD) or RFC 3339 timestamps; the
+        /// start is inclusive and the end is exclusive. A single date matches
+        /// that entire day.
+        created: Option<DateRange>,
+
+        #[arg(long, value_name = "RANGE")]
+        /// Only search for posts modified within a date range
+        modified: Option<DateRange>,
+
+        #[arg(long, value_name = "TYPE")]
+        /// Only search for posts with objects of a media type
+        ///
+        /// The type may be given alone, as in 'video', or together with a
+        /// subtype, as in 'image/png'.
+        media: Option<String>,
+
+        #[arg(long, value_name = "RANGE")]
+        /// Only search for posts with objects within a width range in pixels
+        ///
+        /// A range is written as MIN..MAX, where either bound may be omitted.
+        /// Both bounds are inclusive. '>MIN' and '<MAX' are shorthands for a
+        /// single bound, and a lone number matches exactly.
+        width: Option<NumberRange>,
+
+        #[arg(long, value_name = "RANGE")]
+        /// Only search for posts with objects within a height range in pixels
+        height: Option<NumberRange>,
+
+        #[arg(long, value_name = "RANGE")]
+        /// Only search for posts with objects within a duration range in
+        /// seconds
+        duration: Option<NumberRange>,
+
+        #[arg(long, value_name = "ORIENTATION")]
+        /// Only search for posts with landscape, portrait or square objects
+        orientation: Option<Orientation>,
+
+        #[arg(long, value_name = "COUNT", default_value = "0")]
+        /// Show the most common tags among matching posts
+        facets: u32,
+
+        #[arg(short, long, value_name = "QUERY")]
+        /// Search using the post query language
+        ///
+        /// Words are tag names or IDs; quote names containing spaces. Prefix
+        /// a tag with '-' to exclude it, and separate tags with '|' to match
+        /// any of them. A name may be qualified by its category, as in
+        /// artist:"Jane Doe". Other filters are written as key:value pairs:
+        /// text:"words", poster:<id>, sort:title.asc, vis:draft,
+        /// created:2024-01-01.., modified:>2024-06-01, media:video,
+        /// width:>1920, height:..1080, duration:<60, orientation:portrait.
+        query: Option<String>,
+
         /// Title/description text to search for
         text: Option<String>,
     },
 
     /// Search for tags
     Tag {
+        #[arg(short = 'C', long)]
+        /// Only search for tags in the given category
+        category: Option<String>,
+
         /// Name or alias of the tag to search for
         name: String,
     },
@@ -332,6 +425,26 @@ pub enum New {
         /// Add tags to the post
         tag: Option<Vec<Uuid>>,
 
+        #[arg(long)]
+        /// Attach files even if they already exist on the server
+        ///
+        /// By default, uploaded files matching an existing object are
+        /// reported and skipped.
+        allow_duplicate: bool,
+
+        #[arg(
+            short,
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["allow_duplicate", "objects"]
+        )]
+        /// Create the post from the files in a ZIP or TAR archive
+        ///
+        /// Every file in the archive is uploaded and attached in natural
+        /// filename order. If no title is given, the archive's file name is
+        /// used. Files that could not be stored are reported.
+        archive: Option<PathBuf>,
+
         /// Files to attach to the post
         objects: Vec<String>,
     },
@@ -353,6 +466,26 @@ pub enum New {
         /// New tag's name
         name: text::Name,
     },
+
+    /// Create a new tag category
+    ///
+    /// Requires administrator privileges.
+    Category {
+        /// New category's name
+        name: text::Category,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Categories {
+    /// Delete a tag category
+    ///
+    /// Tags in the category become uncategorized. Requires administrator
+    /// privileges.
+    Rm {
+        /// Name of the category to delete
+        name: String,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -392,6 +525,12 @@ pub enum Me {
         #[command(subcommand)]
         command: Option<MeRm>,
     },
+
+    /// Manage personal access tokens
+    Token {
+        #[command(subcommand)]
+        command: MeToken,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -409,6 +548,43 @@ pub enum MeRm {
     },
 }
 
+#[derive(Debug, Subcommand)]
+pub enum MeToken {
+    /// Create a new token and print its secret
+    ///
+    /// The secret is only shown once. Any scope also grants read access, and
+    /// the admin scope grants all others.
+    Create {
+        #[arg(
+            short,
+            long = "scope",
+            value_name = "SCOPE",
+            value_delimiter = ',',
+            required = true
+        )]
+        /// Permissions granted to the token: read, posts, tags, account, or admin
+        scopes: Vec<TokenScope>,
+
+        #[arg(short, long, value_name = "DAYS")]
+        /// Number of days until the token expires
+        ///
+        /// If omitted, the token is valid until it is revoked.
+        expires: Option<u32>,
+
+        /// A name to identify the token by
+        name: text::Name,
+    },
+
+    /// List your tokens
+    List,
+
+    /// Revoke a token
+    Revoke {
+        /// Token ID
+        id: Uuid,
+    },
+}
+
 #[derive(Debug, Subcommand)]
 pub enum Object {
     /// Download an object's data
@@ -417,15 +593,45 @@ pub enum Object {
         /// Do not overwrite an existing file
         no_clobber: bool,
 
+        #[arg(
+            short = 'c',
+            long,
+            requires = "destination",
+            conflicts_with = "no_clobber"
+        )]
+        /// Continue a partially downloaded file
+        ///
+        /// Only the remaining bytes are requested from the server. If the
+        /// file is already complete, nothing is downloaded.
+        resume: bool,
+
         /// Write output to a file instead of stdout
         destination: Option<PathBuf>,
     },
+
+    /// Print the status of an object's preview generation
+    Preview,
+
+    /// List visually similar objects and the posts containing them
+    Similar {
+        #[arg(short, long, value_name = "BITS")]
+        /// Maximum Hamming distance between perceptual hashes
+        ///
+        /// If omitted, the server's default threshold is used.
+        distance: Option<u32>,
+    },
 }
 
 #[derive(Debug, Subcommand)]
 pub enum Objects {
     /// Print all object preview errors
     Errors,
+
+    /// Find objects with the given SHA-256 hash
+    Hash {
+        /// Hex-encoded hash of the object's contents
+        hash: String,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -454,6 +660,13 @@ pub enum Post {
         /// If omitted, new objects will be appended to the end.
         destination: Option<Uuid>,
 
+        #[arg(long)]
+        /// Attach files even if they already exist on the server
+        ///
+        /// By default, uploaded files matching an existing object are
+        /// reported and skipped.
+        allow_duplicate: bool,
+
         #[arg(required = true)]
         /// Files to attach to the post
         ///
@@ -536,6 +749,30 @@ pub enum Tag {
         description: Option<text::Description>,
     },
 
+    /// Place a tag in a category
+    Category {
+        /// Name of an existing category
+        category: String,
+    },
+
+    /// Add tags implied by a tag or list the tag's implications
+    ///
+    /// Posts given this tag will also receive the implied tags.
+    /// Implications are transitive. Requires administrator privileges.
+    Implies {
+        /// IDs of implied tags
+        tags: Vec<Uuid>,
+    },
+
+    /// Merge another tag into this one
+    ///
+    /// The other tag's posts, names, links and description are moved to
+    /// this tag, and the other tag is deleted.
+    Merge {
+        /// ID of the tag to merge and delete
+        other: Uuid,
+    },
+
     /// Add a link to a tag
     Ln {
         /// Tag's new link
@@ -563,6 +800,16 @@ pub enum TagRm {
         alias: Option<String>,
     },
 
+    /// Remove a tag from its category
+    Category,
+
+    /// Remove tags implied by a tag
+    Implication {
+        #[arg(required = true)]
+        /// IDs of implied tags
+        tags: Vec<Uuid>,
+    },
+
     /// Remove a tag's link
     Link {
         /// Tag links to delete
@@ -588,6 +835,23 @@ pub enum Revoke {
     },
 }
 
+#[derive(Clone, Debug)]
+pub struct TagGroup(pub Vec<Uuid>);
+
+impl FromStr for TagGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        s.split(',')
+            .map(|id| {
+                Uuid::try_parse(id.trim())
+                    .map_err(|err| format!("invalid tag ID '{id}': {err}"))
+            })
+            .collect::<result::Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
 impl Cli {
     pub fn config(&self) -> Result<ConfigFile> {
         ConfigFile::read(self.config.clone())
@@ -5,6 +5,7 @@ use crate::{
     Error,
 };
 
+use chrono::{Days, Local};
 use minty::{
     http::{self, cookie::CookieFile, Credentials},
     model::*,
@@ -12,14 +13,18 @@ use minty::{
 };
 use rpassword::prompt_password;
 use serde_json as json;
+use sha2::{Digest, Sha256};
 use std::{
     env,
-    io::{stdin, IsTerminal, Read},
-    path::PathBuf,
+    io::{self, stdin, Cursor, IsTerminal, Read, SeekFrom},
+    path::{Path, PathBuf},
     str::FromStr,
     sync::Arc,
 };
-use tokio::fs::File;
+use tokio::{
+    fs::File,
+    io::{AsyncRead, AsyncReadExt, AsyncSeekExt},
+};
 use tokio_util::io::{ReaderStream, StreamReader};
 
 const USER_AGENT: &str =
@@ -38,13 +43,21 @@ impl Client {
         alias: &str,
         server: Url,
         cookie_file: PathBuf,
+        token: Option<String>,
         output: Output,
     ) -> crate::Result<Self> {
-        let cookie_file = CookieFile::new(cookie_file)
-            .map_err(|err| Error::Config(err.to_string()))?;
+        let credentials = match token {
+            Some(token) => Credentials::Token(token),
+            None => {
+                let cookie_file = CookieFile::new(cookie_file)
+                    .map_err(|err| Error::Config(err.to_string()))?;
+
+                Credentials::CookieFile(Arc::new(cookie_file))
+            }
+        };
 
         let repo = http::Repo::build(server)
-            .credentials(Credentials::CookieFile(Arc::new(cookie_file)))
+            .credentials(credentials)
             .user_agent(USER_AGENT)
             .build()?;
 
@@ -93,18 +106,21 @@ impl Client {
     pub async fn add_objects(
         &self,
         args: Vec<String>,
+        allow_duplicate: bool,
     ) -> crate::Result<Vec<Uuid>> {
         let mut objects = Vec::new();
 
         for arg in args {
-            if let Ok(uuid) = Uuid::parse_str(&arg) {
-                objects.push(uuid);
+            let object = if let Ok(uuid) = Uuid::parse_str(&arg) {
+                Some(uuid)
             } else if let Ok(url) = Url::parse(&arg) {
-                objects.push(self.upload_url(url).await?);
+                self.upload_url(url, allow_duplicate).await?
             } else {
                 let path = PathBuf::from(&arg);
-                objects.push(self.upload_file(path).await?);
-            }
+                self.upload_file(path, allow_duplicate).await?
+            };
+
+            objects.extend(object);
         }
 
         Ok(objects)
@@ -115,8 +131,9 @@ impl Client {
         post_id: Uuid,
         objects: Vec<String>,
         destination: Option<Uuid>,
+        allow_duplicate: bool,
     ) -> Result {
-        let objects = self.add_objects(objects).await?;
+        let objects = self.add_objects(objects, allow_duplicate).await?;
 
         match destination {
             Some(destination) => {
@@ -164,6 +181,23 @@ impl Client {
         self.print(self.repo.add_tag_alias(id, alias).await?)
     }
 
+    pub async fn add_tag_category(&self, name: text::Category) -> Result {
+        self.repo.add_tag_category(name).await?;
+        Ok(())
+    }
+
+    pub async fn add_tag_implications(
+        &self,
+        id: Uuid,
+        tags: Vec<Uuid>,
+    ) -> Result {
+        for tag in tags {
+            self.repo.add_tag_implication(id, tag).await?;
+        }
+
+        Ok(())
+    }
+
     pub async fn add_tag_source(&self, id: Uuid, url: &Url) -> Result {
         self.repo.add_tag_source(id, url).await?;
         Ok(())
@@ -193,6 +227,63 @@ impl Client {
         Ok(())
     }
 
+    pub async fn create_post_from_archive(
+        &self,
+        path: PathBuf,
+        mut parts: PostParts,
+    ) -> Result {
+        if parts.title.is_none() {
+            parts.title = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse().ok());
+        }
+
+        let file = File::open(&path).await.map_err(|err| {
+            format!("failed to open file '{}': {err}", path.display())
+        })?;
+        let stream = ReaderStream::new(file);
+
+        let upload = self.repo.create_post_from_archive(&parts, stream).await?;
+
+        for ArchiveEntryError { name, message } in &upload.errors {
+            eprintln!("failed to store '{name}': {message}");
+        }
+
+        println!("{}", upload.post_id);
+        Ok(())
+    }
+
+    pub async fn create_token(
+        &self,
+        name: text::Name,
+        scopes: Vec<TokenScope>,
+        expires: Option<u32>,
+    ) -> Result {
+        let expiration = match expires {
+            Some(days) => Some(
+                Local::now()
+                    .checked_add_days(Days::new(days.into()))
+                    .ok_or_else(|| {
+                        format!("expiration of {days} days is too far away")
+                    })?,
+            ),
+            None => None,
+        };
+
+        let token = self
+            .repo
+            .create_token(&NewToken {
+                name,
+                scopes,
+                expiration,
+            })
+            .await?;
+
+        println!("{}", token.secret);
+        Ok(())
+    }
+
     pub async fn delete_comment(
         &self,
         id: Uuid,
@@ -301,6 +392,11 @@ impl Client {
         self.print(self.repo.delete_tag_alias(id, &alias).await?)
     }
 
+    pub async fn delete_tag_category(&self, name: &str) -> Result {
+        self.repo.delete_tag_category(name).await?;
+        Ok(())
+    }
+
     pub async fn delete_tag_source(&self, id: Uuid) -> Result {
         let tag = self.repo.get_tag(id).await?;
         let Some(source) = ask::delete_source("tag", tag.profile)? else {
@@ -312,6 +408,18 @@ impl Client {
         Ok(())
     }
 
+    pub async fn delete_tag_implications(
+        &self,
+        id: Uuid,
+        tags: Vec<Uuid>,
+    ) -> Result {
+        for tag in tags {
+            self.repo.delete_tag_implication(id, tag).await?;
+        }
+
+        Ok(())
+    }
+
     pub async fn delete_tag_sources(
         &self,
         id: Uuid,
@@ -374,6 +482,19 @@ impl Client {
         Ok(())
     }
 
+    pub async fn find_posts(
+        &self,
+        terms: Option<String>,
+        query: PostQuery,
+    ) -> Result {
+        match terms {
+            Some(terms) => {
+                self.print(self.repo.search_posts(&terms, &query).await?)
+            }
+            None => self.get_posts(query).await,
+        }
+    }
+
     pub async fn find_tags(&self, query: ProfileQuery) -> Result {
         self.print(self.repo.search_tags(&query).await?)
     }
@@ -446,10 +567,70 @@ impl Client {
         Ok(())
     }
 
+    pub async fn resume_object_data(&self, id: Uuid, path: &Path) -> Result {
+        let mut file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)
+            .await
+            .map_err(|err| {
+                format!("failed to open file '{}': {err}", path.display())
+            })?;
+
+        let len = file
+            .metadata()
+            .await
+            .map_err(|err| {
+                format!(
+                    "failed to read metadata for '{}': {err}",
+                    path.display()
+                )
+            })?
+            .len();
+
+        let size = self.repo.get_object(id).await?.size;
+
+        if len == size {
+            return Ok(());
+        }
+
+        let offset = if len < size { len } else { 0 };
+        let (_, start, stream) =
+            self.repo.get_object_data_from(id, offset).await?;
+        let mut reader = StreamReader::new(stream);
+
+        file.set_len(start).await.map_err(|err| {
+            format!("failed to truncate file '{}': {err}", path.display())
+        })?;
+        file.seek(SeekFrom::Start(start)).await.map_err(|err| {
+            format!("failed to seek in file '{}': {err}", path.display())
+        })?;
+
+        tokio::io::copy(&mut reader, &mut file)
+            .await
+            .map_err(|err| {
+                format!(
+                    "failed to stream data to file '{}': {err}",
+                    path.display()
+                )
+            })?;
+
+        Ok(())
+    }
+
     pub async fn get_object_preview_errors(&self) -> Result {
         self.print(self.repo.get_object_preview_errors().await?)
     }
 
+    pub async fn get_object_preview_status(&self, id: Uuid) -> Result {
+        self.print(self.repo.get_object_preview_status(id).await?)
+    }
+
+    pub async fn get_objects_by_hash(&self, hash: &str) -> Result {
+        self.print(self.repo.get_objects_by_hash(hash).await?.list())
+    }
+
     pub async fn get_post(&self, id: Uuid) -> Result {
         self.print(self.repo.get_post(id).await?)
     }
@@ -458,14 +639,38 @@ impl Client {
         self.print(self.repo.get_posts(&query).await?)
     }
 
+    pub async fn get_similar_objects(
+        &self,
+        id: Uuid,
+        distance: Option<u32>,
+    ) -> Result {
+        self.print(self.repo.get_similar_objects(id, distance).await?.list())
+    }
+
     pub async fn get_tag(&self, id: Uuid) -> Result {
         self.print(self.repo.get_tag(id).await?)
     }
 
+    pub async fn get_tag_categories(&self) -> Result {
+        for category in self.repo.get_tag_categories().await? {
+            println!("{category}");
+        }
+
+        Ok(())
+    }
+
+    pub async fn get_tag_implications(&self, id: Uuid) -> Result {
+        self.print(self.repo.get_tag_implications(id).await?.list())
+    }
+
     pub async fn get_tags(&self, ids: &[Uuid]) -> Result {
         self.print(self.repo.get_tags(ids).await?.list())
     }
 
+    pub async fn get_tokens(&self) -> Result {
+        self.print(self.repo.get_tokens().await?.list())
+    }
+
     pub async fn get_user(&self, id: Uuid) -> Result {
         self.print(self.repo.get_user(id).await?)
     }
@@ -474,6 +679,10 @@ impl Client {
         self.print(self.repo.get_users(&query).await?)
     }
 
+    pub async fn merge_tag(&self, id: Uuid, other: Uuid) -> Result {
+        self.print(self.repo.merge_tag(id, other).await?)
+    }
+
     pub async fn publish_post(&self, id: Uuid) -> Result {
         self.repo.publish_post(id).await?;
         Ok(())
@@ -495,6 +704,11 @@ impl Client {
         Ok(())
     }
 
+    pub async fn revoke_token(&self, id: Uuid) -> Result {
+        self.repo.revoke_token(id).await?;
+        Ok(())
+    }
+
     pub async fn set_comment_content(
         &self,
         id: Uuid,
@@ -540,6 +754,15 @@ impl Client {
         Ok(())
     }
 
+    pub async fn set_tag_category(
+        &self,
+        id: Uuid,
+        category: Option<&str>,
+    ) -> Result {
+        self.repo.set_tag_category(id, category).await?;
+        Ok(())
+    }
+
     pub async fn set_tag_description(
         &self,
         id: Uuid,
@@ -633,27 +856,78 @@ impl Client {
         Ok(())
     }
 
-    async fn upload_file(&self, path: PathBuf) -> crate::Result<Uuid> {
-        let file = File::open(&path).await.map_err(|err| {
-            format!("failed to open file '{}': {err}", path.display())
-        })?;
-        let stream = ReaderStream::new(file);
-        let object = self.repo.add_object(stream).await?;
+    async fn skip_duplicate(
+        &self,
+        name: &str,
+        hash: &str,
+    ) -> crate::Result<bool> {
+        let duplicates = self.repo.get_objects_by_hash(hash).await?;
+
+        if duplicates.is_empty() {
+            return Ok(false);
+        }
+
+        eprintln!("skipping '{name}': file already exists");
 
-        Ok(object.id)
+        for duplicate in &duplicates {
+            eprintln!("  object {}", duplicate.object.id);
+
+            for post in &duplicate.posts {
+                eprintln!("    post {} {}", post.id, post.title);
+            }
+        }
+
+        Ok(true)
     }
 
-    async fn upload_url(&self, url: Url) -> crate::Result<Uuid> {
-        let stream = reqwest::get(url.as_str())
+    async fn upload_file(
+        &self,
+        path: PathBuf,
+        allow_duplicate: bool,
+    ) -> crate::Result<Option<Uuid>> {
+        if !allow_duplicate {
+            let hash = sha256(open(&path).await?).await.map_err(|err| {
+                format!("failed to read file '{}': {err}", path.display())
+            })?;
+            let name = path.display().to_string();
+
+            if self.skip_duplicate(&name, &hash).await? {
+                return Ok(None);
+            }
+        }
+
+        let stream = ReaderStream::new(open(&path).await?);
+        let upload = self.repo.add_object(stream).await?;
+
+        Ok(Some(upload.object.id))
+    }
+
+    async fn upload_url(
+        &self,
+        url: Url,
+        allow_duplicate: bool,
+    ) -> crate::Result<Option<Uuid>> {
+        let data = reqwest::get(url.as_str())
             .await
             .map_err(|err| format!("request to '{url}' failed: {err}"))?
             .error_for_status()
             .map_err(|err| format!("({}) {url}", err.status().unwrap()))?
-            .bytes_stream();
+            .bytes()
+            .await
+            .map_err(|err| format!("failed to download '{url}': {err}"))?;
 
-        let object = self.repo.add_object(stream).await?;
+        if !allow_duplicate {
+            let hash = sha256(&data[..]).await?;
 
-        Ok(object.id)
+            if self.skip_duplicate(url.as_str(), &hash).await? {
+                return Ok(None);
+            }
+        }
+
+        let stream = ReaderStream::new(Cursor::new(data));
+        let upload = self.repo.add_object(stream).await?;
+
+        Ok(Some(upload.object.id))
     }
 }
 
@@ -666,3 +940,26 @@ where
 
     Ok(buffer.parse().map_err(|err: text::Error| err.to_string())?)
 }
+
+async fn open(path: &Path) -> crate::Result<File> {
+    Ok(File::open(path).await.map_err(|err| {
+        format!("failed to open file '{}': {err}", path.display())
+    })?)
+}
+
+async fn sha256(mut reader: impl AsyncRead + Unpin) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; 64 * 1024];
+
+    loop {
+        let n = reader.read(&mut buf).await?;
+
+        if n == 0 {
+            break;
+        }
+
+        hasher.update(&buf[..n]);
+    }
+
+    Ok(base16ct::lower::encode_string(&hasher.finalize()))
+}
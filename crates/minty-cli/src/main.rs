@@ -26,6 +26,7 @@ impl Client {
             &args.server,
             server.clone(),
             config.cookies(),
+            args.token.clone(),
             Output {
                 human_readable: args.human_readable,
                 json: args.json,
@@ -48,6 +49,7 @@ impl Client {
     async fn run_async(&self, args: Cli) -> Result {
         match args.command {
             Command::About => self.client.about().await,
+            Command::Categories { command } => self.categories(command).await,
             Command::Comment { id, command } => self.comment(id, command).await,
             Command::Comments { post } => self.client.get_comments(post).await,
             Command::Email { email } => self.client.set_user_email(email).await,
@@ -56,7 +58,8 @@ impl Client {
                 command,
                 from,
                 size,
-            } => self.find(command, Pagination { from, size }).await,
+                cursor,
+            } => self.find(command, Pagination { from, size }, cursor).await,
             Command::Grant { command } => self.grant(command).await,
             Command::Invite => self.client.get_invitation().await,
             Command::Login { email } => self.client.authenticate(email).await,
@@ -79,6 +82,19 @@ impl Client {
         }
     }
 
+    async fn categories(&self, command: Option<Categories>) -> Result {
+        let Some(command) = command else {
+            self.client.get_tag_categories().await?;
+            return Ok(());
+        };
+
+        match command {
+            Categories::Rm { name } => {
+                self.client.delete_tag_category(&name).await
+            }
+        }
+    }
+
     async fn comment(&self, id: Uuid, command: Option<Comment>) -> Result {
         let Some(command) = command else {
             self.client.get_comment(id).await?;
@@ -96,36 +112,73 @@ impl Client {
         }
     }
 
-    async fn find(&self, command: Find, pagination: Pagination) -> Result {
+    async fn find(
+        &self,
+        command: Find,
+        pagination: Pagination,
+        cursor: Option<String>,
+    ) -> Result {
         match command {
             Find::Post {
                 drafts,
                 poster,
                 sort_by,
                 tag,
+                exclude,
+                any,
+                created,
+                modified,
+                media,
+                width,
+                height,
+                duration,
+                orientation,
+                facets,
+                query,
                 text,
             } => {
                 self.client
-                    .get_posts(PostQuery {
-                        pagination,
-                        poster,
-                        text: text.unwrap_or_default(),
-                        tags: tag,
-                        visibility: if drafts {
-                            Visibility::Draft
-                        } else {
-                            Visibility::Public
+                    .find_posts(
+                        query,
+                        PostQuery {
+                            pagination,
+                            poster,
+                            text: text.unwrap_or_default(),
+                            tags: tag,
+                            exclude_tags: exclude,
+                            any_tags: any
+                                .into_iter()
+                                .map(|group| group.0)
+                                .collect(),
+                            created: created.unwrap_or_default(),
+                            modified: modified.unwrap_or_default(),
+                            media_type: media,
+                            width: width.unwrap_or_default(),
+                            height: height.unwrap_or_default(),
+                            duration_ms: duration
+                                .map(|duration| duration.scale(1000))
+                                .unwrap_or_default(),
+                            orientation,
+                            visibility: if drafts {
+                                Visibility::Draft
+                            } else {
+                                Visibility::Public
+                            },
+                            sort: sort_by,
+                            facets,
+                            cursor,
                         },
-                        sort: sort_by,
-                    })
+                    )
                     .await
             }
-            Find::Tag { name } => {
+            Find::Tag { name, category } => {
                 self.client
                     .find_tags(ProfileQuery {
                         pagination,
                         name,
                         exclude: Default::default(),
+                        category,
+                        cursor,
                     })
                     .await
             }
@@ -135,6 +188,8 @@ impl Client {
                         pagination,
                         name,
                         exclude: Default::default(),
+                        category: None,
+                        cursor,
                     })
                     .await
             }
@@ -164,6 +219,7 @@ impl Client {
                 Some(command) => self.me_rm(command).await,
                 None => self.client.delete_user(force).await,
             },
+            Me::Token { command } => self.me_token(command).await,
         }
     }
 
@@ -180,8 +236,21 @@ impl Client {
         }
     }
 
+    async fn me_token(&self, command: MeToken) -> Result {
+        match command {
+            MeToken::Create {
+                scopes,
+                expires,
+                name,
+            } => self.client.create_token(name, scopes, expires).await,
+            MeToken::List => self.client.get_tokens().await,
+            MeToken::Revoke { id } => self.client.revoke_token(id).await,
+        }
+    }
+
     async fn cmd_new(&self, command: New) -> Result {
         match command {
+            New::Category { name } => self.client.add_tag_category(name).await,
             New::Comment { post, content } => {
                 self.client.add_comment(post, content).await
             }
@@ -191,29 +260,37 @@ impl Client {
                 draft,
                 tag,
                 post,
+                allow_duplicate,
+                archive,
                 objects,
             } => {
-                let objects = self.client.add_objects(objects).await?;
-                let objects = if objects.is_empty() {
-                    None
-                } else {
-                    Some(objects)
+                let mut parts = PostParts {
+                    title,
+                    description,
+                    visibility: if draft {
+                        Some(Visibility::Draft)
+                    } else {
+                        None
+                    },
+                    objects: None,
+                    posts: post,
+                    tags: tag,
                 };
 
-                self.client
-                    .create_post(PostParts {
-                        title,
-                        description,
-                        visibility: if draft {
-                            Some(Visibility::Draft)
-                        } else {
-                            None
-                        },
-                        objects,
-                        posts: post,
-                        tags: tag,
-                    })
-                    .await
+                if let Some(archive) = archive {
+                    return self
+                        .client
+                        .create_post_from_archive(archive, parts)
+                        .await;
+                }
+
+                let objects =
+                    self.client.add_objects(objects, allow_duplicate).await?;
+                if !objects.is_empty() {
+                    parts.objects = Some(objects);
+                }
+
+                self.client.create_post(parts).await
             }
             New::Tag { name } => self.client.add_tag(name).await,
         }
@@ -228,11 +305,21 @@ impl Client {
         match command {
             Object::Get {
                 no_clobber,
+                resume,
                 destination,
-            } => {
-                self.client
-                    .get_object_data(id, no_clobber, destination)
-                    .await
+            } => match destination {
+                Some(path) if resume => {
+                    self.client.resume_object_data(id, &path).await
+                }
+                destination => {
+                    self.client
+                        .get_object_data(id, no_clobber, destination)
+                        .await
+                }
+            },
+            Object::Preview => self.client.get_object_preview_status(id).await,
+            Object::Similar { distance } => {
+                self.client.get_similar_objects(id, distance).await
             }
         }
     }
@@ -240,6 +327,9 @@ impl Client {
     async fn objects(&self, command: Objects) -> Result {
         match command {
             Objects::Errors => self.client.get_object_preview_errors().await,
+            Objects::Hash { hash } => {
+                self.client.get_objects_by_hash(&hash).await
+            }
         }
     }
 
@@ -255,8 +345,13 @@ impl Client {
             }
             Post::Obj {
                 destination,
+                allow_duplicate,
                 objects,
-            } => self.client.add_post_objects(id, objects, destination).await,
+            } => {
+                self.client
+                    .add_post_objects(id, objects, destination, allow_duplicate)
+                    .await
+            }
             Post::Ln { posts } => {
                 self.client.add_related_posts(id, posts).await
             }
@@ -298,10 +393,21 @@ impl Client {
 
         match command {
             Tag::Aka { alias } => self.client.add_tag_alias(id, alias).await,
+            Tag::Category { category } => {
+                self.client.set_tag_category(id, Some(&category)).await
+            }
             Tag::Desc { description } => {
                 self.client.set_tag_description(id, description).await
             }
+            Tag::Implies { tags } => {
+                if tags.is_empty() {
+                    self.client.get_tag_implications(id).await
+                } else {
+                    self.client.add_tag_implications(id, tags).await
+                }
+            }
             Tag::Ln { url } => self.client.add_tag_source(id, &url).await,
+            Tag::Merge { other } => self.client.merge_tag(id, other).await,
             Tag::Rename { name } => self.client.set_tag_name(id, name).await,
             Tag::Rm { force, command } => match command {
                 Some(command) => self.tag_rm(id, command).await,
@@ -315,6 +421,10 @@ impl Client {
             TagRm::Alias { alias } => {
                 self.client.delete_tag_alias(id, alias).await
             }
+            TagRm::Category => self.client.set_tag_category(id, None).await,
+            TagRm::Implication { tags } => {
+                self.client.delete_tag_implications(id, tags).await
+            }
             TagRm::Link { sources } => {
                 if sources.is_empty() {
                     self.client.delete_tag_source(id).await
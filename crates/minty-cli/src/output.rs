@@ -13,6 +13,7 @@ mod search_result;
 mod tag;
 mod text;
 mod time;
+mod token;
 mod user;
 mod view;
 
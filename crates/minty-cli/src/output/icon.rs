@@ -12,6 +12,9 @@ impl Display for Icon {
 /// nf-md-account 󰀄
 pub const ACCOUNT: Icon = Icon('\u{f0004}');
 
+/// nf-md-aspect_ratio 󰨤
+pub const ASPECT_RATIO: Icon = Icon('\u{f0a24}');
+
 /// nf-oct-file_binary 
 pub const BINARY: Icon = Icon('\u{f471}');
 
@@ -33,6 +36,9 @@ pub const EMAIL: Icon = Icon('\u{f01ee}');
 /// nf-md-eye 󰈈
 pub const EYE: Icon = Icon('\u{f0208}');
 
+/// nf-md-filmstrip 󰈱
+pub const FILMSTRIP: Icon = Icon('\u{f0231}');
+
 /// nf-md-harddisk 󰋊
 pub const HARDDISK: Icon = Icon('\u{f02ca}');
 
@@ -48,8 +54,17 @@ pub const POUND: Icon = Icon('\u{f0423}');
 /// nf-md-seal 󰑺
 pub const SEAL: Icon = Icon('\u{f047a}');
 
+/// nf-md-speedometer 󰓅
+pub const SPEEDOMETER: Icon = Icon('\u{f04c5}');
+
 /// nf-md-tag 󰓹
 pub const TAG: Icon = Icon('\u{f04f9}');
 
+/// nf-md-timer_outline 󰔛
+pub const TIMER: Icon = Icon('\u{f051b}');
+
 /// nf-oct-trash 
 pub const TRASH: Icon = Icon('\u{f48e}');
+
+/// nf-md-volume_high 󰕾
+pub const VOLUME: Icon = Icon('\u{f057e}');
@@ -1,14 +1,22 @@
 use super::{
-    bytes::ByteSize, color, icon, metadata::Metadata, time::FormatDate,
+    bytes::ByteSize,
+    color, icon,
+    metadata::Metadata,
+    time::{self, FormatDate},
     HumanReadable,
 };
 
-use minty::{Object, ObjectError, ObjectPreview};
+use minty::{
+    DuplicateObject, MediaMetadata, Object, ObjectError, ObjectPreview,
+    PostPreview, PreviewStatus, SimilarObject, Storyboard,
+};
 use owo_colors::OwoColorize;
 use std::io::{Result, Write};
 
 impl HumanReadable for Object {
     fn human_readable<W: Write>(&self, w: &mut W, indent: usize) -> Result<()> {
+        let media = &self.metadata;
+
         writeln!(w, "Posts {}", self.posts.len().fg::<color::Result>())?;
 
         for (i, post) in self.posts.iter().enumerate() {
@@ -20,6 +28,12 @@ impl HumanReadable for Object {
         Metadata::new()
             .row("ID", icon::POUND, self.id)
             .optional_row("Preview", icon::IMAGE, self.preview_id)
+            .optional_row(
+                "Storyboard",
+                icon::FILMSTRIP,
+                self.storyboard.as_ref().map(storyboard),
+            )
+            .optional_row("Animation", icon::IMAGE, self.animation_id)
             .row("SHA256", icon::BINARY, self.hash.as_str())
             .row("Size", icon::HARDDISK, self.size.to_bytestring())
             .row(
@@ -28,7 +42,47 @@ impl HumanReadable for Object {
                 format!("{}/{}", self.r#type, self.subtype),
             )
             .row("Added", icon::CLOCK, self.added.long_date())
-            .print(indent, w)
+            .optional_row("Dimensions", icon::ASPECT_RATIO, dimensions(media))
+            .optional_row(
+                "Duration",
+                icon::TIMER,
+                media.duration_ms.map(time::duration),
+            )
+            .optional_row(
+                "Frame Rate",
+                icon::SPEEDOMETER,
+                media.frame_rate.map(|rate| format!("{rate} fps")),
+            )
+            .optional_row(
+                "Video",
+                icon::FILMSTRIP,
+                media.video_codec.as_deref(),
+            )
+            .optional_row("Audio", icon::VOLUME, audio(media))
+            .print(indent, w)?;
+
+        if self.previews.is_empty() {
+            return Ok(());
+        }
+
+        write!(w, "{:1$}", "", indent)?;
+        writeln!(w, "Previews {}", self.previews.len().fg::<color::Result>())?;
+
+        let width = self.previews.keys().map(String::len).max().unwrap_or(0);
+
+        for (name, id) in &self.previews {
+            let name = format!("{name:width$}");
+
+            write!(w, "{:1$}", "", indent)?;
+            writeln!(
+                w,
+                "  {}  {}",
+                name.fg::<color::Label>(),
+                id.fg::<color::Secodary>()
+            )?;
+        }
+
+        Ok(())
     }
 }
 
@@ -39,11 +93,32 @@ impl HumanReadable for ObjectError {
     }
 }
 
+impl HumanReadable for PreviewStatus {
+    fn human_readable<W: Write>(&self, w: &mut W, indent: usize) -> Result<()> {
+        Metadata::new()
+            .row("ID", icon::POUND, self.id)
+            .row("State", icon::IMAGE, self.state)
+            .row("Attempts", icon::TIMER, self.attempts)
+            .optional_row("Error", icon::DOCUMENT, self.error.as_deref())
+            .print(indent, w)
+    }
+}
+
 impl HumanReadable for ObjectPreview {
     fn human_readable<W: Write>(&self, w: &mut W, indent: usize) -> Result<()> {
         let media_type = format!("{}/{}", self.r#type, self.subtype);
 
-        writeln!(w, "{media_type}")?;
+        write!(w, "{media_type}")?;
+
+        if let Some(dimensions) = dimensions(&self.metadata) {
+            write!(w, " {}", dimensions.fg::<color::Secodary>())?;
+        }
+
+        if let Some(duration) = self.metadata.duration_ms {
+            write!(w, " {}", time::duration(duration).fg::<color::Secodary>())?;
+        }
+
+        writeln!(w)?;
 
         write!(w, "{:1$}", "", indent)?;
         writeln!(
@@ -56,3 +131,63 @@ impl HumanReadable for ObjectPreview {
         Ok(())
     }
 }
+
+impl HumanReadable for DuplicateObject {
+    fn human_readable<W: Write>(&self, w: &mut W, indent: usize) -> Result<()> {
+        self.object.human_readable(w, indent)?;
+        print_posts(w, &self.posts, indent)
+    }
+}
+
+impl HumanReadable for SimilarObject {
+    fn human_readable<W: Write>(&self, w: &mut W, indent: usize) -> Result<()> {
+        self.object.human_readable(w, indent)?;
+
+        write!(w, "{:1$}", "", indent)?;
+        writeln!(w, "Distance {}", self.distance.fg::<color::Result>())?;
+
+        print_posts(w, &self.posts, indent)
+    }
+}
+
+fn print_posts<W: Write>(
+    w: &mut W,
+    posts: &[PostPreview],
+    indent: usize,
+) -> Result<()> {
+    write!(w, "{:1$}", "", indent)?;
+    writeln!(w, "Posts {}", posts.len().fg::<color::Result>())?;
+
+    for (i, post) in posts.iter().enumerate() {
+        write!(w, "{:1$}", "", indent)?;
+        write!(w, "  {} ", (i + 1).fg::<color::Index>())?;
+        post.human_readable(w, indent + 4)?;
+    }
+
+    Ok(())
+}
+
+fn dimensions(metadata: &MediaMetadata) -> Option<String> {
+    let width = metadata.width?;
+    let height = metadata.height?;
+
+    Some(format!("{width} x {height}"))
+}
+
+fn audio(metadata: &MediaMetadata) -> Option<String> {
+    let codec = metadata.audio_codec.as_deref()?;
+
+    Some(match metadata.audio_channels {
+        Some(1) => format!("{codec} (mono)"),
+        Some(2) => format!("{codec} (stereo)"),
+        Some(channels) => format!("{codec} ({channels} channels)"),
+        None => codec.to_owned(),
+    })
+}
+
+fn storyboard(storyboard: &Storyboard) -> String {
+    format!(
+        "{} ({} frames, {} x {} grid)",
+        storyboard.id, storyboard.frames, storyboard.columns, storyboard.rows
+    )
+}
@@ -3,9 +3,12 @@ use super::{
     HumanReadable,
 };
 
-use minty::{Post, PostPreview};
+use minty::{Post, PostPreview, TagPreview};
 use owo_colors::OwoColorize;
-use std::io::{Result, Write};
+use std::{
+    collections::BTreeMap,
+    io::{Result, Write},
+};
 
 impl HumanReadable for Post {
     fn human_readable<W: Write>(&self, w: &mut W, indent: usize) -> Result<()> {
@@ -45,10 +48,18 @@ impl HumanReadable for Post {
             }
         }
 
-        if !self.tags.is_empty() {
-            writeln!(w, "Tags {}", self.tags.len().fg::<color::Result>())?;
+        let mut groups: BTreeMap<Option<&str>, Vec<&TagPreview>> =
+            BTreeMap::new();
+
+        for tag in &self.tags {
+            groups.entry(tag.category.as_deref()).or_default().push(tag);
+        }
+
+        for (category, tags) in groups {
+            let heading = category.unwrap_or("Tags");
+            writeln!(w, "{heading} {}", tags.len().fg::<color::Result>())?;
 
-            for tag in &self.tags {
+            for tag in tags {
                 write!(w, "  \u{eab6} ")?;
                 tag.human_readable(w, 4)?;
                 writeln!(w)?;
@@ -1,9 +1,22 @@
 use super::{color, HumanReadable, SliceExt};
 
-use minty::SearchResult;
+use minty::{PostSearchResult, SearchResult};
 use owo_colors::OwoColorize;
 use std::io::{Result, Write};
 
+impl HumanReadable for PostSearchResult {
+    fn human_readable<W: Write>(&self, w: &mut W, indent: usize) -> Result<()> {
+        self.posts.human_readable(w, indent)?;
+
+        if !self.facets.is_empty() {
+            writeln!(w)?;
+            self.facets.list().human_readable(w, indent)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl<T> HumanReadable for SearchResult<T>
 where
     T: HumanReadable,
@@ -26,6 +39,10 @@ where
             self.total.fg::<color::Result>(),
         )?;
 
+        if let Some(cursor) = &self.cursor {
+            writeln!(w, "Next: {cursor}")?;
+        }
+
         Ok(())
     }
 }
@@ -1,6 +1,6 @@
 use super::{color, icon, metadata::Metadata, time::FormatDate, HumanReadable};
 
-use minty::{Tag, TagPreview};
+use minty::{Tag, TagFacet, TagPreview};
 use owo_colors::OwoColorize;
 use std::io::{Result, Write};
 
@@ -10,15 +10,29 @@ impl HumanReadable for Tag {
 
         Metadata::new()
             .row("ID", icon::POUND, self.id)
+            .optional_row("Category", icon::TAG, self.category.as_deref())
             .row("Posts", icon::DOCUMENT, self.post_count)
             .row("Created", icon::CALENDAR, self.profile.created.long_date())
             .print(indent, w)
     }
 }
 
+impl HumanReadable for TagFacet {
+    fn human_readable<W: Write>(&self, w: &mut W, indent: usize) -> Result<()> {
+        write!(w, "{} ", self.count.fg::<color::Result>())?;
+        self.tag.human_readable(w, indent)
+    }
+}
+
 impl HumanReadable for TagPreview {
     fn human_readable<W: Write>(&self, w: &mut W, indent: usize) -> Result<()> {
-        writeln!(w, "{}", self.name.bold())?;
+        write!(w, "{}", self.name.bold())?;
+
+        if let Some(category) = &self.category {
+            write!(w, " {}", category.fg::<color::Secodary>())?;
+        }
+
+        writeln!(w)?;
 
         write!(w, "{:1$}", "", indent)?;
         writeln!(
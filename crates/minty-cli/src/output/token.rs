@@ -0,0 +1,34 @@
+use super::{icon, metadata::Metadata, time::FormatDate, HumanReadable};
+
+use minty::Token;
+use owo_colors::OwoColorize;
+use std::io::{Result, Write};
+
+impl HumanReadable for Token {
+    fn human_readable<W: Write>(&self, w: &mut W, indent: usize) -> Result<()> {
+        writeln!(w, "{}", self.name.bold())?;
+
+        let scopes = self
+            .scopes
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Metadata::new()
+            .row("ID", icon::POUND, self.id)
+            .row("Scopes", icon::SEAL, scopes)
+            .row("Created", icon::CALENDAR, self.created.long_date())
+            .optional_row(
+                "Expires",
+                icon::TIMER,
+                self.expiration.map(FormatDate::long_date),
+            )
+            .optional_row(
+                "Last used",
+                icon::CLOCK,
+                self.last_used.map(FormatDate::long_date),
+            )
+            .print(indent, w)
+    }
+}
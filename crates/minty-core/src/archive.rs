@@ -0,0 +1,282 @@
+use crate::{conf::ArchiveConfig, Error, Result};
+
+use bytes::Bytes;
+use futures::{TryStream, TryStreamExt};
+use std::{
+    error,
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    pin::pin,
+    result,
+};
+use tokio::{io::AsyncWriteExt, sync::mpsc, task};
+use zip::ZipArchive;
+
+const TAR_MAGIC: &[u8] = b"ustar";
+const TAR_MAGIC_OFFSET: usize = 257;
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+const ZIP_EMPTY_MAGIC: &[u8] = b"PK\x05\x06";
+
+pub fn is_hidden(name: &str) -> bool {
+    name.split('/')
+        .any(|part| part.starts_with('.') || part == "__MACOSX")
+}
+
+/// Bounds the amount of data decompressed from an archive.
+///
+/// Sizes recorded in archive headers are not trusted: entries are read until
+/// they end or exceed the remaining limit.
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    entry: u64,
+    total: u64,
+    remaining: u64,
+}
+
+impl Limits {
+    pub fn new(config: &ArchiveConfig) -> Self {
+        Self {
+            entry: config.max_entry_size,
+            total: config.max_total_size,
+            remaining: config.max_total_size,
+        }
+    }
+
+    pub fn read(
+        &mut self,
+        reader: impl Read,
+    ) -> result::Result<Vec<u8>, String> {
+        let limit = self.entry.min(self.remaining);
+
+        let mut data = Vec::new();
+        reader
+            .take(limit.saturating_add(1))
+            .read_to_end(&mut data)
+            .map_err(|err| format!("failed to read entry: {err}"))?;
+
+        let size = data.len() as u64;
+
+        if size > self.entry {
+            return Err(format!(
+                "entry exceeds size limit of {} bytes",
+                self.entry
+            ));
+        }
+
+        if size > self.remaining {
+            return Err(format!(
+                "archive exceeds total size limit of {} bytes",
+                self.total
+            ));
+        }
+
+        self.remaining -= size;
+        Ok(data)
+    }
+}
+
+#[derive(Debug)]
+pub struct Entry {
+    pub name: String,
+    pub data: result::Result<Bytes, String>,
+}
+
+struct TarEntry {
+    name: String,
+    offset: u64,
+    size: u64,
+}
+
+enum Archive {
+    Tar {
+        file: File,
+        entries: Vec<TarEntry>,
+    },
+    Zip {
+        archive: ZipArchive<File>,
+        names: Vec<String>,
+    },
+}
+
+impl Archive {
+    fn open(mut file: File) -> result::Result<Self, String> {
+        let len = TAR_MAGIC_OFFSET + TAR_MAGIC.len();
+        let mut header = Vec::with_capacity(len);
+
+        file.rewind()
+            .and_then(|_| {
+                file.by_ref().take(len as u64).read_to_end(&mut header)
+            })
+            .and_then(|_| file.rewind())
+            .map_err(|err| format!("failed to read archive: {err}"))?;
+
+        if header.starts_with(ZIP_MAGIC) || header.starts_with(ZIP_EMPTY_MAGIC)
+        {
+            Self::open_zip(file)
+        } else if header.get(TAR_MAGIC_OFFSET..) == Some(TAR_MAGIC) {
+            Self::open_tar(file)
+        } else {
+            Err("unsupported archive format; expected ZIP or TAR".into())
+        }
+    }
+
+    fn open_tar(file: File) -> result::Result<Self, String> {
+        let error =
+            |err: io::Error| format!("failed to read TAR archive: {err}");
+
+        let mut entries = Vec::new();
+
+        for entry in tar::Archive::new(&file)
+            .entries_with_seek()
+            .map_err(error)?
+        {
+            let entry = entry.map_err(error)?;
+
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+
+            let name = entry.path().map_err(error)?;
+            let name = name.to_string_lossy();
+
+            if is_hidden(&name) {
+                continue;
+            }
+
+            entries.push(TarEntry {
+                name: name.into_owned(),
+                offset: entry.raw_file_position(),
+                size: entry.size(),
+            });
+        }
+
+        entries.sort_by(|a, b| natord::compare(&a.name, &b.name));
+
+        Ok(Self::Tar { file, entries })
+    }
+
+    fn open_zip(file: File) -> result::Result<Self, String> {
+        let archive = ZipArchive::new(file)
+            .map_err(|err| format!("failed to read ZIP archive: {err}"))?;
+
+        let mut names: Vec<String> = archive
+            .file_names()
+            .filter(|name| !name.ends_with('/') && !is_hidden(name))
+            .map(String::from)
+            .collect();
+
+        names.sort_by(|a, b| natord::compare(a, b));
+
+        Ok(Self::Zip { archive, names })
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Tar { entries, .. } => entries.len(),
+            Self::Zip { names, .. } => names.len(),
+        }
+    }
+
+    fn read(&mut self, index: usize, limits: &mut Limits) -> Entry {
+        match self {
+            Self::Tar { file, entries } => {
+                let TarEntry { name, offset, size } = &entries[index];
+
+                let data = file
+                    .seek(SeekFrom::Start(*offset))
+                    .map_err(|err| format!("failed to read entry: {err}"))
+                    .and_then(|_| limits.read(file.by_ref().take(*size)))
+                    .map(Bytes::from);
+
+                Entry {
+                    name: name.clone(),
+                    data,
+                }
+            }
+            Self::Zip { archive, names } => {
+                let name = &names[index];
+
+                let data = archive
+                    .by_name(name)
+                    .map_err(|err| format!("failed to open entry: {err}"))
+                    .and_then(|file| limits.read(file))
+                    .map(Bytes::from);
+
+                Entry {
+                    name: name.clone(),
+                    data,
+                }
+            }
+        }
+    }
+}
+
+async fn spool<S>(stream: S) -> Result<File>
+where
+    S: TryStream + Send,
+    S::Error: Into<Box<dyn error::Error + Send + Sync>>,
+    Bytes: From<S::Ok>,
+{
+    let error = |err: io::Error| {
+        Error::Internal(format!("failed to buffer archive: {err}"))
+    };
+
+    let file = task::spawn_blocking(tempfile::tempfile)
+        .await
+        .map_err(|err| Error::Internal(err.to_string()))?
+        .map_err(error)?;
+
+    let mut file = tokio::fs::File::from_std(file);
+    let mut stream = pin!(stream.into_stream());
+
+    loop {
+        let chunk = match stream.try_next().await {
+            Ok(Some(chunk)) => Bytes::from(chunk),
+            Ok(None) => break,
+            Err(err) => {
+                let err: Box<dyn error::Error + Send + Sync> = err.into();
+                return Err(Error::InvalidInput(format!(
+                    "failed to read archive: {err}"
+                )));
+            }
+        };
+
+        file.write_all(&chunk).await.map_err(error)?;
+    }
+
+    file.flush().await.map_err(error)?;
+
+    Ok(file.into_std().await)
+}
+
+pub async fn entries<S>(
+    stream: S,
+    config: &ArchiveConfig,
+) -> Result<mpsc::Receiver<Entry>>
+where
+    S: TryStream + Send,
+    S::Error: Into<Box<dyn error::Error + Send + Sync>>,
+    Bytes: From<S::Ok>,
+{
+    let file = spool(stream).await?;
+
+    let mut archive = task::spawn_blocking(move || Archive::open(file))
+        .await
+        .map_err(|err| Error::Internal(err.to_string()))?
+        .map_err(Error::InvalidInput)?;
+
+    let mut limits = Limits::new(config);
+    let (tx, rx) = mpsc::channel(1);
+
+    task::spawn_blocking(move || {
+        for index in 0..archive.len() {
+            let entry = archive.read(index, &mut limits);
+
+            if tx.blocking_send(entry).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(rx)
+}
@@ -78,4 +78,5 @@ macro_rules! random_byte_array {
 
 byte_array!(Digest, 32);
 
+random_byte_array!(AccessToken, 32);
 random_byte_array!(SessionId, 32);
@@ -1,9 +1,23 @@
 use super::{Cache, Cached, Id, Result, User};
 
-use crate::db;
-
-use minty::{DateTime, ObjectPreview, Uuid};
-use std::sync::{Arc, RwLock};
+use crate::{db, preview::Preview};
+
+use minty::{
+    DateTime, MediaMetadata, ObjectPreview, PostPreview, Storyboard, Uuid,
+};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+
+#[derive(Clone, Debug)]
+struct PreviewData {
+    preview_id: Option<Uuid>,
+    previews: BTreeMap<String, Uuid>,
+    metadata: MediaMetadata,
+    storyboard: Option<Storyboard>,
+    animation_id: Option<Uuid>,
+}
 
 #[derive(Debug)]
 pub struct Object {
@@ -14,7 +28,7 @@ pub struct Object {
     pub subtype: String,
     pub extension: Option<String>,
     pub added: DateTime,
-    pub preview_id: Option<Uuid>,
+    preview: RwLock<PreviewData>,
     posts: RwLock<Vec<Uuid>>,
 }
 
@@ -28,18 +42,37 @@ impl Object {
             subtype: obj.subtype,
             extension: obj.extension,
             added: obj.added,
-            preview_id: data.preview_id,
+            preview: RwLock::new(PreviewData {
+                storyboard: data.storyboard(),
+                preview_id: data.preview_id,
+                previews: data.previews.0,
+                animation_id: data.animation_id,
+                metadata: data.metadata.map(|json| json.0).unwrap_or_default(),
+            }),
             posts: RwLock::new(data.posts),
         }
     }
 
+    pub fn update_preview(&self, preview: &Preview) {
+        *self.preview.write().unwrap() = PreviewData {
+            preview_id: preview.id,
+            previews: preview.variants.iter().cloned().collect(),
+            metadata: preview.metadata.clone(),
+            storyboard: preview
+                .storyboard_id
+                .zip(preview.storyboard)
+                .map(|(id, layout)| layout.storyboard(id)),
+            animation_id: preview.animation_id,
+        };
+    }
+
     pub async fn model(
         &self,
         cache: &Cache,
         user: Option<&Arc<Cached<User>>>,
     ) -> Result<minty::Object> {
-        let posts = self.posts.read().unwrap().clone();
-        let posts = cache.posts().previews(&posts, user).await?;
+        let posts = self.posts(cache, user).await?;
+        let preview = self.preview.read().unwrap().clone();
 
         Ok(minty::Object {
             id: self.id,
@@ -49,18 +82,34 @@ impl Object {
             subtype: self.subtype.clone(),
             extension: self.extension.clone(),
             added: self.added,
-            preview_id: self.preview_id,
+            preview_id: preview.preview_id,
+            previews: preview.previews,
+            metadata: preview.metadata,
+            storyboard: preview.storyboard,
+            animation_id: preview.animation_id,
             posts,
         })
     }
 
+    pub async fn posts(
+        &self,
+        cache: &Cache,
+        user: Option<&Arc<Cached<User>>>,
+    ) -> Result<Vec<PostPreview>> {
+        let posts = self.posts.read().unwrap().clone();
+        cache.posts().previews(&posts, user).await
+    }
+
     pub fn preview(&self) -> ObjectPreview {
+        let preview = self.preview.read().unwrap();
+
         ObjectPreview {
             id: self.id,
-            preview_id: self.preview_id,
+            preview_id: preview.preview_id,
             r#type: self.r#type.clone(),
             subtype: self.subtype.clone(),
             extension: self.extension.clone(),
+            metadata: preview.metadata.clone(),
         }
     }
 
@@ -94,6 +143,10 @@ impl<'a> Objects<'a> {
         Self { cache }
     }
 
+    pub fn get_cached(&self, id: Uuid) -> Option<Arc<Cached<Object>>> {
+        self.cache.objects.get_cached(id)
+    }
+
     pub async fn get(&self, id: Uuid) -> Result<Option<Arc<Cached<Object>>>> {
         self.cache
             .objects
@@ -1,6 +1,6 @@
 use super::{Cache, CacheLock, Cached, Comment, Id, Object, Result, Tag, User};
 
-use crate::{db, error::Found, Error};
+use crate::{db, error::Found, event::Audience, Error};
 
 use dashmap::DashMap;
 use minty::{CommentData, DateTime, PostPreview, Uuid, Visibility};
@@ -203,6 +203,18 @@ impl Post {
         })
     }
 
+    pub fn audience(&self) -> Option<Audience> {
+        let is_draft = self
+            .mutable
+            .map(|post| post.visibility == Visibility::Draft)?;
+
+        if !is_draft {
+            Some(Audience::Everyone)
+        } else {
+            self.poster.as_ref().map(|user| Audience::User(user.id))
+        }
+    }
+
     pub fn can_edit(&self, user: &Arc<Cached<User>>) -> Result<()> {
         let poster = self.poster.as_ref().map(|user| user.id);
 
@@ -680,6 +692,10 @@ impl<'a> Posts<'a> {
             .await
     }
 
+    pub fn get_cached(&self, id: Uuid) -> Option<Arc<Cached<Post>>> {
+        self.cache.posts.get_cached(id)
+    }
+
     async fn get_multiple(
         &self,
         ids: &[Uuid],
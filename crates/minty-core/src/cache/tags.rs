@@ -8,6 +8,7 @@ use std::sync::Arc;
 #[derive(Debug)]
 pub struct TagMut {
     pub profile: EntityProfile,
+    pub category: Option<String>,
     pub post_count: u32,
 }
 
@@ -35,6 +36,7 @@ impl Tag {
             creator,
             mutable: CacheLock::new(TagMut {
                 profile: tag.profile.into(),
+                category: tag.category,
                 post_count: tag.post_count,
             }),
         }
@@ -55,6 +57,7 @@ impl Tag {
             id: self.id,
             profile: tag.profile.clone(),
             creator: self.creator.as_ref().and_then(|user| user.preview()),
+            category: tag.category.clone(),
             post_count: tag.post_count,
         })
     }
@@ -64,6 +67,7 @@ impl Tag {
             id: self.id,
             name: tag.profile.name.clone(),
             avatar: tag.profile.avatar,
+            category: tag.category.clone(),
         })
     }
 
@@ -117,6 +121,10 @@ impl<'a> Tags<'a> {
             .await
     }
 
+    pub fn get_cached(&self, id: Uuid) -> Option<Arc<Cached<Tag>>> {
+        self.cache.tags.get_cached(id)
+    }
+
     pub async fn get_multiple(
         &self,
         ids: &[Uuid],
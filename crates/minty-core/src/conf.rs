@@ -7,7 +7,7 @@ pub use pgtools::{
 
 use minty::Url;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
 const DEFAULT_SQL_DIRECTORY: &str =
     match option_env!("MINTY_DEFAULT_SQL_DIRECTORY") {
@@ -51,19 +51,457 @@ pub struct BucketConfig {
     pub bucket: String,
 }
 
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewFit {
+    #[default]
+    Crop,
+    Contain,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewFormat {
+    #[default]
+    Jpeg,
+    Png,
+    Webp,
+}
+
+impl PreviewFormat {
+    pub fn magick(self) -> &'static str {
+        match self {
+            Self::Jpeg => "JPEG",
+            Self::Png => "PNG",
+            Self::Webp => "WEBP",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PreviewVariant {
+    pub size: u64,
+
+    #[serde(default)]
+    pub fit: PreviewFit,
+
+    #[serde(default)]
+    pub format: PreviewFormat,
+
+    #[serde(default = "PreviewVariant::default_quality")]
+    pub quality: u8,
+}
+
+impl PreviewVariant {
+    fn default_quality() -> u8 {
+        75
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StoryboardConfig {
+    #[serde(default = "StoryboardConfig::default_frames")]
+    pub frames: u32,
+
+    #[serde(default = "StoryboardConfig::default_columns")]
+    pub columns: u32,
+
+    #[serde(default = "StoryboardConfig::default_width")]
+    pub width: u64,
+
+    #[serde(default = "PreviewVariant::default_quality")]
+    pub quality: u8,
+}
+
+impl StoryboardConfig {
+    fn default_frames() -> u32 {
+        16
+    }
+
+    fn default_columns() -> u32 {
+        4
+    }
+
+    fn default_width() -> u64 {
+        160
+    }
+}
+
+impl Default for StoryboardConfig {
+    fn default() -> Self {
+        Self {
+            frames: Self::default_frames(),
+            columns: Self::default_columns(),
+            width: Self::default_width(),
+            quality: PreviewVariant::default_quality(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnimationFormat {
+    #[default]
+    Gif,
+    Webp,
+}
+
+impl AnimationFormat {
+    pub fn magick(self) -> &'static str {
+        match self {
+            Self::Gif => "GIF",
+            Self::Webp => "WEBP",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AnimationConfig {
+    #[serde(default = "AnimationConfig::default_frames")]
+    pub frames: u32,
+
+    #[serde(default = "AnimationConfig::default_size")]
+    pub size: u64,
+
+    #[serde(default = "AnimationConfig::default_delay_ms")]
+    pub delay_ms: u64,
+
+    #[serde(default)]
+    pub format: AnimationFormat,
+}
+
+impl AnimationConfig {
+    fn default_frames() -> u32 {
+        10
+    }
+
+    fn default_size() -> u64 {
+        320
+    }
+
+    fn default_delay_ms() -> u64 {
+        500
+    }
+}
+
+impl Default for AnimationConfig {
+    fn default() -> Self {
+        Self {
+            frames: Self::default_frames(),
+            size: Self::default_size(),
+            delay_ms: Self::default_delay_ms(),
+            format: Default::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ArchiveConfig {
+    #[serde(default = "ArchiveConfig::default_max_entry_size")]
+    pub max_entry_size: u64,
+
+    #[serde(default = "ArchiveConfig::default_max_total_size")]
+    pub max_total_size: u64,
+}
+
+impl ArchiveConfig {
+    fn default_max_entry_size() -> u64 {
+        256 * 1024 * 1024
+    }
+
+    fn default_max_total_size() -> u64 {
+        4 * 1024 * 1024 * 1024
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_entry_size == 0 || self.max_total_size == 0 {
+            return Err("archive size limits must be nonzero".into());
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            max_entry_size: Self::default_max_entry_size(),
+            max_total_size: Self::default_max_total_size(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SvgConfig {
+    #[serde(default = "SvgConfig::default_max_file_size")]
+    pub max_file_size: u64,
+
+    #[serde(default = "SvgConfig::default_max_nodes")]
+    pub max_nodes: u32,
+
+    #[serde(default = "SvgConfig::default_max_dimension")]
+    pub max_dimension: u32,
+}
+
+impl SvgConfig {
+    fn default_max_file_size() -> u64 {
+        5 * 1024 * 1024
+    }
+
+    fn default_max_nodes() -> u32 {
+        100_000
+    }
+
+    fn default_max_dimension() -> u32 {
+        2048
+    }
+}
+
+impl Default for SvgConfig {
+    fn default() -> Self {
+        Self {
+            max_file_size: Self::default_max_file_size(),
+            max_nodes: Self::default_max_nodes(),
+            max_dimension: Self::default_max_dimension(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PreviewQueueConfig {
+    #[serde(default = "PreviewQueueConfig::default_workers")]
+    pub workers: usize,
+
+    #[serde(default = "PreviewQueueConfig::default_max_attempts")]
+    pub max_attempts: u32,
+
+    #[serde(default = "PreviewQueueConfig::default_retry_delay")]
+    pub retry_delay_seconds: u32,
+
+    #[serde(default = "PreviewQueueConfig::default_max_retry_delay")]
+    pub max_retry_delay_seconds: u32,
+
+    #[serde(default = "PreviewQueueConfig::default_lease")]
+    pub lease_seconds: u32,
+
+    #[serde(default = "PreviewQueueConfig::default_poll_interval")]
+    pub poll_interval_ms: u64,
+
+    #[serde(default = "PreviewQueueConfig::default_priorities")]
+    pub priorities: BTreeMap<String, i16>,
+}
+
+impl PreviewQueueConfig {
+    fn default_workers() -> usize {
+        2
+    }
+
+    fn default_max_attempts() -> u32 {
+        5
+    }
+
+    fn default_retry_delay() -> u32 {
+        30
+    }
+
+    fn default_max_retry_delay() -> u32 {
+        3600
+    }
+
+    fn default_lease() -> u32 {
+        3600
+    }
+
+    fn default_poll_interval() -> u64 {
+        5000
+    }
+
+    fn default_priorities() -> BTreeMap<String, i16> {
+        BTreeMap::from([("image".into(), 2), ("audio".into(), 1)])
+    }
+
+    pub fn priority(&self, r#type: &str) -> i16 {
+        self.priorities.get(r#type).copied().unwrap_or_default()
+    }
+
+    pub fn retry_delay(&self, attempts: u32) -> u32 {
+        let exponent = attempts.saturating_sub(1).min(31);
+
+        self.retry_delay_seconds
+            .saturating_mul(1 << exponent)
+            .min(self.max_retry_delay_seconds)
+    }
+}
+
+impl Default for PreviewQueueConfig {
+    fn default() -> Self {
+        Self {
+            workers: Self::default_workers(),
+            max_attempts: Self::default_max_attempts(),
+            retry_delay_seconds: Self::default_retry_delay(),
+            max_retry_delay_seconds: Self::default_max_retry_delay(),
+            lease_seconds: Self::default_lease(),
+            poll_interval_ms: Self::default_poll_interval(),
+            priorities: Self::default_priorities(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PreviewConfig {
+    #[serde(default = "PreviewConfig::default_default")]
+    pub default: String,
+
+    #[serde(default = "PreviewConfig::default_variants")]
+    pub variants: BTreeMap<String, PreviewVariant>,
+
+    #[serde(default = "PreviewConfig::default_storyboard")]
+    pub storyboard: Option<StoryboardConfig>,
+
+    #[serde(default = "PreviewConfig::default_animation")]
+    pub animation: Option<AnimationConfig>,
+
+    #[serde(default)]
+    pub svg: SvgConfig,
+
+    #[serde(default)]
+    pub queue: PreviewQueueConfig,
+}
+
+impl PreviewConfig {
+    fn default_default() -> String {
+        "thumbnail".into()
+    }
+
+    fn default_variants() -> BTreeMap<String, PreviewVariant> {
+        BTreeMap::from([(
+            Self::default_default(),
+            PreviewVariant {
+                size: 250,
+                fit: PreviewFit::Crop,
+                format: PreviewFormat::Jpeg,
+                quality: PreviewVariant::default_quality(),
+            },
+        )])
+    }
+
+    fn default_storyboard() -> Option<StoryboardConfig> {
+        Some(Default::default())
+    }
+
+    fn default_animation() -> Option<AnimationConfig> {
+        Some(Default::default())
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.variants.contains_key(&self.default) {
+            return Err(format!(
+                "default preview variant '{}' is not defined",
+                self.default
+            ));
+        }
+
+        for (name, variant) in &self.variants {
+            if variant.size == 0 {
+                return Err(format!(
+                    "preview variant '{name}' must have a nonzero size"
+                ));
+            }
+
+            if !(1..=100).contains(&variant.quality) {
+                return Err(format!(
+                    "preview variant '{name}' quality must be between 1 and 100"
+                ));
+            }
+        }
+
+        if let Some(storyboard) = &self.storyboard {
+            if storyboard.frames == 0
+                || storyboard.columns == 0
+                || storyboard.width == 0
+            {
+                return Err("storyboard frames, columns and width \
+                    must be nonzero"
+                    .into());
+            }
+
+            if !(1..=100).contains(&storyboard.quality) {
+                return Err(
+                    "storyboard quality must be between 1 and 100".into()
+                );
+            }
+        }
+
+        if let Some(animation) = &self.animation {
+            if animation.frames == 0 || animation.size == 0 {
+                return Err("animation frames and size must be nonzero".into());
+            }
+        }
+
+        if self.svg.max_file_size == 0
+            || self.svg.max_nodes == 0
+            || self.svg.max_dimension == 0
+        {
+            return Err("svg limits must be nonzero".into());
+        }
+
+        if self.queue.workers == 0
+            || self.queue.max_attempts == 0
+            || self.queue.lease_seconds == 0
+            || self.queue.poll_interval_ms == 0
+        {
+            return Err("preview queue workers, attempts, lease and poll \
+                interval must be nonzero"
+                .into());
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for PreviewConfig {
+    fn default() -> Self {
+        Self {
+            default: Self::default_default(),
+            variants: Self::default_variants(),
+            storyboard: Self::default_storyboard(),
+            animation: Self::default_animation(),
+            svg: Default::default(),
+            queue: Default::default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchBackend {
+    #[default]
+    Elasticsearch,
+    Postgres,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SearchConfig {
+    #[serde(default)]
+    pub backend: SearchBackend,
+
+    #[serde(flatten)]
+    pub elasticsearch: Option<ElasticsearchConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ElasticsearchConfig {
     pub node: Url,
 
     pub auth: SearchAuth,
 
     pub namespace: String,
 
-    #[serde(default = "SearchConfig::default_refresh")]
+    #[serde(default = "ElasticsearchConfig::default_refresh")]
     pub refresh: Refresh,
 }
 
-impl SearchConfig {
+impl ElasticsearchConfig {
     fn default_refresh() -> Refresh {
         Refresh::False
     }
@@ -83,6 +521,9 @@ impl From<SearchAuth> for elasticsearch::auth::Credentials {
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct RepoConfig {
+    #[serde(default)]
+    pub archive: ArchiveConfig,
+
     #[serde(default)]
     pub cache: Cache,
 
@@ -92,6 +533,9 @@ pub struct RepoConfig {
 
     pub objects: BucketConfig,
 
+    #[serde(default)]
+    pub preview: PreviewConfig,
+
     #[serde(default)]
     pub require_account: bool,
 
@@ -8,7 +8,8 @@ use crate::conf::DatabaseConfig;
 
 use core::time::Duration;
 use log::LevelFilter;
-use minty::model::export::Data;
+use minty::{model::export::Data, MediaMetadata};
+use serde_json::Value as JsonValue;
 use sqlx::{
     postgres::{
         PgConnectOptions as ConnectOptions, PgPoolOptions as PoolOptions,
@@ -19,18 +20,45 @@ use sqlx::{
 use sqlx_helper_macros::{database, transaction};
 
 database! {
+    claim_preview_job(lease_seconds: i32) -> Option<(Uuid, i16)>;
+
+    complete_preview_job(object_id: Uuid);
+
     create_comment(user_id: Uuid, post_id: Uuid, content: &str) -> Comment;
 
     create_entity_link(profile_id: Uuid, source_id: i64);
 
+    create_implied_post_tags() -> Vec<(Uuid, Vec<Uuid>)>;
+
     create_object(
         object_id: Uuid,
+        hash: &str,
+        media_type: &str,
+        media_subtype: &str,
         preview_id: Option<Uuid>,
         source_id: Option<i64>,
+        phash: Option<i64>,
+        variants: &[&str],
+        previews: &[Uuid],
+        metadata: Json<&MediaMetadata>,
+        storyboard_id: Option<Uuid>,
+        storyboard: Option<Json<&StoryboardLayout>>,
+        animation_id: Option<Uuid>,
     );
 
     create_object_preview_error(object_id: Uuid, message: &str);
 
+    create_pending_object(
+        object_id: Uuid,
+        hash: &str,
+        media_type: &str,
+        media_subtype: &str,
+        priority: i16,
+        uploader: Uuid,
+    );
+
+    create_preview_job(object_id: Uuid, priority: i16);
+
     create_related_post(post_id: Uuid, related: Uuid) -> (Vec<Uuid>,);
 
     create_reply(
@@ -43,18 +71,40 @@ database! {
 
     create_source(site_id: i64, resource: &str) -> Source;
 
+    create_tag_category(name: &str) -> bool;
+
+    create_tag_implication(tag_id: Uuid, implied_tag_id: Uuid) -> bool;
+
     create_user_session(user_id: Uuid, session_id: &[u8], expiration: DateTime);
 
+    create_user_token(
+        user_id: Uuid,
+        name: &str,
+        digest: &[u8],
+        scopes: &[&str],
+        expiration: Option<DateTime>,
+    ) -> Token;
+
     delete_comment(id: Uuid, recursive: bool) -> bool;
 
     delete_entity_link(profile_id: Uuid, source_id: i64) -> bool;
 
     delete_related_post(post_id: Uuid, related: Uuid) -> (Option<Vec<Uuid>>,);
 
+    delete_tag_implication(tag_id: Uuid, implied_tag_id: Uuid) -> bool;
+
     delete_user_session(session_id: &[u8]);
 
+    delete_user_token(user_id: Uuid, token_id: Uuid) -> bool;
+
     export() -> (Json<Data>,);
 
+    fail_preview_job(
+        object_id: Uuid,
+        message: &str,
+        retry_seconds: Option<i32>,
+    );
+
     import(data: Json<&Data>);
 
     prune();
@@ -67,30 +117,52 @@ database! {
 
     read_object(object_id: Uuid) -> Option<Object>;
 
+    read_object_hash(object_id: Uuid) -> Option<(String,)>;
+
+    read_object_audience(object_id: Uuid) -> (bool, Vec<Uuid>);
+
+    read_object_post_search(
+        object_id: Uuid,
+    ) -> Vec<(Uuid, DateTime, Json<Vec<ObjectSearch>>)>;
+
     read_object_preview_errors() -> Vec<ObjectError>;
 
     read_object_total() -> i64;
 
     read_objects(objects: &[Uuid]) -> Vec<Object>;
 
+    read_objects_by_hash(hash: &str) -> Vec<(Uuid,)>;
+
     read_post(id: Uuid) -> Option<Post>;
 
     read_posts(posts: &[Uuid]) -> Vec<Post>;
 
+    read_similar_objects(object_id: Uuid, distance: i32) -> Vec<(Uuid, i32)>;
+
     read_post_search() -> Stream<PostSearch>;
 
     read_post_total() -> i64;
 
+    read_preview_status(object_id: Uuid) -> Option<PreviewStatus>;
+
     read_site(scheme: &str, host: &str) -> (Option<i64>,);
 
     read_tag(id: Uuid) -> Option<Tag>;
 
+    read_tag_categories() -> Vec<(String,)>;
+
+    read_tag_implications(tag_id: Uuid) -> (Vec<Uuid>,);
+
     read_tag_search() -> Stream<TagSearch>;
 
     read_tag_total() -> i64;
 
     read_tags(tags: &[Uuid]) -> Vec<Tag>;
 
+    read_tags_by_name(
+        names: &[&str]
+    ) -> Vec<(String, Option<String>, Vec<Uuid>)>;
+
     read_user(id: Uuid) -> Option<User>;
 
     read_user_password(email: &str) -> Option<Password>;
@@ -99,10 +171,51 @@ database! {
 
     read_user_session(session_id: &[u8]) -> Option<Session>;
 
+    read_user_token(digest: &[u8]) -> Option<Token>;
+
+    read_user_tokens(user_id: Uuid) -> Vec<Token>;
+
     read_user_total() -> i64;
 
     read_users(users: &[Uuid]) -> Vec<User>;
 
+    search_posts(
+        text: &str,
+        visibility: Visibility,
+        poster: Option<Uuid>,
+        tags: &[Uuid],
+        any_tags: Json<&[Vec<Uuid>]>,
+        exclude_tags: &[Uuid],
+        created_after: Option<DateTime>,
+        created_before: Option<DateTime>,
+        modified_after: Option<DateTime>,
+        modified_before: Option<DateTime>,
+        media: Option<&JsonValue>,
+        sort: &str,
+        ascending: bool,
+        after: Option<&JsonValue>,
+        from: i64,
+        size: i64,
+        facets: i64
+    ) -> PostSearchResult;
+
+    search_tags(
+        name: &str,
+        category: Option<&str>,
+        exclude: &[Uuid],
+        after: Option<&JsonValue>,
+        from: i64,
+        size: i64
+    ) -> ProfileSearchResult;
+
+    search_users(
+        name: &str,
+        exclude: &[Uuid],
+        after: Option<&JsonValue>,
+        from: i64,
+        size: i64
+    ) -> ProfileSearchResult;
+
     stream_objects() -> Stream<Object>;
 
     update_admin(user_id: Uuid, admin: bool) -> bool;
@@ -111,7 +224,20 @@ database! {
 
     update_entity_description(profile_id: Uuid, description: &str) -> bool;
 
-    update_object_preview(object_id: Uuid, preview_id: Option<Uuid>);
+    update_object_preview(
+        object_id: Uuid,
+        hash: &str,
+        media_type: &str,
+        media_subtype: &str,
+        preview_id: Option<Uuid>,
+        phash: Option<i64>,
+        variants: &[&str],
+        previews: &[Uuid],
+        metadata: Json<&MediaMetadata>,
+        storyboard_id: Option<Uuid>,
+        storyboard: Option<Json<&StoryboardLayout>>,
+        animation_id: Option<Uuid>,
+    );
 
     update_user_email(user_id: Uuid, email: &str) -> bool;
 
@@ -137,7 +263,7 @@ transaction! {
         destination: Option<Uuid>,
     ) -> PostObjects;
 
-    create_post_tag(post_id: Uuid, tag_id: Uuid);
+    create_post_tag(post_id: Uuid, tag_id: Uuid) -> (Vec<Uuid>,);
 
     create_tag(name: &str, creator: Uuid) -> Tag;
 
@@ -153,10 +279,16 @@ transaction! {
 
     delete_post_tag(post_id: Uuid, tag_id: Uuid) -> bool;
 
+    delete_tag_category(name: &str) -> (Option<Vec<Uuid>>,);
+
+    merge_tag(tag_id: Uuid, other: Uuid) -> (Vec<Uuid>,);
+
     prune_objects() -> Vec<(Uuid,)>;
 
     publish_post(post_id: Uuid) -> (DateTime,);
 
+    read_post_objects_search(post_id: Uuid) -> (Json<Vec<ObjectSearch>>,);
+
     update_entity_name(profile_id: Uuid, name: &str) -> Option<ProfileNameUpdate>;
 
     update_post_description(
@@ -165,6 +297,8 @@ transaction! {
     ) -> Option<(DateTime,)>;
 
     update_post_title(post_id: Uuid, title: &str) -> Option<(DateTime,)>;
+
+    update_tag_category(tag_id: Uuid, category: Option<&str>) -> bool;
 }
 
 impl Database {
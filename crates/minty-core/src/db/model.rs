@@ -1,6 +1,8 @@
 pub use minty::{DateTime, Url, Uuid};
 
-use serde::Serialize;
+use minty::{MediaMetadata, Orientation, TokenScope};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 use sqlx::{
     encode::IsNull,
     error::BoxDynError,
@@ -8,8 +10,10 @@ use sqlx::{
         types::{PgRecordDecoder, PgRecordEncoder},
         PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef,
     },
+    types::Json,
     Decode, Encode, FromRow, Postgres, Type,
 };
+use std::collections::BTreeMap;
 
 pub trait Id {
     fn id(&self) -> Uuid;
@@ -60,6 +64,20 @@ pub struct Object {
     pub id: Uuid,
     pub preview_id: Option<Uuid>,
     pub posts: Vec<Uuid>,
+    pub previews: Json<BTreeMap<String, Uuid>>,
+    pub metadata: Option<Json<MediaMetadata>>,
+    pub storyboard_id: Option<Uuid>,
+    pub storyboard: Option<Json<StoryboardLayout>>,
+    pub animation_id: Option<Uuid>,
+}
+
+impl Object {
+    pub fn storyboard(&self) -> Option<minty::Storyboard> {
+        let id = self.storyboard_id?;
+        let layout = self.storyboard.as_ref()?;
+
+        Some(layout.0.storyboard(id))
+    }
 }
 
 impl<'r> Decode<'r, Postgres> for Object {
@@ -70,6 +88,11 @@ impl<'r> Decode<'r, Postgres> for Object {
             id: decoder.try_decode()?,
             preview_id: decoder.try_decode()?,
             posts: decoder.try_decode()?,
+            previews: decoder.try_decode()?,
+            metadata: decoder.try_decode()?,
+            storyboard_id: decoder.try_decode()?,
+            storyboard: decoder.try_decode()?,
+            animation_id: decoder.try_decode()?,
         })
     }
 }
@@ -101,6 +124,31 @@ impl PgHasArrayType for Object {
     }
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ObjectSearch {
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+
+    #[serde(
+        default,
+        rename = "subtype",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub media_subtype: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub orientation: Option<Orientation>,
+}
+
 #[derive(Clone, Debug, FromRow)]
 pub struct ObjectError {
     #[sqlx(rename = "object_id")]
@@ -117,6 +165,34 @@ impl From<ObjectError> for minty::ObjectError {
     }
 }
 
+#[derive(Debug, FromRow)]
+pub struct PreviewStatus {
+    #[sqlx(rename = "object_id")]
+    pub id: Uuid,
+    pub state: String,
+    #[sqlx(try_from = "i32")]
+    pub attempts: u32,
+    pub error: Option<String>,
+}
+
+impl From<PreviewStatus> for minty::PreviewStatus {
+    fn from(value: PreviewStatus) -> Self {
+        use minty::PreviewState::*;
+
+        Self {
+            id: value.id,
+            state: match value.state.as_str() {
+                "pending" => Pending,
+                "running" => Running,
+                "failed" => Failed,
+                _ => Done,
+            },
+            attempts: value.attempts,
+            error: value.error,
+        }
+    }
+}
+
 #[derive(Clone, Debug, FromRow)]
 pub struct Post {
     #[sqlx(rename = "post_id")]
@@ -137,7 +213,7 @@ pub struct Post {
 }
 
 impl Post {
-    pub fn search(&self) -> PostSearch {
+    pub fn search(&self, objects: Vec<ObjectSearch>) -> PostSearch {
         PostSearch {
             id: self.id,
             poster: self.poster,
@@ -147,6 +223,7 @@ impl Post {
             created: self.created,
             modified: self.modified,
             tags: self.tags.clone(),
+            objects,
         }
     }
 }
@@ -159,7 +236,6 @@ pub struct PostObjects {
 
 #[derive(Clone, Debug, FromRow, Serialize)]
 pub struct PostSearch {
-    #[serde(skip)]
     #[sqlx(rename = "post_id")]
     pub id: Uuid,
 
@@ -181,6 +257,9 @@ pub struct PostSearch {
     pub modified: DateTime,
 
     pub tags: Vec<Uuid>,
+
+    #[sqlx(json)]
+    pub objects: Vec<ObjectSearch>,
 }
 
 impl Id for PostSearch {
@@ -189,6 +268,22 @@ impl Id for PostSearch {
     }
 }
 
+#[derive(Clone, Debug, FromRow)]
+pub struct PostSearchResult {
+    pub total: i64,
+    pub hits: Vec<Uuid>,
+    pub cursor: Option<JsonValue>,
+    pub facet_tags: Vec<Uuid>,
+    pub facet_counts: Vec<i64>,
+}
+
+#[derive(Clone, Debug, FromRow)]
+pub struct ProfileSearchResult {
+    pub total: i64,
+    pub hits: Vec<Uuid>,
+    pub cursor: Option<JsonValue>,
+}
+
 #[derive(Clone, Debug, FromRow)]
 pub struct Session {
     pub user_id: Uuid,
@@ -262,6 +357,30 @@ impl From<Source> for minty::Source {
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct StoryboardLayout {
+    pub frames: u32,
+    pub columns: u32,
+    pub rows: u32,
+    pub frame_width: u32,
+    pub frame_height: u32,
+    pub interval_ms: u64,
+}
+
+impl StoryboardLayout {
+    pub fn storyboard(self, id: Uuid) -> minty::Storyboard {
+        minty::Storyboard {
+            id,
+            frames: self.frames,
+            columns: self.columns,
+            rows: self.rows,
+            frame_width: self.frame_width,
+            frame_height: self.frame_height,
+            interval_ms: self.interval_ms,
+        }
+    }
+}
+
 #[derive(Clone, Debug, FromRow)]
 pub struct Tag {
     #[sqlx(rename = "tag_id")]
@@ -269,6 +388,7 @@ pub struct Tag {
     #[sqlx(flatten)]
     pub profile: EntityProfile,
     pub creator: Option<Uuid>,
+    pub category: Option<String>,
     #[sqlx(try_from = "i32")]
     pub post_count: u32,
 }
@@ -303,10 +423,10 @@ pub struct ProfileNameUpdate {
 
 #[derive(Clone, Debug, FromRow, Serialize)]
 pub struct TagSearch {
-    #[serde(skip)]
     #[sqlx(rename = "tag_id")]
     pub id: Uuid,
     pub names: Vec<String>,
+    pub category: Option<String>,
 }
 
 impl Id for TagSearch {
@@ -315,6 +435,41 @@ impl Id for TagSearch {
     }
 }
 
+#[derive(Clone, Debug, FromRow)]
+pub struct Token {
+    #[sqlx(rename = "token_id")]
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub scopes: Vec<String>,
+    #[sqlx(rename = "date_created")]
+    pub created: DateTime,
+    pub expiration: Option<DateTime>,
+    pub last_used: Option<DateTime>,
+}
+
+impl Token {
+    pub fn scopes(&self) -> Vec<TokenScope> {
+        self.scopes
+            .iter()
+            .filter_map(|scope| scope.parse().ok())
+            .collect()
+    }
+}
+
+impl From<Token> for minty::Token {
+    fn from(value: Token) -> Self {
+        Self {
+            id: value.id,
+            scopes: value.scopes(),
+            name: value.name,
+            created: value.created,
+            expiration: value.expiration,
+            last_used: value.last_used,
+        }
+    }
+}
+
 #[derive(Clone, Debug, FromRow)]
 pub struct User {
     #[sqlx(rename = "user_id")]
@@ -347,7 +502,6 @@ impl From<User> for minty::User {
 
 #[derive(Clone, Debug, FromRow, Serialize)]
 pub struct UserSearch {
-    #[serde(skip)]
     #[sqlx(rename = "user_id")]
     pub id: Uuid,
     pub names: Vec<String>,
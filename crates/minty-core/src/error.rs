@@ -23,6 +23,9 @@ pub enum Error {
     #[error("permission denied")]
     Unauthorized,
 
+    #[error("requested range is not satisfiable for size {size}")]
+    RangeNotSatisfiable { size: u64 },
+
     #[error("SQL error: {0}")]
     Sql(#[from] sqlx::Error),
 
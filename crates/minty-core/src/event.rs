@@ -0,0 +1,103 @@
+use crate::cache::Post;
+
+use futures::{stream, Stream};
+use minty::{Event, Uuid};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio_util::sync::CancellationToken;
+
+const CAPACITY: usize = 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Audience {
+    Everyone,
+    User(Uuid),
+}
+
+impl Audience {
+    fn includes(self, user: Option<Uuid>) -> bool {
+        match self {
+            Self::Everyone => true,
+            Self::User(id) => user == Some(id),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Envelope {
+    event: Event,
+    audience: Audience,
+}
+
+#[derive(Debug)]
+pub struct EventBus {
+    sender: broadcast::Sender<Envelope>,
+    token: CancellationToken,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CAPACITY);
+
+        Self {
+            sender,
+            token: CancellationToken::new(),
+        }
+    }
+
+    pub fn send(&self, event: Event, audience: Audience) {
+        // Sending only fails when there are no subscribers.
+        let _ = self.sender.send(Envelope { event, audience });
+    }
+
+    pub fn send_all(&self, event: Event) {
+        self.send(event, Audience::Everyone);
+    }
+
+    pub fn send_post(&self, post: &Post, event: Event) {
+        if let Some(audience) = post.audience() {
+            self.send(event, audience);
+        }
+    }
+
+    pub fn close(&self) {
+        self.token.cancel();
+    }
+
+    pub fn subscribe(
+        &self,
+        user: Option<Uuid>,
+    ) -> impl Stream<Item = Event> + Send + 'static {
+        let receiver = self.sender.subscribe();
+        let token = self.token.clone();
+
+        stream::unfold(
+            (receiver, token),
+            move |(mut receiver, token)| async move {
+                loop {
+                    let result = tokio::select! {
+                        biased;
+
+                        _ = token.cancelled() => return None,
+                        result = receiver.recv() => result,
+                    };
+
+                    let event = match result {
+                        Ok(Envelope { event, audience }) => {
+                            if !audience.includes(user) {
+                                continue;
+                            }
+
+                            event
+                        }
+                        Err(RecvError::Lagged(skipped)) => {
+                            Event::Lagged { skipped }
+                        }
+                        Err(RecvError::Closed) => return None,
+                    };
+
+                    return Some((event, (receiver, token)));
+                }
+            },
+        )
+    }
+}
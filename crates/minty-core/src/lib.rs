@@ -1,9 +1,11 @@
 pub mod conf;
 
+mod archive;
 mod auth;
 mod cache;
 mod db;
 mod error;
+mod event;
 mod ico;
 mod model;
 mod obj;
@@ -12,10 +14,11 @@ mod repo;
 mod search;
 mod task;
 
-pub use auth::{Base64DecodeError, SessionId};
+pub use auth::{AccessToken, Base64DecodeError, SessionId};
 pub use cache::{Cached, Session, User};
 pub use error::{Error, Result};
-pub use model::{About, SessionInfo};
+pub use model::{About, ByteRange, ObjectInfo, SessionInfo, Token};
+pub use preview::{PreviewGenerator, Previews, StoryboardLayout};
 pub use repo::Repo;
 pub use task::Task;
 
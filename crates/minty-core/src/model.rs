@@ -1,14 +1,42 @@
-use crate::SessionId;
+use crate::{Cached, SessionId, User};
 
 use chrono::Duration;
-use minty::Uuid;
+use minty::{DateTime, TokenScope, Uuid};
 use serde::{Deserialize, Serialize};
+use std::{ops::Range, sync::Arc};
 
 #[derive(Clone, Copy, Debug, Serialize)]
 pub struct About {
     pub version: &'static str,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteRange {
+    From(u64),
+    Inclusive(u64, u64),
+    Suffix(u64),
+}
+
+impl ByteRange {
+    pub fn resolve(self, size: u64) -> Option<Range<u64>> {
+        let range = match self {
+            Self::From(start) => start..size,
+            Self::Inclusive(start, end) => {
+                start..end.saturating_add(1).min(size)
+            }
+            Self::Suffix(length) => size.saturating_sub(length)..size,
+        };
+
+        (range.start < range.end).then_some(range)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ObjectInfo {
+    pub hash: String,
+    pub added: DateTime,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Invitation {
     sub: Uuid,
@@ -30,3 +58,17 @@ pub struct SessionInfo {
     pub user_id: Uuid,
     pub max_age: Duration,
 }
+
+#[derive(Debug)]
+pub struct Token {
+    pub user: Arc<Cached<User>>,
+    pub scopes: Vec<TokenScope>,
+}
+
+impl Token {
+    pub fn allows(&self, scope: TokenScope) -> bool {
+        scope == TokenScope::Read
+            || self.scopes.contains(&TokenScope::Admin)
+            || self.scopes.contains(&scope)
+    }
+}
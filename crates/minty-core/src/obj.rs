@@ -1,11 +1,41 @@
-use crate::{conf::BucketConfig, Error, Result};
+use crate::{conf::BucketConfig, ByteRange, Error, ObjectInfo, Result};
 
 use bytes::Bytes;
 use fstore::{http::Client, Object, RemoveResult};
-use futures::Stream;
+use futures::{future, Stream, StreamExt, TryStreamExt};
 use futures_core::TryStream;
 use minty::{ObjectSummary, Uuid};
-use std::{error, io, result};
+use std::{error, io, ops::Range, result};
+
+fn slice_stream<S>(
+    stream: S,
+    range: Range<u64>,
+) -> impl Stream<Item = io::Result<Bytes>>
+where
+    S: Stream<Item = io::Result<Bytes>>,
+{
+    stream
+        .scan(0, move |position: &mut u64, chunk| {
+            let start = *position;
+
+            if start >= range.end {
+                return future::ready(None);
+            }
+
+            let chunk = chunk.map(|chunk| {
+                let len = chunk.len() as u64;
+                *position += len;
+
+                let from = range.start.saturating_sub(start).min(len);
+                let to = (range.end - start).min(len);
+
+                chunk.slice(from as usize..to as usize)
+            });
+
+            future::ready(Some(chunk))
+        })
+        .try_filter(|chunk| future::ready(!chunk.is_empty()))
+}
 
 #[derive(Clone, Debug)]
 pub struct Bucket {
@@ -42,6 +72,24 @@ impl Bucket {
         Ok(self.bucket.get_object(id).await?)
     }
 
+    pub async fn get_object_info(&self, id: Uuid) -> Result<ObjectInfo> {
+        let object =
+            self.bucket.get_object(id).await.map_err(|err| {
+                match err.kind() {
+                    fstore::ErrorKind::NotFound => Error::NotFound {
+                        entity: "object",
+                        id,
+                    },
+                    _ => err.into(),
+                }
+            })?;
+
+        Ok(ObjectInfo {
+            hash: object.hash,
+            added: object.added,
+        })
+    }
+
     pub async fn get_objects(
         &self,
         objects: &[Uuid],
@@ -86,6 +134,27 @@ impl Bucket {
         Ok((summary, stream))
     }
 
+    /// The fstore client cannot request part of an object, so the object is
+    /// streamed from its beginning and the bytes before the range are read
+    /// and discarded. Reading stops at the end of the range.
+    pub async fn get_object_range(
+        &self,
+        id: Uuid,
+        range: ByteRange,
+    ) -> Result<(
+        ObjectSummary,
+        Range<u64>,
+        impl Stream<Item = io::Result<Bytes>>,
+    )> {
+        let (summary, stream) = self.get_object_stream(id).await?;
+
+        let range = range
+            .resolve(summary.size)
+            .ok_or(Error::RangeNotSatisfiable { size: summary.size })?;
+
+        Ok((summary, range.clone(), slice_stream(stream, range)))
+    }
+
     pub async fn remove_objects(
         &self,
         objects: &[Uuid],
@@ -1,14 +1,141 @@
+mod archive;
 mod audio;
 mod image;
+mod svg;
 mod video;
 
-use crate::obj::Bucket;
+pub use crate::db::StoryboardLayout;
 
+use crate::{
+    conf::{ArchiveConfig, PreviewConfig},
+    obj::Bucket,
+};
+
+use bytes::Bytes;
 use fstore::Object;
-use minty::Uuid;
-use std::result;
+use minty::{MediaMetadata, Uuid};
+use std::{result, sync::Arc};
+use tokio::task;
+
+#[derive(Clone, Debug, Default)]
+pub struct Preview {
+    pub id: Option<Uuid>,
+    pub variants: Vec<(String, Uuid)>,
+    pub storyboard_id: Option<Uuid>,
+    pub storyboard: Option<StoryboardLayout>,
+    pub animation_id: Option<Uuid>,
+    pub hash: Option<u64>,
+    pub metadata: MediaMetadata,
+}
+
+impl Preview {
+    pub fn phash(&self) -> Option<i64> {
+        self.hash.map(|hash| hash as i64)
+    }
+
+    pub fn variants(&self) -> (Vec<&str>, Vec<Uuid>) {
+        self.variants
+            .iter()
+            .map(|(name, id)| (name.as_str(), *id))
+            .unzip()
+    }
+}
+
+pub type Result = result::Result<Preview, String>;
+
+#[derive(Debug, Default)]
+pub struct Previews {
+    pub images: Vec<(String, Bytes)>,
+    pub storyboard: Option<(Bytes, StoryboardLayout)>,
+    pub animation: Option<Bytes>,
+    pub hash: Option<u64>,
+    pub metadata: MediaMetadata,
+}
 
-pub type Result = result::Result<Option<Uuid>, String>;
+/// Creates previews for objects of the media types it supports.
+///
+/// When several generators support an object, the one with the highest
+/// priority is used. Ties go to the generator registered last, so custom
+/// generators take precedence over the built-in ones by default.
+pub trait PreviewGenerator: Send + Sync {
+    fn supports(&self, r#type: &str, subtype: &str) -> bool;
+
+    fn priority(&self) -> i32 {
+        0
+    }
+
+    /// Called on a blocking thread with the object's full contents.
+    fn generate(
+        &self,
+        data: Bytes,
+        config: &PreviewConfig,
+    ) -> result::Result<Previews, String>;
+}
+
+pub struct PreviewGenerators {
+    generators: Vec<Arc<dyn PreviewGenerator>>,
+}
+
+impl PreviewGenerators {
+    pub fn new(
+        archive: &ArchiveConfig,
+        extra: Vec<Box<dyn PreviewGenerator>>,
+    ) -> Self {
+        let mut generators: Vec<Arc<dyn PreviewGenerator>> = vec![
+            Arc::new(archive::ArchiveGenerator::new(archive)),
+            Arc::new(audio::AudioGenerator),
+            Arc::new(image::ImageGenerator),
+            Arc::new(svg::SvgGenerator),
+            Arc::new(video::VideoGenerator),
+        ];
+
+        generators.extend(extra.into_iter().map(Arc::from));
+
+        Self { generators }
+    }
+
+    fn find(
+        &self,
+        r#type: &str,
+        subtype: &str,
+    ) -> Option<&Arc<dyn PreviewGenerator>> {
+        self.generators
+            .iter()
+            .filter(|generator| generator.supports(r#type, subtype))
+            .fold(None, |best, generator| match best {
+                Some(best) if best.priority() > generator.priority() => {
+                    Some(best)
+                }
+                _ => Some(generator),
+            })
+    }
+
+    pub async fn generate(
+        &self,
+        bucket: &Bucket,
+        config: &PreviewConfig,
+        object: &Object,
+    ) -> Result {
+        let Some(generator) = self.find(&object.r#type, &object.subtype) else {
+            return Ok(Default::default());
+        };
+
+        let (_, bytes) = bucket
+            .get_object_bytes(object.id)
+            .await
+            .map_err(|err| format!("failed to retrieve object data: {err}"))?;
+
+        let generator = generator.clone();
+        let preview_config = config.clone();
+        let previews = task::spawn_blocking(move || {
+            generator.generate(bytes, &preview_config)
+        })
+        .await
+        .map_err(|err| err.to_string())??;
+
+        upload_previews(bucket, config, previews).await
+    }
+}
 
 pub struct Env {
     _image: image::Env,
@@ -22,11 +149,53 @@ impl Env {
     }
 }
 
-pub async fn generate_preview(bucket: &Bucket, object: &Object) -> Result {
-    match object.r#type.as_str() {
-        "audio" => audio::generate_preview(bucket, object).await,
-        "image" => image::generate_preview(bucket, object).await,
-        "video" => video::generate_preview(bucket, object).await,
-        _ => Ok(None),
+async fn upload_previews(
+    bucket: &Bucket,
+    config: &PreviewConfig,
+    previews: Previews,
+) -> Result {
+    let Previews {
+        images,
+        storyboard,
+        animation,
+        hash,
+        metadata,
+    } = previews;
+
+    let mut preview = Preview {
+        hash,
+        metadata,
+        ..Default::default()
+    };
+
+    for (name, image) in images {
+        let object = bucket.add_object(image).await.map_err(|err| {
+            format!("failed to upload '{name}' preview to bucket: {err}")
+        })?;
+
+        if name == config.default {
+            preview.id = Some(object.id);
+        }
+
+        preview.variants.push((name, object.id));
+    }
+
+    if let Some((image, layout)) = storyboard {
+        let object = bucket.add_object(image).await.map_err(|err| {
+            format!("failed to upload storyboard to bucket: {err}")
+        })?;
+
+        preview.storyboard_id = Some(object.id);
+        preview.storyboard = Some(layout);
     }
+
+    if let Some(image) = animation {
+        let object = bucket.add_object(image).await.map_err(|err| {
+            format!("failed to upload animated preview to bucket: {err}")
+        })?;
+
+        preview.animation_id = Some(object.id);
+    }
+
+    Ok(preview)
 }
@@ -0,0 +1,166 @@
+use super::{
+    image::{self, Image},
+    PreviewGenerator, Previews,
+};
+
+use crate::{
+    archive::{is_hidden, Limits},
+    conf::{ArchiveConfig, PreviewConfig},
+};
+
+use bytes::Bytes;
+use log::debug;
+use roxmltree::Document;
+use std::{io::Cursor, path::Path, result};
+use zip::ZipArchive;
+
+const IMAGE_EXTENSIONS: &[&str] =
+    &["avif", "bmp", "gif", "jpeg", "jpg", "png", "webp"];
+
+const SUBTYPES: &[&str] = &["epub+zip", "vnd.comicbook+zip", "x-cbz", "zip"];
+
+type Archive = ZipArchive<Cursor<Bytes>>;
+
+fn is_image(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            IMAGE_EXTENSIONS
+                .iter()
+                .any(|image| extension.eq_ignore_ascii_case(image))
+        })
+}
+
+fn read_file(
+    archive: &mut Archive,
+    limits: &mut Limits,
+    name: &str,
+) -> result::Result<Vec<u8>, String> {
+    let file = archive
+        .by_name(name)
+        .map_err(|err| format!("failed to open '{name}' in archive: {err}"))?;
+
+    limits.read(file).map_err(|err| format!("'{name}': {err}"))
+}
+
+fn read_xml(
+    archive: &mut Archive,
+    limits: &mut Limits,
+    name: &str,
+) -> Option<String> {
+    let data = read_file(archive, limits, name)
+        .inspect_err(|err| debug!("{err}"))
+        .ok()?;
+
+    String::from_utf8(data).ok()
+}
+
+fn resolve(base: &str, href: &str) -> String {
+    let mut parts: Vec<&str> = base.split('/').collect();
+    parts.pop();
+
+    for part in href.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+
+    parts.join("/")
+}
+
+fn epub_cover(archive: &mut Archive, limits: &mut Limits) -> Option<String> {
+    let container = read_xml(archive, limits, "META-INF/container.xml")?;
+    let container = Document::parse(&container).ok()?;
+    let path = container
+        .descendants()
+        .find(|node| node.has_tag_name("rootfile"))?
+        .attribute("full-path")?;
+
+    let package = read_xml(archive, limits, path)?;
+    let package = Document::parse(&package).ok()?;
+    let items = || {
+        package
+            .descendants()
+            .filter(|node| node.has_tag_name("item"))
+    };
+
+    let cover = items()
+        .find(|item| {
+            item.attribute("properties").is_some_and(|properties| {
+                properties
+                    .split_whitespace()
+                    .any(|property| property == "cover-image")
+            })
+        })
+        .or_else(|| {
+            let id = package
+                .descendants()
+                .find(|node| {
+                    node.has_tag_name("meta")
+                        && node.attribute("name") == Some("cover")
+                })?
+                .attribute("content")?;
+
+            items().find(|item| item.attribute("id") == Some(id))
+        })?;
+
+    Some(resolve(path, cover.attribute("href")?))
+}
+
+fn first_image(archive: &Archive) -> Option<String> {
+    archive
+        .file_names()
+        .filter(|name| is_image(name) && !is_hidden(name))
+        .min_by(|a, b| natord::compare(a, b))
+        .map(String::from)
+}
+
+pub struct ArchiveGenerator {
+    limits: Limits,
+}
+
+impl ArchiveGenerator {
+    pub fn new(config: &ArchiveConfig) -> Self {
+        Self {
+            limits: Limits::new(config),
+        }
+    }
+}
+
+impl PreviewGenerator for ArchiveGenerator {
+    fn supports(&self, r#type: &str, subtype: &str) -> bool {
+        r#type == "application" && SUBTYPES.contains(&subtype)
+    }
+
+    fn generate(
+        &self,
+        data: Bytes,
+        config: &PreviewConfig,
+    ) -> result::Result<Previews, String> {
+        let mut archive = ZipArchive::new(Cursor::new(data))
+            .map_err(|err| format!("failed to read archive: {err}"))?;
+
+        let mut limits = self.limits;
+
+        let Some(cover) = epub_cover(&mut archive, &mut limits)
+            .or_else(|| first_image(&archive))
+        else {
+            return Ok(Default::default());
+        };
+
+        let cover = read_file(&mut archive, &mut limits, &cover)?;
+        let image = Image::from_bytes(cover.into())
+            .map_err(|err| format!("failed to read cover image: {err}"))?;
+
+        Ok(Previews {
+            images: image::make_previews(&image, config)?,
+            hash: Some(image::perceptual_hash(&image)?),
+            ..Default::default()
+        })
+    }
+}
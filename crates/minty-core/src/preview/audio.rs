@@ -1,18 +1,19 @@
 use super::{
+    image::{self, Image},
     video::{FormatContext, IoContext, PacketHandle},
-    Bucket, Object, Result,
+    PreviewGenerator, Previews,
 };
 
+use crate::conf::PreviewConfig;
+
 use bytes::Bytes;
 use ffmpeg_sys_next::AVMediaType::AVMEDIA_TYPE_VIDEO;
 use log::debug;
 use std::result;
-use tokio::task;
-
-fn find_embedded_image(audio: Bytes) -> result::Result<Option<Bytes>, String> {
-    let io = IoContext::new(audio.as_ref())?;
-    let format = FormatContext::new(&io)?;
 
+fn find_embedded_image(
+    format: &FormatContext,
+) -> result::Result<Option<Bytes>, String> {
     let Some((stream, _)) = format.find_best_stream(AVMEDIA_TYPE_VIDEO)? else {
         debug!("Audio file does not contain a video stream");
         return Ok(None);
@@ -32,22 +33,41 @@ fn find_embedded_image(audio: Bytes) -> result::Result<Option<Bytes>, String> {
     Ok(None)
 }
 
-pub async fn generate_preview(bucket: &Bucket, object: &Object) -> Result {
-    let (_, bytes) = bucket
-        .get_object_bytes(object.id)
-        .await
-        .map_err(|err| format!("failed to retrieve audio data: {err}"))?;
+fn make_previews(
+    audio: Bytes,
+    config: &PreviewConfig,
+) -> result::Result<Previews, String> {
+    let io = IoContext::new(audio.as_ref())?;
+    let format = FormatContext::new(&io)?;
 
-    let Some(image) = task::spawn_blocking(move || find_embedded_image(bytes))
-        .await
-        .map_err(|err| err.to_string())??
-    else {
-        return Ok(None);
+    let mut previews = Previews {
+        metadata: format.metadata(),
+        ..Default::default()
     };
 
-    let object = bucket.add_object(image).await.map_err(|err| {
-        format!("failed to upload audio preview to bucket: {err}")
-    })?;
+    if let Some(cover) = find_embedded_image(&format)? {
+        let image = Image::from_bytes(cover)
+            .map_err(|err| format!("failed to read embedded image: {err}"))?;
 
-    Ok(Some(object.id))
+        previews.images = image::make_previews(&image, config)?;
+        previews.hash = Some(image::perceptual_hash(&image)?);
+    }
+
+    Ok(previews)
+}
+
+pub struct AudioGenerator;
+
+impl PreviewGenerator for AudioGenerator {
+    fn supports(&self, r#type: &str, _subtype: &str) -> bool {
+        r#type == "audio"
+    }
+
+    fn generate(
+        &self,
+        data: Bytes,
+        config: &PreviewConfig,
+    ) -> result::Result<Previews, String> {
+        make_previews(data, config)
+    }
 }
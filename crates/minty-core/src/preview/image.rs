@@ -2,15 +2,16 @@ mod magick;
 
 pub use magick::*;
 
-use super::{Bucket, Object, Result};
+use super::{PreviewGenerator, Previews};
+
+use crate::conf::{PreviewConfig, PreviewFit, PreviewVariant};
 
 use bytes::Bytes;
 use log::debug;
+use minty::MediaMetadata;
 use std::{cmp, result};
-use tokio::task;
 
-const THUMBNAIL_FORMAT: &str = "JPEG";
-const THUMBNAIL_SIZE: u64 = 250;
+const HASH_SIZE: u64 = 8;
 
 pub struct Env;
 
@@ -30,63 +31,148 @@ impl Drop for Env {
     }
 }
 
-pub fn make_thumbnail(image: &mut Image) -> result::Result<Bytes, String> {
+/// Computes a difference hash: each bit records whether a pixel is brighter
+/// than its right neighbor in a 9x8 grayscale version of the image.
+pub fn perceptual_hash(image: &Image) -> result::Result<u64, String> {
+    let mut image = image
+        .try_clone()
+        .map_err(|err| format!("failed to copy image: {err}"))?;
+
+    image
+        .scale(HASH_SIZE + 1, HASH_SIZE)
+        .map_err(|err| format!("failed to scale image for hashing: {err}"))?;
+
+    let pixels = image
+        .intensities()
+        .map_err(|err| format!("failed to read image pixels: {err}"))?;
+
+    let hash = pixels
+        .chunks_exact((HASH_SIZE + 1) as usize)
+        .flat_map(|row| row.windows(2))
+        .fold(0, |hash, pair| (hash << 1) | u64::from(pair[0] > pair[1]));
+
+    Ok(hash)
+}
+
+pub fn metadata(image: &Image) -> MediaMetadata {
+    MediaMetadata {
+        width: u32::try_from(image.width()).ok(),
+        height: u32::try_from(image.height()).ok(),
+        ..Default::default()
+    }
+}
+
+fn crop_to_square(image: &mut Image) -> result::Result<(), String> {
     let width = image.width();
     let height = image.height();
 
-    debug!("Image dimensions: {width} x {height}");
+    if width == height {
+        return Ok(());
+    }
+
+    let smaller = cmp::min(width, height);
+    let larger = cmp::max(width, height);
+    let offset = ((larger - smaller) / 2) as i64;
 
-    // Crop the image to a square if it is not a square already
-    if width != height {
-        let smaller = cmp::min(width, height);
-        let larger = cmp::max(width, height);
-        let offset = ((larger - smaller) / 2) as i64;
+    let crop = Geometry {
+        width: smaller,
+        height: smaller,
+        x: if smaller == height { offset } else { 0 },
+        y: if smaller == width { offset } else { 0 },
+    };
 
-        let crop = Geometry {
-            width: smaller,
-            height: smaller,
-            x: if smaller == height { offset } else { 0 },
-            y: if smaller == width { offset } else { 0 },
-        };
+    debug!("Crop image to {crop}");
 
-        debug!("Crop image to {crop}");
+    image
+        .crop(crop)
+        .map_err(|err| format!("failed to crop image to {crop}: {err}"))
+}
 
-        image
-            .crop(crop)
-            .map_err(|err| format!("failed to crop image to {crop}: {err}"))?;
+/// Returns the largest dimensions with the image's aspect ratio that fit
+/// within a square of the given size. Smaller images are not enlarged.
+pub fn contained_size(width: u64, height: u64, size: u64) -> (u64, u64) {
+    if width <= size && height <= size {
+        (width, height)
+    } else if width >= height {
+        (size, cmp::max(1, height * size / width))
+    } else {
+        (cmp::max(1, width * size / height), size)
     }
+}
+
+pub fn make_thumbnail(
+    image: &mut Image,
+    variant: &PreviewVariant,
+) -> result::Result<Bytes, String> {
+    let width = image.width();
+    let height = image.height();
+
+    debug!("Image dimensions: {width} x {height}");
+
+    let (width, height) = match variant.fit {
+        PreviewFit::Crop => {
+            crop_to_square(image)?;
+            (variant.size, variant.size)
+        }
+        PreviewFit::Contain => contained_size(width, height, variant.size),
+    };
 
     image
-        .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        .thumbnail(width, height)
         .map_err(|err| format!("failed to resize image: {err}"))?;
 
-    image.magick(THUMBNAIL_FORMAT);
+    let format = variant.format.magick();
+
+    image.magick(format);
+    image.quality(variant.quality);
 
     let thumbnail = image.bytes().map_err(|err| {
-        format!("failed to write image data as a {THUMBNAIL_FORMAT}: {err}")
+        format!("failed to write image data as a {format}: {err}")
     })?;
 
     Ok(thumbnail)
 }
 
-pub async fn generate_preview(bucket: &Bucket, object: &Object) -> Result {
-    let (_, bytes) = bucket
-        .get_object_bytes(object.id)
-        .await
-        .map_err(|err| format!("failed to retrieve image data: {err}"))?;
+pub fn make_previews(
+    image: &Image,
+    config: &PreviewConfig,
+) -> result::Result<Vec<(String, Bytes)>, String> {
+    config
+        .variants
+        .iter()
+        .map(|(name, variant)| {
+            let mut image = image
+                .try_clone()
+                .map_err(|err| format!("failed to copy image: {err}"))?;
+
+            let thumbnail = make_thumbnail(&mut image, variant)
+                .map_err(|err| format!("'{name}' preview: {err}"))?;
+
+            Ok((name.clone(), thumbnail))
+        })
+        .collect()
+}
 
-    let thumbnail = task::spawn_blocking(move || {
-        let mut image = Image::from_bytes(bytes)
-            .map_err(|err| format!("failed to read image data: {err}"))?;
+pub struct ImageGenerator;
 
-        make_thumbnail(&mut image)
-    })
-    .await
-    .map_err(|err| err.to_string())??;
+impl PreviewGenerator for ImageGenerator {
+    fn supports(&self, r#type: &str, _subtype: &str) -> bool {
+        r#type == "image"
+    }
 
-    let preview = bucket.add_object(thumbnail).await.map_err(|err| {
-        format!("failed to upload thumbnail to bucket: {err}")
-    })?;
+    fn generate(
+        &self,
+        data: Bytes,
+        config: &PreviewConfig,
+    ) -> result::Result<Previews, String> {
+        let image = Image::from_bytes(data)
+            .map_err(|err| format!("failed to read image data: {err}"))?;
 
-    Ok(Some(preview.id))
+        Ok(Previews {
+            images: make_previews(&image, config)?,
+            hash: Some(perceptual_hash(&image)?),
+            metadata: metadata(&image),
+            ..Default::default()
+        })
+    }
 }
@@ -80,6 +80,10 @@ pub struct Exception {
 
 impl Exception {
     unsafe fn new(info: ExceptionInfo) -> Self {
+        Self::from_info(&info)
+    }
+
+    unsafe fn from_info(info: &gm::ExceptionInfo) -> Self {
         let description = NonNull::new(info.description)
             .map(|description| {
                 let cstr = CStr::from_ptr(description.as_ptr());
@@ -255,6 +259,27 @@ impl Image {
         })
     }
 
+    pub fn new(width: u64, height: u64) -> Result<Self> {
+        let pixels = vec![0u8; (width * height * 3) as usize];
+
+        Self::from_raw_pixels(width, height, pixels.as_ptr())
+    }
+
+    pub fn try_clone(&self) -> Result<Self> {
+        let mut exception = ExceptionInfo::default();
+
+        let image = unsafe {
+            gm::CloneImage(self.handle.as_ptr(), 0, 0, 1, exception.as_ptr())
+        };
+
+        let handle = ImageHandle::new(image).ok_or(exception)?;
+
+        Ok(Self {
+            info: ImageInfo::new(),
+            handle,
+        })
+    }
+
     pub fn width(&self) -> u64 {
         self.handle().columns
     }
@@ -296,6 +321,73 @@ impl Image {
         Ok(())
     }
 
+    pub fn scale(&mut self, width: u64, height: u64) -> Result<()> {
+        let mut exception = ExceptionInfo::default();
+
+        let image = unsafe {
+            gm::ScaleImage(
+                self.handle.as_ptr(),
+                width,
+                height,
+                exception.as_ptr(),
+            )
+        };
+
+        self.handle = ImageHandle::new(image).ok_or(exception)?;
+        Ok(())
+    }
+
+    pub fn composite(&mut self, image: &Image, x: i64, y: i64) -> Result<()> {
+        let status = unsafe {
+            gm::CompositeImage(
+                self.handle.as_mut(),
+                gm::CompositeOperator_OverCompositeOp,
+                image.handle.as_ptr(),
+                x,
+                y,
+            )
+        };
+
+        if status == 0 {
+            return Err(unsafe {
+                Exception::from_info(&self.handle().exception)
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn intensities(&self) -> Result<Vec<u8>> {
+        let mut exception = ExceptionInfo::default();
+        let width = self.width();
+        let height = self.height();
+        let mut pixels = vec![0u8; (width * height) as usize];
+
+        let status = unsafe {
+            gm::DispatchImage(
+                self.handle.as_ptr(),
+                0,
+                0,
+                width,
+                height,
+                c"I".as_ptr(),
+                gm::StorageType_CharPixel,
+                pixels.as_mut_ptr().cast(),
+                exception.as_ptr(),
+            )
+        };
+
+        if status == 0 {
+            return Err(exception.into());
+        }
+
+        Ok(pixels)
+    }
+
+    pub fn quality(&mut self, quality: u8) {
+        unsafe { self.info.info.as_mut().quality = quality.into() };
+    }
+
     pub fn magick(&mut self, format: &str) {
         let magick = self.handle_mut().magick.as_mut_ptr();
 
@@ -338,3 +430,45 @@ impl Image {
         unsafe { self.handle.as_mut() }
     }
 }
+
+/// Writes the images as the frames of an animation, each shown for the given
+/// number of hundredths of a second. The animation loops indefinitely.
+pub fn animation(
+    images: &mut [Image],
+    format: &str,
+    delay: u64,
+) -> Result<Bytes> {
+    for image in images.iter_mut() {
+        let handle = image.handle_mut();
+        handle.delay = delay;
+        handle.iterations = 0;
+    }
+
+    for pair in images.windows(2) {
+        let previous = pair[0].handle.handle.as_ptr();
+        let next = pair[1].handle.handle.as_ptr();
+
+        unsafe {
+            (*previous).next = next;
+            (*next).previous = previous;
+        }
+    }
+
+    let result = match images.first_mut() {
+        Some(first) => {
+            first.magick(format);
+            first.bytes()
+        }
+        None => Ok(Bytes::new()),
+    };
+
+    // Each image handle owns only its own image, so the list must be
+    // unlinked before the images are destroyed.
+    for image in images.iter_mut() {
+        let handle = image.handle_mut();
+        handle.next = ptr::null_mut();
+        handle.previous = ptr::null_mut();
+    }
+
+    result
+}
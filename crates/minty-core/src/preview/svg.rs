@@ -0,0 +1,127 @@
+use super::{
+    image::{self, Image},
+    PreviewGenerator, Previews,
+};
+
+use crate::conf::{PreviewConfig, SvgConfig};
+
+use bytes::Bytes;
+use minty::MediaMetadata;
+use resvg::{
+    tiny_skia::{IntSize, Pixmap, Transform},
+    usvg::{ImageHrefResolver, Options, Tree},
+};
+use roxmltree::{Document, ParsingOptions};
+use std::result;
+
+fn parse(data: &[u8], config: &SvgConfig) -> result::Result<Tree, String> {
+    if data.len() as u64 > config.max_file_size {
+        return Err(format!(
+            "SVG file size exceeds limit of {} bytes",
+            config.max_file_size
+        ));
+    }
+
+    let text = std::str::from_utf8(data)
+        .map_err(|err| format!("SVG data is not valid UTF-8: {err}"))?;
+
+    let document = Document::parse_with_options(
+        text,
+        ParsingOptions {
+            allow_dtd: false,
+            nodes_limit: config.max_nodes,
+        },
+    )
+    .map_err(|err| format!("failed to parse SVG: {err}"))?;
+
+    // Only inline data URLs may be used for images; references to files
+    // or remote resources are dropped.
+    let options = Options {
+        resources_dir: None,
+        image_href_resolver: ImageHrefResolver {
+            resolve_data: ImageHrefResolver::default_data_resolver(),
+            resolve_string: Box::new(|_, _| None),
+        },
+        ..Default::default()
+    };
+
+    Tree::from_xmltree(&document, &options)
+        .map_err(|err| format!("failed to read SVG: {err}"))
+}
+
+fn raster_size(tree: &Tree, config: &PreviewConfig) -> Option<IntSize> {
+    let size = tree.size();
+    let width = size.width();
+    let height = size.height();
+
+    let target = config
+        .variants
+        .values()
+        .map(|variant| variant.size)
+        .max()
+        .unwrap_or_default() as f32;
+    let max = config.svg.max_dimension as f32;
+
+    let scale = (target / width.min(height)).min(max / width.max(height));
+
+    IntSize::from_wh(
+        ((width * scale).round() as u32).max(1),
+        ((height * scale).round() as u32).max(1),
+    )
+}
+
+fn rasterize(
+    tree: &Tree,
+    config: &PreviewConfig,
+) -> result::Result<Bytes, String> {
+    let size = raster_size(tree, config)
+        .ok_or_else(|| String::from("SVG has an invalid size"))?;
+
+    let mut pixmap = Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| String::from("failed to allocate SVG canvas"))?;
+
+    let transform = Transform::from_scale(
+        size.width() as f32 / tree.size().width(),
+        size.height() as f32 / tree.size().height(),
+    );
+
+    resvg::render(tree, transform, &mut pixmap.as_mut());
+
+    pixmap
+        .encode_png()
+        .map(Bytes::from)
+        .map_err(|err| format!("failed to encode rasterized SVG: {err}"))
+}
+
+pub struct SvgGenerator;
+
+impl PreviewGenerator for SvgGenerator {
+    fn supports(&self, r#type: &str, subtype: &str) -> bool {
+        r#type == "image" && subtype == "svg+xml"
+    }
+
+    fn priority(&self) -> i32 {
+        1
+    }
+
+    fn generate(
+        &self,
+        data: Bytes,
+        config: &PreviewConfig,
+    ) -> result::Result<Previews, String> {
+        let tree = parse(&data, &config.svg)?;
+        let image = Image::from_bytes(rasterize(&tree, config)?)
+            .map_err(|err| format!("failed to read rasterized SVG: {err}"))?;
+
+        Ok(Previews {
+            images: image::make_previews(&image, config)?,
+            hash: Some(image::perceptual_hash(&image)?),
+            metadata: MediaMetadata {
+                width: Some(tree.size().width().round() as u32),
+                height: Some(tree.size().height().round() as u32),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+}
@@ -15,20 +15,26 @@ pub use sws::*;
 
 use super::{
     image::{self, Image},
-    Bucket, Object, Result,
+    PreviewGenerator, Previews,
+};
+
+use crate::{
+    conf::{AnimationConfig, PreviewConfig, PreviewFormat, StoryboardConfig},
+    db::StoryboardLayout,
 };
 
 use bytes::Bytes;
 use ffmpeg_sys_next::{
-    self as ffmpeg, AVMediaType::AVMEDIA_TYPE_VIDEO, AVPixelFormat,
+    self as ffmpeg, AVMediaType::AVMEDIA_TYPE_VIDEO, AVPixelFormat, AVRational,
+    AV_NOPTS_VALUE,
 };
-use std::result;
-use tokio::task;
+use log::warn;
+use std::{cmp, result};
 
-fn make_image(
+fn frame_image(
     codec: &CodecContext,
     video_frame: &Frame,
-) -> result::Result<Bytes, String> {
+) -> result::Result<Image, String> {
     const PIXEL_FORMAT: AVPixelFormat = AVPixelFormat::AV_PIX_FMT_RGB24;
 
     let image_frame =
@@ -37,17 +43,30 @@ fn make_image(
     SwsContext::new(codec, PIXEL_FORMAT)?
         .scale(video_frame, image_frame.frame());
 
-    let mut image = Image::from_raw_pixels(
+    Image::from_raw_pixels(
         codec.width() as u64,
         codec.height() as u64,
         image_frame.pixels(),
     )
-    .map_err(|err| format!("failed to create image from video frame: {err}"))?;
+    .map_err(|err| format!("failed to create image from video frame: {err}"))
+}
+
+fn make_image(
+    codec: &CodecContext,
+    video_frame: &Frame,
+    config: &PreviewConfig,
+) -> result::Result<(Vec<(String, Bytes)>, u64), String> {
+    let image = frame_image(codec, video_frame)?;
+    let hash = image::perceptual_hash(&image)?;
+    let images = image::make_previews(&image, config)?;
 
-    image::make_thumbnail(&mut image)
+    Ok((images, hash))
 }
 
-fn find_preview_frame(video: Bytes) -> result::Result<Bytes, String> {
+fn find_preview_frame(
+    video: Bytes,
+    config: &PreviewConfig,
+) -> result::Result<Previews, String> {
     let io = IoContext::new(video.as_ref())?;
     let format = FormatContext::new(&io)?;
 
@@ -68,26 +87,212 @@ fn find_preview_frame(video: Bytes) -> result::Result<Bytes, String> {
         }
 
         if codec.decode(&packet, &mut frame)? && frame.is_key_frame() {
-            return make_image(&codec, &frame);
+            let (images, hash) = make_image(&codec, &frame, config)?;
+
+            return Ok(Previews {
+                images,
+                hash: Some(hash),
+                metadata: format.metadata(),
+                ..Default::default()
+            });
         }
     }
 
     Err("failed to obtain video frame for preview".into())
 }
 
-pub async fn generate_preview(bucket: &Bucket, object: &Object) -> Result {
-    let (_, bytes) = bucket
-        .get_object_bytes(object.id)
-        .await
-        .map_err(|err| format!("failed to retrieve video data: {err}"))?;
+/// Decodes `count` frames spaced evenly throughout the video, each taken
+/// from the middle of its interval.
+fn sample_frames(
+    video: &Bytes,
+    count: u32,
+    duration_ms: u64,
+) -> result::Result<Vec<Image>, String> {
+    let io = IoContext::new(video.as_ref())?;
+    let format = FormatContext::new(&io)?;
 
-    let image = task::spawn_blocking(move || find_preview_frame(bytes))
-        .await
-        .map_err(|err| err.to_string())??;
+    let (stream, codec) = format
+        .find_best_stream(AVMEDIA_TYPE_VIDEO)?
+        .ok_or_else(|| String::from("no video stream found"))?;
 
-    let object = bucket.add_object(image).await.map_err(|err| {
-        format!("failed to upload video thumbnail to bucket: {err}")
-    })?;
+    let codec = CodecContext::new(codec)?;
+    codec.copy_params(stream.codecpar)?;
+    codec.open()?;
+
+    let mut packet = PacketHandle::new()?;
+    let mut frame = Frame::new()?;
+    let mut images = Vec::with_capacity(count as usize);
+
+    let interval = duration_ms / u64::from(count);
+
+    for i in 0..u64::from(count) {
+        let position = (interval * i + interval / 2) as i64;
+        let mut timestamp = unsafe {
+            ffmpeg::av_rescale_q(
+                position,
+                AVRational { num: 1, den: 1000 },
+                stream.time_base,
+            )
+        };
+
+        if stream.start_time != AV_NOPTS_VALUE {
+            timestamp += stream.start_time;
+        }
+
+        format.seek(stream, timestamp)?;
+        codec.flush();
+
+        let mut decoded = false;
+
+        while let Some(packet) = format.read_frame(&mut packet)? {
+            if !packet.is_stream(stream)
+                || !codec.decode(&packet, &mut frame)?
+            {
+                continue;
+            }
+
+            decoded = true;
+
+            let pts = frame.best_effort_timestamp;
+            if pts == AV_NOPTS_VALUE || pts >= timestamp {
+                break;
+            }
+        }
+
+        if !decoded {
+            return Err(format!(
+                "failed to decode video frame at {position} ms"
+            ));
+        }
+
+        images.push(frame_image(&codec, &frame)?);
+    }
 
-    Ok(Some(object.id))
+    Ok(images)
+}
+
+fn make_storyboard(
+    frames: Vec<Image>,
+    config: &StoryboardConfig,
+    interval_ms: u64,
+) -> result::Result<(Bytes, StoryboardLayout), String> {
+    let first = frames
+        .first()
+        .ok_or_else(|| String::from("no frames for storyboard"))?;
+
+    let frame_width = config.width;
+    let frame_height =
+        cmp::max(1, first.height() * frame_width / first.width());
+
+    let count = frames.len() as u64;
+    let columns = cmp::min(u64::from(config.columns), count);
+    let rows = count.div_ceil(columns);
+
+    let mut storyboard = Image::new(columns * frame_width, rows * frame_height)
+        .map_err(|err| format!("failed to create storyboard image: {err}"))?;
+
+    for (i, mut frame) in frames.into_iter().enumerate() {
+        let i = i as u64;
+
+        frame
+            .thumbnail(frame_width, frame_height)
+            .map_err(|err| format!("failed to resize video frame: {err}"))?;
+
+        storyboard
+            .composite(
+                &frame,
+                ((i % columns) * frame_width) as i64,
+                ((i / columns) * frame_height) as i64,
+            )
+            .map_err(|err| {
+                format!("failed to add frame to storyboard: {err}")
+            })?;
+    }
+
+    storyboard.magick(PreviewFormat::Jpeg.magick());
+    storyboard.quality(config.quality);
+
+    let bytes = storyboard
+        .bytes()
+        .map_err(|err| format!("failed to write storyboard image: {err}"))?;
+
+    let layout = StoryboardLayout {
+        frames: count as u32,
+        columns: columns as u32,
+        rows: rows as u32,
+        frame_width: frame_width as u32,
+        frame_height: frame_height as u32,
+        interval_ms,
+    };
+
+    Ok((bytes, layout))
+}
+
+fn make_animation(
+    mut frames: Vec<Image>,
+    config: &AnimationConfig,
+) -> result::Result<Bytes, String> {
+    for frame in &mut frames {
+        let (width, height) =
+            image::contained_size(frame.width(), frame.height(), config.size);
+
+        frame
+            .thumbnail(width, height)
+            .map_err(|err| format!("failed to resize video frame: {err}"))?;
+    }
+
+    image::animation(&mut frames, config.format.magick(), config.delay_ms / 10)
+        .map_err(|err| format!("failed to write animated preview: {err}"))
+}
+
+fn make_previews(
+    video: Bytes,
+    config: &PreviewConfig,
+) -> result::Result<Previews, String> {
+    let mut previews = find_preview_frame(video.clone(), config)?;
+
+    let Some(duration) = previews.metadata.duration_ms else {
+        return Ok(previews);
+    };
+
+    if let Some(storyboard) = &config.storyboard {
+        let interval = duration / u64::from(storyboard.frames);
+
+        previews.storyboard =
+            sample_frames(&video, storyboard.frames, duration)
+                .and_then(|frames| {
+                    make_storyboard(frames, storyboard, interval)
+                })
+                .inspect_err(|err| {
+                    warn!("Failed to generate storyboard: {err}")
+                })
+                .ok();
+    }
+
+    if let Some(animation) = &config.animation {
+        previews.animation = sample_frames(&video, animation.frames, duration)
+            .and_then(|frames| make_animation(frames, animation))
+            .inspect_err(|err| {
+                warn!("Failed to generate animated preview: {err}")
+            })
+            .ok();
+    }
+
+    Ok(previews)
+}
+
+pub struct VideoGenerator;
+
+impl PreviewGenerator for VideoGenerator {
+    fn supports(&self, r#type: &str, _subtype: &str) -> bool {
+        r#type == "video"
+    }
+
+    fn generate(
+        &self,
+        data: Bytes,
+        config: &PreviewConfig,
+    ) -> result::Result<Previews, String> {
+        make_previews(data, config)
+    }
 }
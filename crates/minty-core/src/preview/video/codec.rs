@@ -93,6 +93,10 @@ impl<'a> CodecContext<'a> {
         Err(format!("error during decoding: {reason}"))
     }
 
+    pub fn flush(&self) {
+        unsafe { ffmpeg::avcodec_flush_buffers(self.as_ptr()) };
+    }
+
     pub fn width(&self) -> i32 {
         unsafe { self.ctx.as_ref().width }
     }
@@ -1,19 +1,37 @@
 use super::{
     error::{ToError, ToResult},
     ffmpeg::{
-        self, AVCodec, AVFormatContext, AVMediaType, AVStream,
-        AVERROR_DECODER_NOT_FOUND, AVERROR_EOF, AVERROR_STREAM_NOT_FOUND,
+        self, AVCodec, AVCodecID, AVFormatContext, AVMediaType, AVRational,
+        AVStream, AVERROR_DECODER_NOT_FOUND, AVERROR_EOF,
+        AVERROR_STREAM_NOT_FOUND, AVSEEK_FLAG_BACKWARD,
+        AV_DISPOSITION_ATTACHED_PIC, AV_TIME_BASE,
     },
     io::IoContext,
     packet::*,
 };
 
+use minty::{FrameRate, MediaMetadata};
 use std::{
+    ffi::CStr,
     marker::PhantomData,
     ops::{Deref, DerefMut},
-    ptr,
+    ptr, slice,
 };
 
+fn codec_name(id: AVCodecID) -> Option<String> {
+    let name = unsafe { ffmpeg::avcodec_get_name(id).as_ref()? };
+    let name = unsafe { CStr::from_ptr(name) };
+
+    Some(name.to_string_lossy().into_owned())
+}
+
+fn frame_rate(rate: AVRational) -> Option<FrameRate> {
+    let num = u32::try_from(rate.num).ok().filter(|num| *num > 0)?;
+    let den = u32::try_from(rate.den).ok().filter(|den| *den > 0)?;
+
+    Some(FrameRate { num, den })
+}
+
 pub struct FormatContext<'a> {
     handle: *mut AVFormatContext,
     _phantom: PhantomData<&'a IoContext>,
@@ -81,6 +99,83 @@ impl<'a> FormatContext<'a> {
         }
     }
 
+    pub fn streams(&self) -> impl Iterator<Item = &AVStream> {
+        let streams = if self.streams.is_null() {
+            &[]
+        } else {
+            unsafe {
+                slice::from_raw_parts(self.streams, self.nb_streams as usize)
+            }
+        };
+
+        streams
+            .iter()
+            .filter_map(|stream| unsafe { stream.as_ref() })
+    }
+
+    pub fn metadata(&self) -> MediaMetadata {
+        let mut metadata = MediaMetadata {
+            duration_ms: u64::try_from(self.duration)
+                .ok()
+                .filter(|duration| *duration > 0)
+                .map(|duration| duration * 1000 / u64::from(AV_TIME_BASE)),
+            ..Default::default()
+        };
+
+        for stream in self.streams() {
+            let Some(params) = (unsafe { stream.codecpar.as_ref() }) else {
+                continue;
+            };
+
+            let attached_pic =
+                stream.disposition & AV_DISPOSITION_ATTACHED_PIC as i32 != 0;
+
+            match params.codec_type {
+                AVMediaType::AVMEDIA_TYPE_VIDEO
+                    if !attached_pic && metadata.video_codec.is_none() =>
+                {
+                    metadata.width = u32::try_from(params.width).ok();
+                    metadata.height = u32::try_from(params.height).ok();
+                    metadata.frame_rate = frame_rate(stream.avg_frame_rate);
+                    metadata.video_codec = codec_name(params.codec_id);
+                }
+                AVMediaType::AVMEDIA_TYPE_AUDIO
+                    if metadata.audio_codec.is_none() =>
+                {
+                    metadata.audio_channels =
+                        u32::try_from(params.ch_layout.nb_channels)
+                            .ok()
+                            .filter(|channels| *channels > 0);
+                    metadata.audio_codec = codec_name(params.codec_id);
+                }
+                _ => (),
+            }
+        }
+
+        metadata
+    }
+
+    pub fn seek(
+        &self,
+        stream: &AVStream,
+        timestamp: i64,
+    ) -> Result<(), String> {
+        unsafe {
+            ffmpeg::av_seek_frame(
+                self.handle,
+                stream.index,
+                timestamp,
+                AVSEEK_FLAG_BACKWARD as i32,
+            )
+            .to_result()
+            .map_err(|err| {
+                format!("failed to seek to timestamp {timestamp}: {err}")
+            })?
+        };
+
+        Ok(())
+    }
+
     pub fn read_frame<'p>(
         &self,
         packet: &'p mut PacketHandle,
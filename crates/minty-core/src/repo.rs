@@ -6,9 +6,11 @@ mod entity;
 mod links;
 mod object;
 mod objects;
+mod preview_queue;
 mod sessions;
 mod task;
 mod tasks;
+mod tokens;
 
 pub use admin::Admin;
 pub use object::Object;
@@ -16,20 +18,24 @@ pub use objects::Objects;
 pub use optional_user::OptionalUser;
 pub use sessions::*;
 pub use tasks::Tasks;
+pub use tokens::Tokens;
 pub use with_user::WithUser;
 
 use entity::Entity;
 use links::Links;
+use preview_queue::PreviewQueue;
 
 use crate::{
     auth::Auth,
     cache::{self, Cache, Cached},
-    conf::RepoConfig,
+    conf::{ArchiveConfig, PreviewConfig, RepoConfig},
     db::{Database, Password},
     error::{Found, Result},
+    event::EventBus,
     ico::Favicons,
     model::Invitation,
     obj::Bucket,
+    preview::{PreviewGenerator, PreviewGenerators},
     search::Search,
     task::Task,
     About, Error, SessionInfo,
@@ -40,12 +46,17 @@ use minty::{export, Login, SignUp, Uuid};
 use std::{path::Path, result, sync::Arc};
 
 pub struct Repo {
+    archive: ArchiveConfig,
     auth: Auth,
     bucket: Bucket,
     cache: Cache,
     database: Database,
     db_support: pgtools::Database,
+    events: EventBus,
     favicons: Favicons,
+    generators: PreviewGenerators,
+    preview: PreviewConfig,
+    preview_queue: PreviewQueue,
     require_account: bool,
     require_invitation: bool,
     search: Search,
@@ -53,6 +64,16 @@ pub struct Repo {
 
 impl Repo {
     pub async fn new(config: &RepoConfig) -> result::Result<Self, String> {
+        Self::with_preview_generators(config, Vec::new()).await
+    }
+
+    pub async fn with_preview_generators(
+        config: &RepoConfig,
+        generators: Vec<Box<dyn PreviewGenerator>>,
+    ) -> result::Result<Self, String> {
+        config.archive.validate()?;
+        config.preview.validate()?;
+
         let database = Database::from_config(&config.database).await?;
         let db_support = pgtools::Database::new(
             crate::VERSION,
@@ -67,17 +88,23 @@ impl Repo {
         let bucket = Bucket::new(&config.objects).await?;
         let cache = Cache::new(database.clone(), bucket.clone(), &config.cache);
         let favicons = Favicons::new(bucket.clone());
+        let search = Search::new(&config.search, &database)?;
 
         Ok(Self {
+            archive: config.archive.clone(),
             auth: Auth::new(&config.jwt_secret),
             bucket,
             cache,
             database,
             db_support,
+            events: EventBus::new(),
             favicons,
+            generators: PreviewGenerators::new(&config.archive, generators),
+            preview: config.preview.clone(),
+            preview_queue: Default::default(),
             require_account: config.require_account,
             require_invitation: config.require_invitation,
-            search: Search::new(&config.search)?,
+            search,
         })
     }
 
@@ -231,15 +258,32 @@ impl Repo {
         Tasks::new(self)
     }
 
+    pub fn tokens(&self) -> Tokens {
+        Tokens::new(self)
+    }
+
     pub fn with_user(&self, user: Arc<Cached<cache::User>>) -> WithUser {
         WithUser::new(self, user)
     }
 
     pub async fn prepare(&self) -> result::Result<(), String> {
-        self.db_support.check_schema_version().await
+        self.db_support.check_schema_version().await?;
+        self.search
+            .check_indices()
+            .await
+            .map_err(|err| err.to_string())
+    }
+
+    pub fn start_preview_workers(self: &Arc<Self>) {
+        self.preview_queue.start(self);
+    }
+
+    pub fn close_events(&self) {
+        self.events.close();
     }
 
     pub async fn shutdown(&self) {
+        self.preview_queue.stop().await;
         self.database.close().await;
     }
 
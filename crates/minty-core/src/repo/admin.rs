@@ -1,7 +1,11 @@
+mod categories;
 mod objects;
+mod tag;
 mod user;
 
+pub use categories::Categories;
 pub use objects::Objects;
+pub use tag::Tag;
 pub use user::User;
 
 use crate::{cache, error::Found, Cached, Repo, Result};
@@ -22,10 +26,19 @@ impl<'a> Admin<'a> {
         Ok(Self { repo })
     }
 
+    pub fn categories(self) -> Categories<'a> {
+        Categories::new(self.repo)
+    }
+
     pub fn objects(self) -> Objects<'a> {
         Objects::new(self.repo)
     }
 
+    pub async fn tag(self, id: Uuid) -> Result<Tag<'a>> {
+        let tag = self.repo.cache.tags().get(id).await?.found("tag", id)?;
+        Ok(Tag::new(self.repo, tag))
+    }
+
     pub async fn user(self, id: Uuid) -> Result<User<'a>> {
         let user = self.repo.cache.users().get(id).await?.found("user", id)?;
         Ok(User::new(self.repo, user))
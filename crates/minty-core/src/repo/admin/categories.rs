@@ -0,0 +1,40 @@
+use crate::{Repo, Result};
+
+use minty::text::Category;
+
+pub struct Categories<'a> {
+    repo: &'a Repo,
+}
+
+impl<'a> Categories<'a> {
+    pub(super) fn new(repo: &'a Repo) -> Self {
+        Self { repo }
+    }
+
+    pub async fn add(&self, name: Category) -> Result<bool> {
+        Ok(self
+            .repo
+            .database
+            .create_tag_category(name.as_ref())
+            .await?)
+    }
+
+    pub async fn delete(&self, name: &str) -> Result<bool> {
+        let mut tx = self.repo.database.begin().await?;
+
+        let (Some(tags),) = tx.delete_tag_category(name).await? else {
+            return Ok(false);
+        };
+
+        for tag in tags {
+            self.repo.search.update_tag_category(tag, None).await?;
+
+            if let Some(tag) = self.repo.cache.tags().get_cached(tag) {
+                tag.update(|tag| tag.category = None);
+            }
+        }
+
+        tx.commit().await?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{cache, Cached, Error, Repo, Result};
+
+use minty::{Event, Uuid};
+use std::sync::Arc;
+
+pub struct Tag<'a> {
+    repo: &'a Repo,
+    tag: Arc<Cached<cache::Tag>>,
+}
+
+impl<'a> Tag<'a> {
+    pub(super) fn new(repo: &'a Repo, tag: Arc<Cached<cache::Tag>>) -> Self {
+        Self { repo, tag }
+    }
+
+    pub async fn add_implication(&self, implied: Uuid) -> Result<()> {
+        let created = self
+            .repo
+            .database
+            .create_tag_implication(self.tag.id, implied)
+            .await
+            .map_err(|err| {
+                err.as_database_error()
+                    .and_then(|e| e.constraint())
+                    .and_then(|constraint| match constraint {
+                        "tag_implication_tag_id_fkey" => {
+                            Some(Error::NotFound {
+                                entity: "tag",
+                                id: self.tag.id,
+                            })
+                        }
+                        "tag_implication_implied_tag_id_fkey" => {
+                            Some(Error::NotFound {
+                                entity: "tag",
+                                id: implied,
+                            })
+                        }
+                        _ => None,
+                    })
+                    .unwrap_or_else(|| err.into())
+            })?;
+
+        if created {
+            self.changed();
+            Ok(())
+        } else {
+            Err(Error::InvalidInput(
+                "tag implication would create a cycle".into(),
+            ))
+        }
+    }
+
+    pub async fn delete_implication(&self, implied: Uuid) -> Result<bool> {
+        let deleted = self
+            .repo
+            .database
+            .delete_tag_implication(self.tag.id, implied)
+            .await?;
+
+        if deleted {
+            self.changed();
+        }
+
+        Ok(deleted)
+    }
+
+    fn changed(&self) {
+        self.repo
+            .events
+            .send_all(Event::TagChanged { tag: self.tag.id });
+    }
+}
@@ -18,7 +18,7 @@ impl<'a> Entity<'a> {
         &self,
         alias: Name,
         entity: &'static str,
-        index: &Index,
+        index: Index,
     ) -> Result<ProfileName> {
         let alias = alias.as_ref();
         let mut tx = self.repo.database.begin().await?;
@@ -68,12 +68,12 @@ impl<'a> Entity<'a> {
     pub async fn delete(
         &self,
         entity: &'static str,
-        index: &Index,
+        index: Index,
     ) -> Result<()> {
         let mut tx = self.repo.database.begin().await?;
 
         tx.delete_entity(self.id).await?.found(entity, self.id)?;
-        index.delete_doc(self.id).await?;
+        self.repo.search.delete_entity(index, self.id).await?;
 
         tx.commit().await?;
         Ok(())
@@ -83,7 +83,7 @@ impl<'a> Entity<'a> {
         &self,
         alias: &str,
         entity: &'static str,
-        index: &Index,
+        index: Index,
     ) -> Result<ProfileName> {
         let mut tx = self.repo.database.begin().await?;
 
@@ -145,7 +145,7 @@ impl<'a> Entity<'a> {
         &self,
         new_name: Name,
         entity: &'static str,
-        index: &Index,
+        index: Index,
     ) -> Result<ProfileName> {
         let new_name = new_name.as_ref();
         let mut tx = self.repo.database.begin().await?;
@@ -158,7 +158,7 @@ impl<'a> Entity<'a> {
         if let Some(old_name) = update.old_name {
             self.repo
                 .search
-                .update_entity_name(self.id, &old_name, new_name, index)
+                .update_entity_name(index, self.id, &old_name, new_name)
                 .await?;
         }
 
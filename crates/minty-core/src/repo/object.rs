@@ -1,8 +1,9 @@
 use super::Repo;
 
-use crate::{preview, Error, Result};
+use crate::{Error, Result};
 
 use minty::Uuid;
+use sqlx::types::Json;
 
 pub struct Object<'a> {
     repo: &'a Repo,
@@ -17,13 +18,34 @@ impl<'a> Object<'a> {
     pub async fn regenerate_preview(&self) -> Result<Option<Uuid>> {
         let object = self.repo.bucket.get_object(self.id).await?;
 
-        match preview::generate_preview(&self.repo.bucket, &object).await {
+        let result = self
+            .repo
+            .generators
+            .generate(&self.repo.bucket, &self.repo.preview, &object)
+            .await;
+
+        match result {
             Ok(preview) => {
+                let (variants, previews) = preview.variants();
+
                 self.repo
                     .database
-                    .update_object_preview(object.id, preview)
+                    .update_object_preview(
+                        object.id,
+                        &object.hash,
+                        &object.r#type,
+                        &object.subtype,
+                        preview.id,
+                        preview.phash(),
+                        &variants,
+                        &previews,
+                        Json(&preview.metadata),
+                        preview.storyboard_id,
+                        preview.storyboard.as_ref().map(Json),
+                        preview.animation_id,
+                    )
                     .await?;
-                Ok(preview)
+                Ok(preview.id)
             }
             Err(message) => {
                 self.repo
@@ -1,9 +1,11 @@
 use super::Repo;
 
-use crate::{preview, Result};
+use crate::{Cached, Result, User};
 
 use log::error;
-use minty::ObjectPreview;
+use minty::{DuplicateObject, ObjectPreview, Uuid};
+use sqlx::types::Json;
+use std::sync::Arc;
 
 pub struct Objects<'a> {
     repo: &'a Repo,
@@ -18,13 +20,31 @@ impl<'a> Objects<'a> {
         &self,
         object: fstore::Object,
     ) -> Result<ObjectPreview> {
-        let result =
-            preview::generate_preview(&self.repo.bucket, &object).await;
-        let preview = result.as_ref().ok().cloned().flatten();
+        let result = self
+            .repo
+            .generators
+            .generate(&self.repo.bucket, &self.repo.preview, &object)
+            .await;
+        let preview = result.as_ref().ok().cloned().unwrap_or_default();
+        let (variants, previews) = preview.variants();
 
         self.repo
             .database
-            .create_object(object.id, preview, None)
+            .create_object(
+                object.id,
+                &object.hash,
+                &object.r#type,
+                &object.subtype,
+                preview.id,
+                None,
+                preview.phash(),
+                &variants,
+                &previews,
+                Json(&preview.metadata),
+                preview.storyboard_id,
+                preview.storyboard.as_ref().map(Json),
+                preview.animation_id,
+            )
             .await?;
 
         if let Err(preview_error) = result {
@@ -44,10 +64,67 @@ impl<'a> Objects<'a> {
 
         Ok(ObjectPreview {
             id: object.id,
-            preview_id: preview,
+            preview_id: preview.id,
             r#type: object.r#type,
             subtype: object.subtype,
             extension: object.extension,
+            metadata: preview.metadata,
         })
     }
+
+    pub(super) async fn add_pending(
+        &self,
+        object: fstore::Object,
+        uploader: Uuid,
+    ) -> Result<ObjectPreview> {
+        self.repo
+            .database
+            .create_pending_object(
+                object.id,
+                &object.hash,
+                &object.r#type,
+                &object.subtype,
+                self.repo.preview.queue.priority(&object.r#type),
+                uploader,
+            )
+            .await?;
+
+        self.repo.preview_queue.notify();
+
+        Ok(ObjectPreview {
+            id: object.id,
+            preview_id: None,
+            r#type: object.r#type,
+            subtype: object.subtype,
+            extension: object.extension,
+            metadata: Default::default(),
+        })
+    }
+
+    pub(super) async fn find_by_hash(
+        &self,
+        hash: &str,
+        user: Option<&Arc<Cached<User>>>,
+    ) -> Result<Vec<DuplicateObject>> {
+        let ids: Vec<Uuid> = self
+            .repo
+            .database
+            .read_objects_by_hash(hash)
+            .await?
+            .into_iter()
+            .map(|(id,)| id)
+            .collect();
+
+        let cache = &self.repo.cache;
+        let mut duplicates = Vec::with_capacity(ids.len());
+
+        for object in cache.objects().get_multiple(&ids).await? {
+            duplicates.push(DuplicateObject {
+                object: object.preview(),
+                posts: object.posts(cache, user).await?,
+            });
+        }
+
+        Ok(duplicates)
+    }
 }
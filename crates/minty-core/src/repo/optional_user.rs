@@ -1,5 +1,6 @@
 mod comment;
 mod object;
+mod objects;
 mod post;
 mod posts;
 mod tag;
@@ -9,6 +10,7 @@ mod users;
 
 pub use comment::*;
 pub use object::*;
+pub use objects::*;
 pub use post::*;
 pub use posts::*;
 pub use tag::*;
@@ -18,7 +20,8 @@ pub use users::*;
 
 use crate::{cache, error::Found, About, Cached, Repo, Result};
 
-use minty::Uuid;
+use futures::Stream;
+use minty::{Event, Uuid};
 use std::sync::Arc;
 
 pub struct OptionalUser<'a> {
@@ -42,6 +45,12 @@ impl<'a> OptionalUser<'a> {
         Comment::new(self.repo, id)
     }
 
+    pub fn events(self) -> impl Stream<Item = Event> + Send + 'static {
+        self.repo
+            .events
+            .subscribe(self.user.as_ref().map(|user| user.id))
+    }
+
     pub async fn post(self, id: Uuid) -> Result<Post<'a>> {
         let post = self.repo.cache.posts().get(id).await?.found("post", id)?;
         Post::new(self.repo, self.user, post)
@@ -55,6 +64,10 @@ impl<'a> OptionalUser<'a> {
         Object::new(self.repo, self.user, id)
     }
 
+    pub fn objects(self) -> Objects<'a> {
+        Objects::new(self.repo, self.user)
+    }
+
     pub async fn other(self, user: Uuid) -> Result<User> {
         let user = self
             .repo
@@ -67,9 +80,9 @@ impl<'a> OptionalUser<'a> {
         Ok(User::new(user))
     }
 
-    pub async fn tag(self, id: Uuid) -> Result<Tag> {
+    pub async fn tag(self, id: Uuid) -> Result<Tag<'a>> {
         let tag = self.repo.cache.tags().get(id).await?.found("tag", id)?;
-        Ok(Tag::new(tag))
+        Ok(Tag::new(self.repo, tag))
     }
 
     pub fn tags(self) -> Tags<'a> {
@@ -1,9 +1,14 @@
-use crate::{error::Found, Cached, Repo, Result, User};
+use crate::{
+    error::Found, ByteRange, Cached, Error, ObjectInfo, Repo, Result, User,
+};
 
 use bytes::Bytes;
 use futures::Stream;
-use minty::{ObjectSummary, Uuid};
-use std::{io, sync::Arc};
+use minty::{ObjectSummary, PreviewStatus, SimilarObject, Uuid};
+use std::{collections::HashMap, io, ops::Range, sync::Arc};
+
+const DEFAULT_SIMILARITY_DISTANCE: u32 = 10;
+const MAX_SIMILARITY_DISTANCE: u32 = 64;
 
 pub struct Object<'a> {
     repo: &'a Repo,
@@ -31,9 +36,97 @@ impl<'a> Object<'a> {
             .await
     }
 
+    pub async fn hash(&self) -> Result<String> {
+        if let Some(object) = self.repo.cache.objects().get_cached(self.id) {
+            return Ok(object.hash.clone());
+        }
+
+        if let Some((hash,)) =
+            self.repo.database.read_object_hash(self.id).await?
+        {
+            return Ok(hash);
+        }
+
+        Ok(self.info().await?.hash)
+    }
+
+    pub async fn info(&self) -> Result<ObjectInfo> {
+        if let Some(object) = self.repo.cache.objects().get_cached(self.id) {
+            return Ok(ObjectInfo {
+                hash: object.hash.clone(),
+                added: object.added,
+            });
+        }
+
+        self.repo.bucket.get_object_info(self.id).await
+    }
+
     pub async fn get_data(
         &self,
     ) -> Result<(ObjectSummary, impl Stream<Item = io::Result<Bytes>>)> {
         self.repo.bucket.get_object_stream(self.id).await
     }
+
+    pub async fn get_data_range(
+        &self,
+        range: ByteRange,
+    ) -> Result<(
+        ObjectSummary,
+        Range<u64>,
+        impl Stream<Item = io::Result<Bytes>>,
+    )> {
+        self.repo.bucket.get_object_range(self.id, range).await
+    }
+
+    pub async fn preview_status(&self) -> Result<PreviewStatus> {
+        self.repo
+            .database
+            .read_preview_status(self.id)
+            .await?
+            .map(Into::into)
+            .found("object", self.id)
+    }
+
+    pub async fn similar(
+        &self,
+        distance: Option<u32>,
+    ) -> Result<Vec<SimilarObject>> {
+        let distance = distance.unwrap_or(DEFAULT_SIMILARITY_DISTANCE);
+
+        if distance > MAX_SIMILARITY_DISTANCE {
+            return Err(Error::InvalidInput(format!(
+                "similarity distance cannot exceed {MAX_SIMILARITY_DISTANCE}"
+            )));
+        }
+
+        let cache = &self.repo.cache;
+        cache
+            .objects()
+            .get(self.id)
+            .await?
+            .found("object", self.id)?;
+
+        let distances: HashMap<Uuid, i32> = self
+            .repo
+            .database
+            .read_similar_objects(self.id, distance as i32)
+            .await?
+            .into_iter()
+            .collect();
+        let ids: Vec<Uuid> = distances.keys().copied().collect();
+
+        let mut similar = Vec::with_capacity(ids.len());
+
+        for object in cache.objects().get_multiple(&ids).await? {
+            similar.push(SimilarObject {
+                object: object.preview(),
+                distance: distances[&object.id] as u32,
+                posts: object.posts(cache, self.user.as_ref()).await?,
+            });
+        }
+
+        similar.sort_by_key(|similar| (similar.distance, similar.object.id));
+
+        Ok(similar)
+    }
 }
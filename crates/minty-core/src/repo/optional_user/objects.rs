@@ -0,0 +1,25 @@
+use crate::{Cached, Repo, Result, User};
+
+use minty::DuplicateObject;
+use std::sync::Arc;
+
+pub struct Objects<'a> {
+    repo: &'a Repo,
+    user: Option<Arc<Cached<User>>>,
+}
+
+impl<'a> Objects<'a> {
+    pub(super) fn new(repo: &'a Repo, user: Option<Arc<Cached<User>>>) -> Self {
+        Self { repo, user }
+    }
+
+    pub async fn find_by_hash(
+        &self,
+        hash: &str,
+    ) -> Result<Vec<DuplicateObject>> {
+        self.repo
+            .objects()
+            .find_by_hash(hash, self.user.as_ref())
+            .await
+    }
+}
@@ -1,7 +1,12 @@
-use crate::{cache::User, Cached, Error, Repo, Result};
+use super::Tags;
 
-use minty::{PostPreview, PostQuery, SearchResult, Visibility};
-use std::sync::Arc;
+use crate::{cache::User, search::SearchHits, Cached, Error, Repo, Result};
+
+use minty::{
+    query::PostTerms, PostQuery, PostSearchResult, SearchResult, TagFacet,
+    Uuid, Visibility,
+};
+use std::{collections::HashMap, sync::Arc};
 
 pub struct Posts<'a> {
     repo: &'a Repo,
@@ -13,10 +18,7 @@ impl<'a> Posts<'a> {
         Self { repo, user }
     }
 
-    pub async fn find(
-        &self,
-        mut query: PostQuery,
-    ) -> Result<SearchResult<PostPreview>> {
+    pub async fn find(&self, mut query: PostQuery) -> Result<PostSearchResult> {
         if query.visibility == Visibility::Draft {
             if let Some(user) = self.user.as_ref() {
                 query.poster = Some(user.id);
@@ -25,8 +27,12 @@ impl<'a> Posts<'a> {
             }
         }
 
-        let SearchResult { total, hits } =
-            self.repo.search.find_posts(&query).await?;
+        let SearchHits {
+            total,
+            hits,
+            tags,
+            cursor,
+        } = self.repo.search.find_posts(&query).await?;
 
         let hits = self
             .repo
@@ -35,6 +41,82 @@ impl<'a> Posts<'a> {
             .previews(&hits, self.user.as_ref())
             .await?;
 
-        Ok(SearchResult { total, hits })
+        let facets = if tags.is_empty() {
+            Vec::new()
+        } else {
+            let ids: Vec<Uuid> = tags.iter().map(|(id, _)| *id).collect();
+            let counts: HashMap<Uuid, u32> = tags.into_iter().collect();
+
+            self.repo
+                .cache
+                .tags()
+                .previews(&ids)
+                .await?
+                .into_iter()
+                .map(|tag| TagFacet {
+                    count: counts[&tag.id],
+                    tag,
+                })
+                .collect()
+        };
+
+        Ok(PostSearchResult {
+            posts: SearchResult {
+                total,
+                hits,
+                cursor,
+            },
+            facets,
+        })
+    }
+
+    pub async fn search(
+        &self,
+        terms: &str,
+        mut query: PostQuery,
+    ) -> Result<PostSearchResult> {
+        let categories = if terms.contains(':') {
+            Tags::new(self.repo).categories().await?
+        } else {
+            Vec::new()
+        };
+
+        let terms = PostTerms::parse(terms, &categories)
+            .map_err(|err| Error::InvalidInput(err.to_string()))?;
+
+        let names = terms.tag_names();
+        let mut tags: HashMap<String, Vec<(Option<String>, Vec<Uuid>)>> =
+            HashMap::new();
+
+        if !names.is_empty() {
+            for (name, category, ids) in
+                self.repo.database.read_tags_by_name(&names).await?
+            {
+                tags.entry(name).or_default().push((category, ids));
+            }
+        }
+
+        terms.apply(&mut query, |category, name| {
+            let ids: Vec<Uuid> = tags
+                .get(&name.to_lowercase())
+                .into_iter()
+                .flatten()
+                .filter(|(c, _)| category.is_none() || c.as_deref() == category)
+                .flat_map(|(_, ids)| ids.iter().copied())
+                .collect();
+
+            if !ids.is_empty() {
+                return Ok(ids);
+            }
+
+            Err(Error::InvalidInput(match category {
+                Some(category) => {
+                    format!("tag '{name}' not found in category '{category}'")
+                }
+                None => format!("tag '{name}' not found"),
+            }))
+        })?;
+
+        self.find(query).await
     }
 }
@@ -1,17 +1,29 @@
-use crate::{cache, error::Found, Cached, Result};
+use crate::{cache, error::Found, Cached, Repo, Result};
 
+use minty::TagPreview;
 use std::sync::Arc;
 
-pub struct Tag {
+pub struct Tag<'a> {
+    repo: &'a Repo,
     tag: Arc<Cached<cache::Tag>>,
 }
 
-impl Tag {
-    pub(super) fn new(tag: Arc<Cached<cache::Tag>>) -> Self {
-        Self { tag }
+impl<'a> Tag<'a> {
+    pub(super) fn new(repo: &'a Repo, tag: Arc<Cached<cache::Tag>>) -> Self {
+        Self { repo, tag }
     }
 
     pub fn get(&self) -> Result<minty::Tag> {
         self.tag.model().found("tag", self.tag.id)
     }
+
+    pub async fn implications(&self) -> Result<Vec<TagPreview>> {
+        let (tags,) = self
+            .repo
+            .database
+            .read_tag_implications(self.tag.id)
+            .await?;
+
+        self.repo.cache.tags().previews(&tags).await
+    }
 }
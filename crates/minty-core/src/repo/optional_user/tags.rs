@@ -1,4 +1,4 @@
-use crate::{Repo, Result};
+use crate::{search::Index, Repo, Result};
 
 use minty::{ProfileQuery, SearchResult, TagPreview, Uuid};
 
@@ -11,6 +11,17 @@ impl<'a> Tags<'a> {
         Self { repo }
     }
 
+    pub async fn categories(&self) -> Result<Vec<String>> {
+        Ok(self
+            .repo
+            .database
+            .read_tag_categories()
+            .await?
+            .into_iter()
+            .map(|(category,)| category)
+            .collect())
+    }
+
     pub async fn get(&self, tags: &[Uuid]) -> Result<Vec<TagPreview>> {
         self.repo.cache.tags().previews(tags).await
     }
@@ -19,14 +30,18 @@ impl<'a> Tags<'a> {
         &self,
         query: &ProfileQuery,
     ) -> Result<SearchResult<TagPreview>> {
-        let SearchResult { total, hits } = self
-            .repo
-            .search
-            .find_entities(&self.repo.search.indices.tag, query)
-            .await?;
+        let SearchResult {
+            total,
+            hits,
+            cursor,
+        } = self.repo.search.find_entities(Index::Tag, query).await?;
 
         let hits = self.repo.cache.tags().previews(&hits).await?;
 
-        Ok(SearchResult { total, hits })
+        Ok(SearchResult {
+            total,
+            hits,
+            cursor,
+        })
     }
 }
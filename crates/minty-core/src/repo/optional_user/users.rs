@@ -1,4 +1,4 @@
-use crate::{Repo, Result};
+use crate::{search::Index, Error, Repo, Result};
 
 use minty::{ProfileQuery, SearchResult, UserPreview};
 
@@ -15,11 +15,14 @@ impl<'a> Users<'a> {
         &self,
         query: &ProfileQuery,
     ) -> Result<SearchResult<UserPreview>> {
-        let results = self
-            .repo
-            .search
-            .find_entities(&self.repo.search.indices.user, query)
-            .await?;
+        if query.category.is_some() {
+            return Err(Error::InvalidInput(
+                "users do not have categories".into(),
+            ));
+        }
+
+        let results =
+            self.repo.search.find_entities(Index::User, query).await?;
 
         let users = self
             .repo
@@ -34,6 +37,7 @@ impl<'a> Users<'a> {
         Ok(SearchResult {
             total: results.total,
             hits: users,
+            cursor: results.cursor,
         })
     }
 }
@@ -0,0 +1,146 @@
+use super::Repo;
+
+use crate::{event::Audience, Error, Result};
+
+use log::{debug, error};
+use minty::{Event, Uuid};
+use sqlx::types::Json;
+use std::{sync::Arc, time::Duration};
+use tokio::{sync::Notify, time};
+use tokio_util::{sync::CancellationToken, task::TaskTracker};
+
+#[derive(Debug, Default)]
+pub(super) struct PreviewQueue {
+    notify: Notify,
+    token: CancellationToken,
+    tracker: TaskTracker,
+}
+
+impl PreviewQueue {
+    pub(super) fn notify(&self) {
+        self.notify.notify_one();
+    }
+
+    pub(super) fn start(&self, repo: &Arc<Repo>) {
+        for _ in 0..repo.preview.queue.workers {
+            let repo = repo.clone();
+            self.tracker.spawn(async move { run(&repo).await });
+        }
+    }
+
+    pub(super) async fn stop(&self) {
+        self.token.cancel();
+        self.tracker.close();
+        self.tracker.wait().await;
+    }
+}
+
+async fn run(repo: &Repo) {
+    let queue = &repo.preview_queue;
+    let config = &repo.preview.queue;
+    let poll_interval = Duration::from_millis(config.poll_interval_ms);
+    let lease = config.lease_seconds.try_into().unwrap_or(i32::MAX);
+
+    while !queue.token.is_cancelled() {
+        let job = match repo.database.claim_preview_job(lease).await {
+            Ok(job) => job,
+            Err(err) => {
+                error!("Failed to claim preview job: {err}");
+                None
+            }
+        };
+
+        let Some((object, attempts)) = job else {
+            tokio::select! {
+                biased;
+
+                _ = queue.token.cancelled() => break,
+                _ = queue.notify.notified() => {}
+                _ = time::sleep(poll_interval) => {}
+            }
+
+            continue;
+        };
+
+        debug!("Generating previews for object {object}");
+
+        if let Err(err) = generate(repo, object).await {
+            let attempts = attempts.try_into().unwrap_or_default();
+            let retry = (attempts < config.max_attempts).then(|| {
+                config.retry_delay(attempts).try_into().unwrap_or(i32::MAX)
+            });
+
+            error!(
+                "Failed to generate previews for object {object} \
+                (attempt {attempts}): {err}"
+            );
+
+            if let Err(err) = repo
+                .database
+                .fail_preview_job(object, &err.to_string(), retry)
+                .await
+            {
+                error!(
+                    "Failed to record preview job failure \
+                    for object {object}: {err}"
+                );
+            }
+        }
+    }
+}
+
+async fn generate(repo: &Repo, id: Uuid) -> Result<()> {
+    let object = repo.bucket.get_object(id).await?;
+
+    let preview = repo
+        .generators
+        .generate(&repo.bucket, &repo.preview, &object)
+        .await
+        .map_err(Error::Internal)?;
+    let (variants, previews) = preview.variants();
+
+    repo.database
+        .update_object_preview(
+            object.id,
+            &object.hash,
+            &object.r#type,
+            &object.subtype,
+            preview.id,
+            preview.phash(),
+            &variants,
+            &previews,
+            Json(&preview.metadata),
+            preview.storyboard_id,
+            preview.storyboard.as_ref().map(Json),
+            preview.animation_id,
+        )
+        .await?;
+    repo.database.complete_preview_job(id).await?;
+
+    if let Some(object) = repo.cache.objects().get_cached(id) {
+        object.update_preview(&preview);
+    }
+
+    for (post, modified, Json(objects)) in
+        repo.database.read_object_post_search(id).await?
+    {
+        repo.search
+            .update_post_objects(post, &objects, modified)
+            .await?;
+    }
+
+    // Objects only referenced by drafts, or by no posts at all, are private
+    // to their uploader and the drafts' posters.
+    let event = Event::ObjectPreviewReady { object: id };
+    let (public, users) = repo.database.read_object_audience(id).await?;
+
+    if public {
+        repo.events.send_all(event);
+    } else {
+        for user in users {
+            repo.events.send(event, Audience::User(user));
+        }
+    }
+
+    Ok(())
+}
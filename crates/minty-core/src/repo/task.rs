@@ -1,10 +1,12 @@
 use super::Repo;
 
-use crate::{db::Id, preview, search::Index, Error, Result};
+use crate::{db::Id, search::Index, Error, Result};
 
 use futures::{stream::BoxStream, StreamExt};
 use log::error;
+use minty::Uuid;
 use serde::Serialize;
+use sqlx::types::Json;
 use std::{result, sync::Arc};
 use tokio::sync::Semaphore;
 use tokio_util::task::TaskTracker;
@@ -23,6 +25,37 @@ impl Task {
         }
     }
 
+    pub async fn apply_tag_implications(
+        &self,
+        posts: Vec<(Uuid, Vec<Uuid>)>,
+    ) -> Result<()> {
+        let cache = &self.repo.cache;
+
+        for (post, tags) in posts {
+            for tag in &tags {
+                self.repo.search.add_post_tag(post, *tag).await?;
+            }
+
+            if let Some(post) = cache.posts().get_cached(post) {
+                for tag in cache.tags().get_multiple(&tags).await? {
+                    post.add_tag(tag);
+                }
+            } else {
+                for tag in &tags {
+                    if let Some(tag) = cache.tags().get_cached(*tag) {
+                        tag.update(|tag| tag.post_count += 1);
+                    }
+                }
+            }
+
+            self.task.increment();
+        }
+
+        self.repo.search.refresh_index(Index::Post).await?;
+
+        Ok(())
+    }
+
     pub async fn regenerate_previews(
         &self,
         batch_size: usize,
@@ -89,12 +122,33 @@ impl Task {
     }
 
     async fn regenerate_previews_subtask(&self, object: &fstore::Object) {
-        match preview::generate_preview(&self.repo.bucket, object).await {
+        let result = self
+            .repo
+            .generators
+            .generate(&self.repo.bucket, &self.repo.preview, object)
+            .await;
+
+        match result {
             Ok(preview) => {
+                let (variants, previews) = preview.variants();
+
                 if let Err(err) = self
                     .repo
                     .database
-                    .update_object_preview(object.id, preview)
+                    .update_object_preview(
+                        object.id,
+                        &object.hash,
+                        &object.r#type,
+                        &object.subtype,
+                        preview.id,
+                        preview.phash(),
+                        &variants,
+                        &previews,
+                        Json(&preview.metadata),
+                        preview.storyboard_id,
+                        preview.storyboard.as_ref().map(Json),
+                        preview.animation_id,
+                    )
                     .await
                 {
                     error!(
@@ -127,14 +181,20 @@ impl Task {
 
     pub async fn reindex<T>(
         &self,
-        index: &Index,
+        index: Index,
         batch_size: usize,
         stream: BoxStream<'_, sqlx::Result<T>>,
     ) -> Result<()>
     where
         T: Id + Serialize,
     {
-        index.recreate().await?;
+        let search = &self.repo.search;
+
+        if !search.has_indices() {
+            return Ok(());
+        }
+
+        search.recreate_index(index).await?;
 
         let mut stream = stream.chunks(batch_size);
 
@@ -142,11 +202,11 @@ impl Task {
             let items =
                 chunk.into_iter().collect::<result::Result<Vec<_>, _>>()?;
 
-            index.bulk_create(&items).await?;
+            search.bulk_create(index, &items).await?;
             self.task.progress(items.len());
         }
 
-        index.refresh().await?;
+        search.refresh_index(index).await?;
 
         Ok(())
     }
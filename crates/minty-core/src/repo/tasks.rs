@@ -1,10 +1,16 @@
 use super::Repo;
 
-use crate::{Result, Task};
+use crate::{search::Index, Error, Result, Task};
 
 use std::sync::Arc;
 use tokio::task::{self, JoinHandle};
 
+fn total(value: i64) -> Result<usize> {
+    value.try_into().map_err(|_| {
+        Error::Internal(format!("task total out of range: {value}"))
+    })
+}
+
 pub struct Tasks<'a> {
     repo: &'a Arc<Repo>,
 }
@@ -14,18 +20,28 @@ impl<'a> Tasks<'a> {
         Self { repo }
     }
 
+    pub async fn apply_tag_implications(
+        &self,
+    ) -> Result<(Task, JoinHandle<Result<()>>)> {
+        let posts = self.repo.database.create_implied_post_tags().await?;
+
+        let task = Task::new(posts.len());
+        let guard = task.guard();
+        let repo = self.repo.clone();
+
+        let handle = task::spawn(async move {
+            repo.task(guard.task()).apply_tag_implications(posts).await
+        });
+
+        Ok((task, handle))
+    }
+
     pub async fn regenerate_previews(
         &self,
         batch_size: usize,
         max_tasks: usize,
     ) -> Result<(Task, JoinHandle<Result<()>>)> {
-        let total = self
-            .repo
-            .database
-            .read_object_total()
-            .await?
-            .try_into()
-            .unwrap();
+        let total = total(self.repo.database.read_object_total().await?)?;
 
         let task = Task::new(total);
         let guard = task.guard();
@@ -44,24 +60,21 @@ impl<'a> Tasks<'a> {
         &self,
         batch_size: usize,
     ) -> Result<(Task, JoinHandle<Result<()>>)> {
-        let total = self
-            .repo
-            .database
-            .read_post_total()
-            .await?
-            .try_into()
-            .unwrap();
+        let total = if self.repo.search.has_indices() {
+            total(self.repo.database.read_post_total().await?)?
+        } else {
+            0
+        };
 
         let task = Task::new(total);
         let guard = task.guard();
         let repo = self.repo.clone();
 
         let handle = task::spawn(async move {
-            let index = &repo.search.indices.post;
             let stream = repo.database.read_post_search();
 
             repo.task(guard.task())
-                .reindex(index, batch_size, stream)
+                .reindex(Index::Post, batch_size, stream)
                 .await
         });
 
@@ -72,24 +85,21 @@ impl<'a> Tasks<'a> {
         &self,
         batch_size: usize,
     ) -> Result<(Task, JoinHandle<Result<()>>)> {
-        let total = self
-            .repo
-            .database
-            .read_tag_total()
-            .await?
-            .try_into()
-            .unwrap();
+        let total = if self.repo.search.has_indices() {
+            total(self.repo.database.read_tag_total().await?)?
+        } else {
+            0
+        };
 
         let task = Task::new(total);
         let guard = task.guard();
         let repo = self.repo.clone();
 
         let handle = task::spawn(async move {
-            let index = &repo.search.indices.tag;
             let stream = repo.database.read_tag_search();
 
             repo.task(guard.task())
-                .reindex(index, batch_size, stream)
+                .reindex(Index::Tag, batch_size, stream)
                 .await
         });
 
@@ -100,24 +110,21 @@ impl<'a> Tasks<'a> {
         &self,
         batch_size: usize,
     ) -> Result<(Task, JoinHandle<Result<()>>)> {
-        let total = self
-            .repo
-            .database
-            .read_user_total()
-            .await?
-            .try_into()
-            .unwrap();
+        let total = if self.repo.search.has_indices() {
+            total(self.repo.database.read_user_total().await?)?
+        } else {
+            0
+        };
 
         let task = Task::new(total);
         let guard = task.guard();
         let repo = self.repo.clone();
 
         let handle = task::spawn(async move {
-            let index = &repo.search.indices.user;
             let stream = repo.database.read_user_search();
 
             repo.task(guard.task())
-                .reindex(index, batch_size, stream)
+                .reindex(Index::User, batch_size, stream)
                 .await
         });
 
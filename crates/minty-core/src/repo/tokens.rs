@@ -0,0 +1,33 @@
+use crate::{AccessToken, Repo, Result, Token};
+
+pub struct Tokens<'a> {
+    repo: &'a Repo,
+}
+
+impl<'a> Tokens<'a> {
+    pub(super) fn new(repo: &'a Repo) -> Self {
+        Self { repo }
+    }
+
+    pub async fn get(&self, token: AccessToken) -> Result<Option<Token>> {
+        let Some(token) =
+            self.repo.database.read_user_token(&token.digest()).await?
+        else {
+            return Ok(None);
+        };
+
+        let Some(user) = self.repo.cache.users().get(token.user_id).await?
+        else {
+            return Ok(None);
+        };
+
+        if user.is_deleted() {
+            return Ok(None);
+        }
+
+        Ok(Some(Token {
+            scopes: token.scopes(),
+            user,
+        }))
+    }
+}
@@ -15,12 +15,12 @@ pub use tag::*;
 pub use tags::*;
 
 use crate::{
-    cache, error::Found, model::Invitation, Cached, Repo, Result, SessionId,
-    SessionInfo,
+    cache, error::Found, model::Invitation, AccessToken, Cached, Error, Repo,
+    Result, SessionId, SessionInfo,
 };
 
 use chrono::{Duration, Local};
-use minty::Uuid;
+use minty::{NewToken, TokenSecret, Uuid};
 use std::sync::Arc;
 
 pub struct WithUser<'a> {
@@ -61,6 +61,48 @@ impl<'a> WithUser<'a> {
         })
     }
 
+    pub async fn create_token(&self, token: &NewToken) -> Result<TokenSecret> {
+        if token.scopes.is_empty() {
+            return Err(Error::InvalidInput(
+                "token requires at least one scope".into(),
+            ));
+        }
+
+        if token
+            .expiration
+            .is_some_and(|expiration| expiration <= Local::now())
+        {
+            return Err(Error::InvalidInput(
+                "token expiration must be in the future".into(),
+            ));
+        }
+
+        let mut scopes = token.scopes.clone();
+        scopes.sort_unstable();
+        scopes.dedup();
+        let scopes: Vec<_> =
+            scopes.iter().map(|scope| scope.as_str()).collect();
+
+        let secret = AccessToken::generate();
+
+        let token = self
+            .repo
+            .database
+            .create_user_token(
+                self.user.id,
+                token.name.as_ref(),
+                &secret.digest(),
+                &scopes,
+                token.expiration,
+            )
+            .await?;
+
+        Ok(TokenSecret {
+            token: token.into(),
+            secret: secret.to_string(),
+        })
+    }
+
     pub fn edit_self(self) -> Edit<'a> {
         Edit::new(self.repo, self.user)
     }
@@ -69,6 +111,17 @@ impl<'a> WithUser<'a> {
         self.user.model().found("user", self.user.id)
     }
 
+    pub async fn get_tokens(&self) -> Result<Vec<minty::Token>> {
+        Ok(self
+            .repo
+            .database
+            .read_user_tokens(self.user.id)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
     pub fn invite(&self) -> Result<String> {
         let exp = Duration::days(1);
         let invitation = Invitation::new(self.user.id);
@@ -77,7 +130,7 @@ impl<'a> WithUser<'a> {
     }
 
     pub fn objects(self) -> Objects<'a> {
-        Objects::new(self.repo)
+        Objects::new(self.repo, self.user)
     }
 
     pub async fn post(self, id: Uuid) -> Result<Post<'a>> {
@@ -89,10 +142,18 @@ impl<'a> WithUser<'a> {
         Posts::new(self.repo, self.user)
     }
 
+    pub async fn revoke_token(&self, id: Uuid) -> Result<()> {
+        self.repo
+            .database
+            .delete_user_token(self.user.id, id)
+            .await?
+            .found("token", id)
+    }
+
     pub async fn tag(self, id: Uuid) -> Result<Tag<'a>> {
         let tag = self.repo.cache.tags().get(id).await?.found("tag", id)?;
         tag.can_edit(&self.user)?;
-        Ok(Tag::new(self.repo, tag))
+        Ok(Tag::new(self.repo, self.user, tag))
     }
 
     pub fn tags(self) -> Tags<'a> {
@@ -1,6 +1,7 @@
 use crate::{cache::User, error::Found, Cached, Repo, Result};
 
-use minty::{text, CommentData, Uuid};
+use log::error;
+use minty::{text, CommentData, Event, Uuid};
 use std::sync::Arc;
 
 pub struct Comment<'a> {
@@ -47,12 +48,31 @@ impl<'a> Comment<'a> {
             .create_reply(self.user.id, self.id, content.as_ref())
             .await?
             .found("comment", self.id)?;
+        let post_id = comment.post_id;
 
-        Ok(self
+        let reply = self
             .repo
             .cache
             .comments()
-            .reply(self.id, comment, self.user))
+            .reply(self.id, comment, self.user);
+
+        match self.repo.cache.posts().get(post_id).await {
+            Ok(Some(post)) => self.repo.events.send_post(
+                &post,
+                Event::CommentAdded {
+                    post: post_id,
+                    comment: reply.id,
+                },
+            ),
+            Ok(None) => (),
+            Err(err) => error!(
+                "Failed to read post '{post_id}' for comment event \
+                '{}': {err}",
+                reply.id
+            ),
+        }
+
+        Ok(reply)
     }
 
     pub async fn set_content(&self, content: text::Comment) -> Result<String> {
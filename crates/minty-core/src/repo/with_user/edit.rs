@@ -1,4 +1,4 @@
-use crate::{cache::User, error::Found, Cached, Repo, Result};
+use crate::{cache::User, error::Found, search::Index, Cached, Repo, Result};
 
 use minty::{
     text::{Description, Email, Name, Password},
@@ -20,7 +20,7 @@ impl<'a> Edit<'a> {
         let names = self
             .repo
             .entity(self.user.id)
-            .add_alias(alias, "user", &self.repo.search.indices.user)
+            .add_alias(alias, "user", Index::User)
             .await?;
 
         self.user.update(|user| user.profile.set_names(&names));
@@ -41,7 +41,7 @@ impl<'a> Edit<'a> {
     pub async fn delete(&self) -> Result<()> {
         self.repo
             .entity(self.user.id)
-            .delete("user", &self.repo.search.indices.user)
+            .delete("user", Index::User)
             .await?;
 
         self.repo.cache.users().remove(&self.user);
@@ -53,7 +53,7 @@ impl<'a> Edit<'a> {
         let names = self
             .repo
             .entity(self.user.id)
-            .delete_alias(alias, "user", &self.repo.search.indices.user)
+            .delete_alias(alias, "user", Index::User)
             .await?;
 
         self.user.update(|user| user.profile.set_names(&names));
@@ -139,7 +139,7 @@ impl<'a> Edit<'a> {
         let names = self
             .repo
             .entity(self.user.id)
-            .set_name(new_name, "user", &self.repo.search.indices.user)
+            .set_name(new_name, "user", Index::User)
             .await?;
 
         self.user.update(|user| user.profile.set_names(&names));
@@ -1,25 +1,38 @@
-use crate::{Repo, Result};
+use crate::{cache, Cached, Repo, Result};
 
 use bytes::Bytes;
 use futures::TryStream;
-use minty::ObjectPreview;
+use minty::{ObjectUpload, PreviewState};
+use std::sync::Arc;
 
 pub struct Objects<'a> {
     repo: &'a Repo,
+    user: Arc<Cached<cache::User>>,
 }
 
 impl<'a> Objects<'a> {
-    pub(super) fn new(repo: &'a Repo) -> Self {
-        Self { repo }
+    pub(super) fn new(repo: &'a Repo, user: Arc<Cached<cache::User>>) -> Self {
+        Self { repo, user }
     }
 
-    pub async fn upload<S>(&self, stream: S) -> Result<ObjectPreview>
+    pub async fn upload<S>(&self, stream: S) -> Result<ObjectUpload>
     where
         S: TryStream + Send + Sync + 'static,
         S::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
         Bytes: From<S::Ok>,
     {
         let object = self.repo.bucket.add_object_stream(stream).await?;
-        self.repo.objects().add(object).await
+        let objects = self.repo.objects();
+
+        let mut duplicates =
+            objects.find_by_hash(&object.hash, Some(&self.user)).await?;
+        duplicates.retain(|duplicate| duplicate.object.id != object.id);
+        let object = objects.add_pending(object, self.user.id).await?;
+
+        Ok(ObjectUpload {
+            object,
+            preview_state: PreviewState::Pending,
+            duplicates,
+        })
     }
 }
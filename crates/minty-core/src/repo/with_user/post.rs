@@ -7,7 +7,7 @@ use crate::{
     Cached, Error, Repo, Result,
 };
 
-use minty::{text, CommentData, Uuid};
+use minty::{text, CommentData, Event, Uuid};
 use std::sync::Arc;
 
 pub struct Post<'a> {
@@ -70,6 +70,14 @@ impl<'a> Post<'a> {
             self.user.clone(),
         );
 
+        self.repo.events.send_post(
+            &self.post,
+            Event::CommentAdded {
+                post: self.post.id,
+                comment: result.id,
+            },
+        );
+
         Ok(result)
     }
 }
@@ -4,7 +4,7 @@ use crate::{
 
 use minty::{
     text::{Description, PostTitle},
-    DateTime, Modification, Uuid,
+    DateTime, Event, Modification, Uuid,
 };
 use std::sync::Arc;
 
@@ -33,16 +33,19 @@ impl<'a> Edit<'a> {
         let PostObjects { modified, objects } = tx
             .create_post_objects(self.post.id, objects, destination)
             .await?;
+        let (search,) = tx.read_post_objects_search(self.post.id).await?;
 
         self.repo
             .search
-            .update_post_modified(self.post.id, modified)
+            .update_post_objects(self.post.id, &search.0, modified)
             .await?;
 
         let objects = self.repo.cache.objects().get_multiple(&objects).await?;
         self.post.add_objects(objects, modified);
 
         tx.commit().await?;
+        self.edited();
+
         Ok(modified)
     }
 
@@ -76,6 +79,7 @@ impl<'a> Edit<'a> {
             })?;
 
         self.post.set_related_posts(posts);
+        self.edited();
 
         Ok(())
     }
@@ -83,29 +87,32 @@ impl<'a> Edit<'a> {
     pub async fn add_tag(&self, tag: Uuid) -> Result<()> {
         let mut tx = self.repo.database.begin().await?;
 
-        tx.create_post_tag(self.post.id, tag).await.map_err(|err| {
-            err.as_database_error()
-                .and_then(|e| e.constraint())
-                .and_then(|constraint| match constraint {
-                    "post_tag_post_id_fkey" => Some(Error::NotFound {
-                        entity: "post",
-                        id: self.post.id,
-                    }),
-                    "post_tag_tag_id_fkey" => Some(Error::NotFound {
-                        entity: "tag",
-                        id: tag,
-                    }),
-                    _ => None,
-                })
-                .unwrap_or_else(|| err.into())
-        })?;
-
-        self.repo.search.add_post_tag(self.post.id, tag).await?;
-
-        let tag = self.repo.cache.tags().get(tag).await?.found("tag", tag)?;
-        self.post.add_tag(tag);
+        let (tags,) =
+            tx.create_post_tag(self.post.id, tag).await.map_err(|err| {
+                err.as_database_error()
+                    .and_then(|e| e.constraint())
+                    .and_then(|constraint| match constraint {
+                        "post_tag_post_id_fkey" => Some(Error::NotFound {
+                            entity: "post",
+                            id: self.post.id,
+                        }),
+                        "post_tag_tag_id_fkey" => Some(Error::NotFound {
+                            entity: "tag",
+                            id: tag,
+                        }),
+                        _ => None,
+                    })
+                    .unwrap_or_else(|| err.into())
+            })?;
+
+        for tag in self.repo.cache.tags().get_multiple(&tags).await? {
+            self.repo.search.add_post_tag(self.post.id, tag.id).await?;
+            self.post.add_tag(tag);
+        }
 
         tx.commit().await?;
+        self.edited();
+
         Ok(())
     }
 
@@ -120,8 +127,15 @@ impl<'a> Edit<'a> {
 
         tx.commit().await?;
 
+        let audience = self.post.audience();
         self.repo.cache.posts().remove(&self.post);
 
+        if let Some(audience) = audience {
+            self.repo
+                .events
+                .send(Event::PostDeleted { post: self.post.id }, audience);
+        }
+
         Ok(())
     }
 
@@ -129,14 +143,17 @@ impl<'a> Edit<'a> {
         let mut tx = self.repo.database.begin().await?;
 
         let modified = tx.delete_post_objects(self.post.id, objects).await?.0;
+        let (search,) = tx.read_post_objects_search(self.post.id).await?;
+
         self.repo
             .search
-            .update_post_modified(self.post.id, modified)
+            .update_post_objects(self.post.id, &search.0, modified)
             .await?;
 
         tx.commit().await?;
 
         self.post.delete_objects(objects, modified);
+        self.edited();
 
         Ok(modified)
     }
@@ -155,6 +172,11 @@ impl<'a> Edit<'a> {
         }
 
         tx.commit().await?;
+
+        if found {
+            self.edited();
+        }
+
         Ok(found)
     }
 
@@ -168,6 +190,7 @@ impl<'a> Edit<'a> {
             .found("post", related)?;
 
         self.post.set_related_posts(posts);
+        self.edited();
 
         Ok(())
     }
@@ -185,6 +208,11 @@ impl<'a> Edit<'a> {
         self.post.publish(timestamp);
 
         tx.commit().await?;
+
+        self.repo
+            .events
+            .send_post(&self.post, Event::PostPublished { post: self.post.id });
+
         Ok(())
     }
 
@@ -208,6 +236,7 @@ impl<'a> Edit<'a> {
         tx.commit().await?;
 
         self.post.set_description(description.clone(), modified);
+        self.edited();
 
         Ok(Modification {
             date_modified: modified,
@@ -235,10 +264,17 @@ impl<'a> Edit<'a> {
         tx.commit().await?;
 
         self.post.set_title(title.clone(), modified);
+        self.edited();
 
         Ok(Modification {
             date_modified: modified,
             new_value: title,
         })
     }
+
+    fn edited(&self) {
+        self.repo
+            .events
+            .send_post(&self.post, Event::PostEdited { post: self.post.id });
+    }
 }
@@ -1,9 +1,17 @@
 use super::Post;
 
-use crate::{cache::User, db, Cached, Repo, Result};
+use crate::{
+    archive::{self, Entry},
+    cache::User,
+    db, Cached, Error, Repo, Result,
+};
 
-use minty::PostParts;
-use std::sync::Arc;
+use bytes::Bytes;
+use futures::TryStream;
+use minty::{
+    ArchiveEntryError, ArchiveUpload, Event, ObjectPreview, PostParts,
+};
+use std::{error, result, sync::Arc};
 
 pub struct Posts<'a> {
     repo: &'a Repo,
@@ -30,11 +38,87 @@ impl<'a> Posts<'a> {
             )
             .await?;
 
-        self.repo.search.add_post(&post.search()).await?;
+        let (objects,) = tx.read_post_objects_search(post.id).await?;
+        self.repo.search.add_post(&post.search(objects.0)).await?;
 
         let post = self.repo.cache.posts().insert(post).await?;
 
         tx.commit().await?;
+
+        self.repo
+            .events
+            .send_post(&post, Event::PostCreated { post: post.id });
+
         Ok(Post::new(self.repo, self.user, post))
     }
+
+    pub async fn add_archive<S>(
+        self,
+        parts: PostParts,
+        stream: S,
+    ) -> Result<ArchiveUpload>
+    where
+        S: TryStream + Send,
+        S::Error: Into<Box<dyn error::Error + Send + Sync>>,
+        Bytes: From<S::Ok>,
+    {
+        let mut entries = archive::entries(stream, &self.repo.archive).await?;
+
+        let mut objects = Vec::new();
+        let mut errors = Vec::new();
+
+        while let Some(Entry { name, data }) = entries.recv().await {
+            let result = match data {
+                Ok(data) => self.add_object(data).await,
+                Err(message) => Err(message),
+            };
+
+            match result {
+                Ok(object) => objects.push(object),
+                Err(message) => {
+                    errors.push(ArchiveEntryError { name, message })
+                }
+            }
+        }
+
+        if objects.is_empty() {
+            return Err(Error::InvalidInput(match errors.first() {
+                Some(ArchiveEntryError { name, message }) => format!(
+                    "no files in archive could be stored; '{name}': {message}"
+                ),
+                None => "archive contains no files".into(),
+            }));
+        }
+
+        let post = self
+            .add(&PostParts {
+                objects: Some(objects.iter().map(|object| object.id).collect()),
+                ..parts
+            })
+            .await?;
+
+        Ok(ArchiveUpload {
+            post_id: post.id(),
+            objects,
+            errors,
+        })
+    }
+
+    async fn add_object(
+        &self,
+        data: Bytes,
+    ) -> result::Result<ObjectPreview, String> {
+        let object = self
+            .repo
+            .bucket
+            .add_object(data)
+            .await
+            .map_err(|err| err.to_string())?;
+
+        self.repo
+            .objects()
+            .add_pending(object, self.user.id)
+            .await
+            .map_err(|err| err.to_string())
+    }
 }
@@ -1,19 +1,24 @@
-use crate::{cache, error::Found, Cached, Repo, Result};
+use crate::{cache, error::Found, search::Index, Cached, Error, Repo, Result};
 
 use minty::{
     text::{Description, Name},
-    ProfileName, Source, Url, Uuid,
+    Event, ProfileName, Source, Url, Uuid,
 };
 use std::sync::Arc;
 
 pub struct Tag<'a> {
     repo: &'a Repo,
+    user: Arc<Cached<cache::User>>,
     tag: Arc<Cached<cache::Tag>>,
 }
 
 impl<'a> Tag<'a> {
-    pub(super) fn new(repo: &'a Repo, tag: Arc<Cached<cache::Tag>>) -> Self {
-        Self { repo, tag }
+    pub(super) fn new(
+        repo: &'a Repo,
+        user: Arc<Cached<cache::User>>,
+        tag: Arc<Cached<cache::Tag>>,
+    ) -> Self {
+        Self { repo, user, tag }
     }
 
     pub fn id(&self) -> Uuid {
@@ -24,10 +29,11 @@ impl<'a> Tag<'a> {
         let names = self
             .repo
             .entity(self.tag.id)
-            .add_alias(alias, "tag", &self.repo.search.indices.tag)
+            .add_alias(alias, "tag", Index::Tag)
             .await?;
 
         self.tag.update(|tag| tag.profile.set_names(&names));
+        self.changed();
 
         Ok(names)
     }
@@ -37,6 +43,7 @@ impl<'a> Tag<'a> {
 
         self.tag
             .update(|tag| tag.profile.add_source(source.clone()));
+        self.changed();
 
         Ok(source)
     }
@@ -44,10 +51,11 @@ impl<'a> Tag<'a> {
     pub async fn delete(&self) -> Result<()> {
         self.repo
             .entity(self.tag.id)
-            .delete("tag", &self.repo.search.indices.tag)
+            .delete("tag", Index::Tag)
             .await?;
 
         self.repo.cache.tags().remove(&self.tag);
+        self.changed();
 
         Ok(())
     }
@@ -56,10 +64,11 @@ impl<'a> Tag<'a> {
         let names = self
             .repo
             .entity(self.tag.id)
-            .delete_alias(alias, "tag", &self.repo.search.indices.tag)
+            .delete_alias(alias, "tag", Index::Tag)
             .await?;
 
         self.tag.update(|tag| tag.profile.set_names(&names));
+        self.changed();
 
         Ok(names)
     }
@@ -73,6 +82,7 @@ impl<'a> Tag<'a> {
 
         if deleted {
             self.tag.update(|tag| tag.profile.delete_source(source_id));
+            self.changed();
         }
 
         Ok(deleted)
@@ -89,6 +99,114 @@ impl<'a> Tag<'a> {
             .await?;
 
         self.tag.update(|tag| tag.profile.delete_sources(&ids));
+        self.changed();
+
+        Ok(())
+    }
+
+    pub async fn merge(&self, other: Uuid) -> Result<minty::Tag> {
+        if self.tag.id == other {
+            return Err(Error::InvalidInput(
+                "tag cannot be merged with itself".into(),
+            ));
+        }
+
+        let other = self
+            .repo
+            .cache
+            .tags()
+            .get(other)
+            .await?
+            .found("tag", other)?;
+        other.can_edit(&self.user)?;
+
+        let profile = other.model().found("tag", other.id)?.profile;
+        let mut tx = self.repo.database.begin().await?;
+
+        let (posts,) = tx.merge_tag(self.tag.id, other.id).await.map_err(
+            |err| match err.as_database_error().and_then(|e| e.constraint()) {
+                Some("tag_implication_cycle") => Error::InvalidInput(
+                    "merging the tags would create an implication cycle".into(),
+                ),
+                _ => err.into(),
+            },
+        )?;
+
+        for name in profile.aliases.iter().chain([&profile.name]) {
+            self.repo.search.add_tag_alias(self.tag.id, name).await?;
+        }
+
+        self.repo.search.delete_entity(Index::Tag, other.id).await?;
+
+        for post in posts.iter().copied() {
+            self.repo
+                .search
+                .replace_post_tag(post, other.id, self.tag.id)
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        for post in posts {
+            if let Some(post) = self.repo.cache.posts().get_cached(post) {
+                post.delete_tag(other.id);
+                post.add_tag(self.tag.clone());
+            }
+        }
+
+        self.repo.cache.tags().remove(&other);
+        self.repo
+            .events
+            .send_all(Event::TagChanged { tag: other.id });
+
+        let tag = self
+            .repo
+            .database
+            .read_tag(self.tag.id)
+            .await?
+            .found("tag", self.tag.id)?;
+
+        self.tag.update(|cached| {
+            cached.profile = tag.profile.into();
+            cached.category = tag.category;
+            cached.post_count = tag.post_count;
+        });
+        self.changed();
+
+        self.tag.model().found("tag", self.tag.id)
+    }
+
+    pub async fn set_category(&self, category: Option<&str>) -> Result<()> {
+        let mut tx = self.repo.database.begin().await?;
+
+        tx.update_tag_category(self.tag.id, category)
+            .await
+            .map_err(|err| {
+                err.as_database_error()
+                    .and_then(|e| e.constraint())
+                    .and_then(|constraint| match constraint {
+                        "tag_category_fkey" => {
+                            Some(Error::InvalidInput(format!(
+                                "tag category '{}' does not exist",
+                                category.unwrap_or_default()
+                            )))
+                        }
+                        _ => None,
+                    })
+                    .unwrap_or_else(|| err.into())
+            })?
+            .found("tag", self.tag.id)?;
+
+        self.repo
+            .search
+            .update_tag_category(self.tag.id, category)
+            .await?;
+
+        self.tag
+            .update(|tag| tag.category = category.map(ToOwned::to_owned));
+
+        tx.commit().await?;
+        self.changed();
 
         Ok(())
     }
@@ -107,6 +225,7 @@ impl<'a> Tag<'a> {
 
         self.tag
             .update(|tag| tag.profile.description.clone_from(&description));
+        self.changed();
 
         Ok(description)
     }
@@ -115,11 +234,18 @@ impl<'a> Tag<'a> {
         let names = self
             .repo
             .entity(self.tag.id)
-            .set_name(new_name, "tag", &self.repo.search.indices.tag)
+            .set_name(new_name, "tag", Index::Tag)
             .await?;
 
         self.tag.update(|tag| tag.profile.set_names(&names));
+        self.changed();
 
         Ok(names)
     }
+
+    fn changed(&self) {
+        self.repo
+            .events
+            .send_all(Event::TagChanged { tag: self.tag.id });
+    }
 }
@@ -2,7 +2,7 @@ use super::Tag;
 
 use crate::{cache::User, Cached, Repo, Result};
 
-use minty::text::Name;
+use minty::{text::Name, Event};
 use std::sync::Arc;
 
 pub struct Tags<'a> {
@@ -21,9 +21,12 @@ impl<'a> Tags<'a> {
 
         let tag = tx.create_tag(name, self.user.id).await?;
         self.repo.search.add_tag_alias(tag.id, name).await?;
-        let tag = self.repo.cache.tags().insert(tag, self.user);
+        let tag = self.repo.cache.tags().insert(tag, self.user.clone());
 
         tx.commit().await?;
-        Ok(Tag::new(self.repo, tag))
+
+        self.repo.events.send_all(Event::TagChanged { tag: tag.id });
+
+        Ok(Tag::new(self.repo, self.user, tag))
     }
 }
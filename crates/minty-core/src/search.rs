@@ -1,238 +1,308 @@
-mod index;
-mod response;
+mod elastic;
+mod postgres;
 
-pub use index::{Index, Indices};
+use elastic::Elastic;
+use postgres::Postgres;
 
-use response::ResponseExt;
-
-use crate::{conf::SearchConfig, db::PostSearch, Result};
-
-use elasticsearch::{
-    http::transport::{SingleNodeConnectionPool, TransportBuilder},
-    Elasticsearch,
+use crate::{
+    conf::{SearchBackend, SearchConfig},
+    db::{Database, Id, ObjectSearch, PostSearch},
+    Error, Result,
 };
-use minty::{
-    DateTime, PostQuery, ProfileQuery, SearchResult, Uuid, Visibility,
-};
-use serde_json::{json, Map, Value as Json};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD as Base64, Engine};
+use minty::{DateTime, PostQuery, ProfileQuery, SearchResult, Uuid};
+use serde::Serialize;
+use serde_json::Value as Json;
 use std::result;
 
+fn encode_cursor(sort: &Json) -> String {
+    Base64.encode(sort.to_string())
+}
+
+fn decode_cursor(cursor: &str) -> Result<Json> {
+    Base64
+        .decode(cursor)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<Json>(&bytes).ok())
+        .filter(Json::is_array)
+        .ok_or_else(|| Error::InvalidInput("invalid search cursor".into()))
+}
+
+fn media_type(query: &PostQuery) -> Option<(&str, Option<&str>)> {
+    query.media_type.as_deref().map(|media_type| {
+        match media_type.split_once('/') {
+            Some((r#type, subtype)) => (r#type, Some(subtype)),
+            None => (media_type, None),
+        }
+    })
+}
+
+fn has_media_filter(query: &PostQuery) -> bool {
+    query.media_type.is_some()
+        || !query.width.is_empty()
+        || !query.height.is_empty()
+        || !query.duration_ms.is_empty()
+        || query.orientation.is_some()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Index {
+    Post,
+    Tag,
+    User,
+}
+
 #[derive(Debug)]
-pub struct Search {
-    pub indices: Indices,
+pub struct SearchHits {
+    pub total: u32,
+    pub hits: Vec<Uuid>,
+    pub tags: Vec<(Uuid, u32)>,
+    pub cursor: Option<String>,
+}
+
+impl From<SearchHits> for SearchResult<Uuid> {
+    fn from(value: SearchHits) -> Self {
+        Self {
+            total: value.total,
+            hits: value.hits,
+            cursor: value.cursor,
+        }
+    }
+}
+
+pub trait Backend {
+    fn has_indices(&self) -> bool;
+
+    async fn check_indices(&self) -> Result<()>;
+
+    async fn create_indices(&self) -> Result<()>;
+
+    async fn delete_indices(&self) -> Result<()>;
+
+    async fn recreate_index(&self, index: Index) -> Result<()>;
+
+    async fn refresh_index(&self, index: Index) -> Result<()>;
+
+    async fn bulk_create<T>(&self, index: Index, items: &[T]) -> Result<()>
+    where
+        T: Id + Serialize;
+
+    async fn add_entity_alias(
+        &self,
+        index: Index,
+        id: Uuid,
+        alias: &str,
+    ) -> Result<()>;
+
+    async fn add_post(&self, post: &PostSearch) -> Result<()>;
+
+    async fn add_post_tag(&self, post: Uuid, tag: Uuid) -> Result<()>;
+
+    async fn delete_entity(&self, index: Index, id: Uuid) -> Result<()>;
+
+    async fn delete_entity_alias(
+        &self,
+        index: Index,
+        id: Uuid,
+        alias: &str,
+    ) -> Result<()>;
+
+    async fn delete_post(&self, post: Uuid) -> Result<()>;
+
+    async fn find_entities(
+        &self,
+        index: Index,
+        query: &ProfileQuery,
+    ) -> Result<SearchResult<Uuid>>;
+
+    async fn find_posts(&self, query: &PostQuery) -> Result<SearchHits>;
+
+    async fn publish_post(&self, post: Uuid, timestamp: DateTime)
+        -> Result<()>;
+
+    async fn remove_post_tag(&self, post: Uuid, tag: Uuid) -> Result<()>;
+
+    async fn replace_post_tag(
+        &self,
+        post: Uuid,
+        old: Uuid,
+        new: Uuid,
+    ) -> Result<()>;
+
+    async fn update_entity_name(
+        &self,
+        index: Index,
+        id: Uuid,
+        old: &str,
+        new: &str,
+    ) -> Result<()>;
+
+    async fn update_post_description(
+        &self,
+        post: Uuid,
+        description: &str,
+        modified: DateTime,
+    ) -> Result<()>;
+
+    async fn update_post_objects(
+        &self,
+        post: Uuid,
+        objects: &[ObjectSearch],
+        modified: DateTime,
+    ) -> Result<()>;
+
+    async fn update_post_title(
+        &self,
+        post: Uuid,
+        title: &str,
+        modified: DateTime,
+    ) -> Result<()>;
+
+    async fn update_tag_category(
+        &self,
+        tag: Uuid,
+        category: Option<&str>,
+    ) -> Result<()>;
+}
+
+macro_rules! backend {
+    ($self:ident.$method:ident($($arg:expr),*)) => {
+        match $self {
+            Self::Elasticsearch(backend) => backend.$method($($arg),*).await,
+            Self::Postgres(backend) => backend.$method($($arg),*).await,
+        }
+    };
+    (sync $self:ident.$method:ident($($arg:expr),*)) => {
+        match $self {
+            Self::Elasticsearch(backend) => backend.$method($($arg),*),
+            Self::Postgres(backend) => backend.$method($($arg),*),
+        }
+    };
+}
+
+pub enum Search {
+    Elasticsearch(Elastic),
+    Postgres(Postgres),
 }
 
 impl Search {
-    pub fn new(config: &SearchConfig) -> result::Result<Self, String> {
-        let pool = SingleNodeConnectionPool::new(config.node.clone());
-
-        let transport = TransportBuilder::new(pool)
-            .auth(config.auth.clone().into())
-            .build()
-            .map_err(|err| {
-                format!(
-                    "failed to initialize Elasticsearch HTTP transport: {err}"
-                )
-            })?;
-
-        let client = Elasticsearch::new(transport);
-        let indices = Indices::new(client, &config.namespace, config.refresh);
-
-        Ok(Self { indices })
+    pub fn new(
+        config: &SearchConfig,
+        database: &Database,
+    ) -> result::Result<Self, String> {
+        Ok(match config.backend {
+            SearchBackend::Elasticsearch => {
+                let config =
+                    config.elasticsearch.as_ref().ok_or_else(|| {
+                        "the Elasticsearch search backend requires \
+                    'node', 'auth' and 'namespace' settings"
+                            .to_owned()
+                    })?;
+
+                Self::Elasticsearch(Elastic::new(config)?)
+            }
+            SearchBackend::Postgres => {
+                Self::Postgres(Postgres::new(database.clone()))
+            }
+        })
+    }
+
+    pub fn has_indices(&self) -> bool {
+        backend!(sync self.has_indices())
+    }
+
+    pub async fn check_indices(&self) -> Result<()> {
+        backend!(self.check_indices())
     }
 
     pub async fn create_indices(&self) -> Result<()> {
-        self.indices.create().await
+        backend!(self.create_indices())
     }
 
     pub async fn delete_indices(&self) -> Result<()> {
-        self.indices.delete().await
+        backend!(self.delete_indices())
+    }
+
+    pub async fn recreate_index(&self, index: Index) -> Result<()> {
+        backend!(self.recreate_index(index))
+    }
+
+    pub async fn refresh_index(&self, index: Index) -> Result<()> {
+        backend!(self.refresh_index(index))
+    }
+
+    pub async fn bulk_create<T>(&self, index: Index, items: &[T]) -> Result<()>
+    where
+        T: Id + Serialize,
+    {
+        backend!(self.bulk_create(index, items))
     }
 
     pub async fn add_entity_alias(
         &self,
-        index: &Index,
+        index: Index,
         id: Uuid,
         alias: &str,
     ) -> Result<()> {
-        let script = "if (!ctx._source.names.contains(params.alias)) {\
-                          ctx._source.names.add(params.alias);\
-                      }";
-
-        index
-            .update_doc(
-                id,
-                json!({
-                    "script": {
-                        "lang": "painless",
-                        "params": { "alias": alias },
-                        "source": script,
-                    },
-                    "upsert": { "names": [alias] }
-                }),
-            )
-            .await
+        backend!(self.add_entity_alias(index, id, alias))
     }
 
     pub async fn add_post(&self, post: &PostSearch) -> Result<()> {
-        self.indices.post.create_doc(post.id, post).await
+        backend!(self.add_post(post))
     }
 
     pub async fn add_post_tag(&self, post: Uuid, tag: Uuid) -> Result<()> {
-        let script = "if (!ctx._source.tags.contains(params.tag)) {\
-                          ctx._source.tags.add(params.tag);\
-                      }";
-
-        self.indices
-            .post
-            .update_doc(
-                post,
-                json!({
-                    "script": {
-                        "lang": "painless",
-                        "params": { "tag": tag },
-                        "source": script,
-                    }
-                }),
-            )
-            .await
+        backend!(self.add_post_tag(post, tag))
     }
 
     pub async fn add_tag_alias(&self, id: Uuid, alias: &str) -> Result<()> {
-        self.add_entity_alias(&self.indices.tag, id, alias).await
+        self.add_entity_alias(Index::Tag, id, alias).await
     }
 
     pub async fn add_user_alias(&self, id: Uuid, alias: &str) -> Result<()> {
-        self.add_entity_alias(&self.indices.user, id, alias).await
+        self.add_entity_alias(Index::User, id, alias).await
+    }
+
+    pub async fn delete_entity(&self, index: Index, id: Uuid) -> Result<()> {
+        backend!(self.delete_entity(index, id))
     }
 
     pub async fn delete_entity_alias(
         &self,
-        index: &Index,
+        index: Index,
         id: Uuid,
         alias: &str,
     ) -> Result<()> {
-        let script = "if (ctx._source.names.contains(params.alias)) {\
-                          ctx._source.names.remove(\
-                              ctx._source.names.indexOf(params.alias)\
-                          );\
-                      }";
-
-        index
-            .update_doc(
-                id,
-                json!({
-                    "script": {
-                        "lang": "painless",
-                        "params": { "alias": alias },
-                        "source": script,
-                    }
-                }),
-            )
-            .await
+        backend!(self.delete_entity_alias(index, id, alias))
     }
 
     pub async fn delete_post(&self, post: Uuid) -> Result<()> {
-        self.indices.post.delete_doc(post).await
+        backend!(self.delete_post(post))
     }
 
     pub async fn find_entities(
         &self,
-        index: &Index,
+        index: Index,
         query: &ProfileQuery,
     ) -> Result<SearchResult<Uuid>> {
-        index.search(json!({
-            "_source": false,
-            "from": query.pagination.from,
-            "size": query.pagination.size,
-            "query": {
-                "bool": {
-                    "must": {
-                        "multi_match": {
-                            "query": query.name,
-                            "type": "bool_prefix",
-                            "fields": ["names", "names._2gram", "names._3gram"]
-                        }
-                    },
-                    "must_not": {
-                        "ids": {
-                            "values": query.exclude
-                        }
-                    }
-                }
-            }
-        })).await
-    }
-
-    pub async fn find_posts(
-        &self,
-        query: &PostQuery,
-    ) -> Result<SearchResult<Uuid>> {
-        let mut filter: Vec<Json> = vec![json!({
-            "term": {
-                "visibility": query.visibility
-            }
-        })];
-
-        if let Some(poster) = query.poster {
-            filter.push(json!({
-                "term": {
-                    "poster": {
-                        "value": poster
-                    }
-                }
-            }))
-        }
+        let mut result = backend!(self.find_entities(index, query))?;
 
-        if !query.tags.is_empty() {
-            filter.push(json!({
-                "terms_set": {
-                    "tags": {
-                        "terms": query.tags,
-                        "minimum_should_match_script": {
-                            "source": query.tags.len().to_string()
-                        }
-                    }
-                }
-            }));
+        if result.hits.len() < query.pagination.size as usize {
+            result.cursor = None;
         }
 
-        let mut bool = Map::new();
+        Ok(result)
+    }
 
-        bool.insert("filter".into(), Json::Array(filter));
+    pub async fn find_posts(&self, query: &PostQuery) -> Result<SearchHits> {
+        let mut result = backend!(self.find_posts(query))?;
 
-        if !query.text.is_empty() {
-            bool.insert(
-                "must".into(),
-                json!({
-                    "multi_match": {
-                        "query": query.text,
-                        "fields": ["title^3", "description"]
-                    }
-                }),
-            );
+        if result.hits.len() < query.pagination.size as usize {
+            result.cursor = None;
         }
 
-        let sort = match query.sort.value {
-            minty::PostSortValue::Created => "created",
-            minty::PostSortValue::Modified => "modified",
-            minty::PostSortValue::Relevance => "_score",
-            minty::PostSortValue::Title => "title.keyword",
-        };
-
-        let query = json!({
-            "_source": false,
-            "from": query.pagination.from,
-            "size": query.pagination.size,
-            "query": {
-                "bool": bool
-            },
-            "sort": {
-                sort: query.sort.order
-            }
-        });
-
-        self.indices.post.search(query).await
+        Ok(result)
     }
 
     pub async fn publish_post(
@@ -240,108 +310,48 @@ impl Search {
         post: Uuid,
         timestamp: DateTime,
     ) -> Result<()> {
-        self.indices
-            .post
-            .update_doc(
-                post,
-                json!({
-                    "doc": {
-                        "visibility": Visibility::Public,
-                        "created": timestamp,
-                        "modified": timestamp
-                    }
-                }),
-            )
-            .await
+        backend!(self.publish_post(post, timestamp))
     }
 
     pub async fn remove_post_tag(&self, post: Uuid, tag: Uuid) -> Result<()> {
-        let script = "if (ctx._source.tags.contains(params.tag)) {\
-                          ctx._source.tags.remove(\
-                              ctx._source.tags.indexOf(params.tag)\
-                          );\
-                      }";
-
-        self.indices
-            .post
-            .update_doc(
-                post,
-                json!({
-                    "script": {
-                        "lang": "painless",
-                        "params": { "tag": tag },
-                        "source": script
-                    }
-                }),
-            )
-            .await
+        backend!(self.remove_post_tag(post, tag))
+    }
+
+    pub async fn replace_post_tag(
+        &self,
+        post: Uuid,
+        old: Uuid,
+        new: Uuid,
+    ) -> Result<()> {
+        backend!(self.replace_post_tag(post, old, new))
     }
 
     pub async fn update_entity_name(
         &self,
+        index: Index,
         id: Uuid,
         old: &str,
         new: &str,
-        index: &Index,
     ) -> Result<()> {
-        let script = "if (ctx._source.names.contains(params.old)) {\
-                          ctx._source.names.remove(\
-                              ctx._source.names.indexOf(params.old)\
-                          );\
-                          ctx._source.names.add(params.new);\
-                      }";
-
-        index
-            .update_doc(
-                id,
-                json!({
-                    "script": {
-                        "lang": "painless",
-                        "params": {
-                            "old": old,
-                            "new": new,
-                        },
-                        "source": script
-                    }
-                }),
-            )
-            .await
+        backend!(self.update_entity_name(index, id, old, new))
     }
 
-    pub async fn update_post_modified(
+    pub async fn update_post_description(
         &self,
         post: Uuid,
+        description: &str,
         modified: DateTime,
     ) -> Result<()> {
-        self.indices
-            .post
-            .update_doc(
-                post,
-                json!({
-                    "doc": { "modified": modified }
-                }),
-            )
-            .await
+        backend!(self.update_post_description(post, description, modified))
     }
 
-    pub async fn update_post_description(
+    pub async fn update_post_objects(
         &self,
         post: Uuid,
-        description: &str,
+        objects: &[ObjectSearch],
         modified: DateTime,
     ) -> Result<()> {
-        self.indices
-            .post
-            .update_doc(
-                post,
-                json!({
-                    "doc": {
-                        "description": description,
-                        "modified": modified
-                    }
-                }),
-            )
-            .await
+        backend!(self.update_post_objects(post, objects, modified))
     }
 
     pub async fn update_post_title(
@@ -350,17 +360,14 @@ impl Search {
         title: &str,
         modified: DateTime,
     ) -> Result<()> {
-        self.indices
-            .post
-            .update_doc(
-                post,
-                json!({
-                    "doc": {
-                        "title": title,
-                        "modified": modified
-                    }
-                }),
-            )
-            .await
+        backend!(self.update_post_title(post, title, modified))
+    }
+
+    pub async fn update_tag_category(
+        &self,
+        tag: Uuid,
+        category: Option<&str>,
+    ) -> Result<()> {
+        backend!(self.update_tag_category(tag, category))
     }
 }
@@ -0,0 +1,604 @@
+mod index;
+mod response;
+
+use index::Indices;
+use response::ResponseExt;
+
+use super::{
+    decode_cursor, has_media_filter, media_type, Backend, Index, SearchHits,
+};
+
+use crate::{
+    conf::ElasticsearchConfig,
+    db::{Id, ObjectSearch, PostSearch},
+    Result,
+};
+
+use elasticsearch::{
+    http::transport::{SingleNodeConnectionPool, TransportBuilder},
+    Elasticsearch,
+};
+use minty::{
+    DateTime, Pagination, PostQuery, PostSortValue, ProfileQuery, SearchResult,
+    Uuid, Visibility,
+};
+use serde::Serialize;
+use serde_json::{json, Map, Value as Json};
+use std::result;
+
+#[derive(Debug)]
+pub struct Elastic {
+    indices: Indices,
+}
+
+impl Elastic {
+    pub fn new(config: &ElasticsearchConfig) -> result::Result<Self, String> {
+        let pool = SingleNodeConnectionPool::new(config.node.clone());
+
+        let transport = TransportBuilder::new(pool)
+            .auth(config.auth.clone().into())
+            .build()
+            .map_err(|err| {
+                format!(
+                    "failed to initialize Elasticsearch HTTP transport: {err}"
+                )
+            })?;
+
+        let client = Elasticsearch::new(transport);
+        let indices = Indices::new(client, &config.namespace, config.refresh);
+
+        Ok(Self { indices })
+    }
+
+    fn index(&self, index: Index) -> &index::Index {
+        match index {
+            Index::Post => &self.indices.post,
+            Index::Tag => &self.indices.tag,
+            Index::User => &self.indices.user,
+        }
+    }
+}
+
+fn media_filter(query: &PostQuery) -> Vec<Json> {
+    let mut filter = Vec::new();
+
+    if let Some((r#type, subtype)) = media_type(query) {
+        filter.push(json!({ "term": { "objects.type": r#type } }));
+
+        if let Some(subtype) = subtype {
+            filter.push(json!({ "term": { "objects.subtype": subtype } }));
+        }
+    }
+
+    for (field, range) in [
+        ("objects.width", &query.width),
+        ("objects.height", &query.height),
+        ("objects.duration_ms", &query.duration_ms),
+    ] {
+        if range.is_empty() {
+            continue;
+        }
+
+        let mut bounds = Map::new();
+
+        if let Some(min) = range.min {
+            bounds.insert("gte".into(), json!(min));
+        }
+
+        if let Some(max) = range.max {
+            bounds.insert("lte".into(), json!(max));
+        }
+
+        filter.push(json!({
+            "range": {
+                field: bounds
+            }
+        }));
+    }
+
+    if let Some(orientation) = query.orientation {
+        filter.push(json!({
+            "term": { "objects.orientation": orientation }
+        }));
+    }
+
+    filter
+}
+
+fn paginate(
+    search: &mut Json,
+    pagination: &Pagination,
+    cursor: Option<&str>,
+) -> Result<()> {
+    search["size"] = json!(pagination.size);
+
+    match cursor {
+        Some(cursor) => search["search_after"] = decode_cursor(cursor)?,
+        None => search["from"] = json!(pagination.from),
+    }
+
+    Ok(())
+}
+
+impl Backend for Elastic {
+    fn has_indices(&self) -> bool {
+        true
+    }
+
+    async fn check_indices(&self) -> Result<()> {
+        self.indices.check_version().await
+    }
+
+    async fn create_indices(&self) -> Result<()> {
+        self.indices.create().await
+    }
+
+    async fn delete_indices(&self) -> Result<()> {
+        self.indices.delete().await
+    }
+
+    async fn recreate_index(&self, index: Index) -> Result<()> {
+        self.index(index).recreate().await
+    }
+
+    async fn refresh_index(&self, index: Index) -> Result<()> {
+        self.index(index).refresh().await
+    }
+
+    async fn bulk_create<T>(&self, index: Index, items: &[T]) -> Result<()>
+    where
+        T: Id + Serialize,
+    {
+        self.index(index).bulk_create(items).await
+    }
+
+    async fn add_entity_alias(
+        &self,
+        index: Index,
+        id: Uuid,
+        alias: &str,
+    ) -> Result<()> {
+        let script = "if (!ctx._source.names.contains(params.alias)) {\
+                          ctx._source.names.add(params.alias);\
+                      }";
+
+        self.index(index)
+            .update_doc(
+                id,
+                json!({
+                    "script": {
+                        "lang": "painless",
+                        "params": { "alias": alias },
+                        "source": script,
+                    },
+                    "upsert": { "id": id, "names": [alias] }
+                }),
+            )
+            .await
+    }
+
+    async fn add_post(&self, post: &PostSearch) -> Result<()> {
+        self.indices.post.create_doc(post.id, post).await
+    }
+
+    async fn add_post_tag(&self, post: Uuid, tag: Uuid) -> Result<()> {
+        let script = "if (!ctx._source.tags.contains(params.tag)) {\
+                          ctx._source.tags.add(params.tag);\
+                      }";
+
+        self.indices
+            .post
+            .update_doc(
+                post,
+                json!({
+                    "script": {
+                        "lang": "painless",
+                        "params": { "tag": tag },
+                        "source": script,
+                    }
+                }),
+            )
+            .await
+    }
+
+    async fn delete_entity(&self, index: Index, id: Uuid) -> Result<()> {
+        self.index(index).delete_doc(id).await
+    }
+
+    async fn delete_entity_alias(
+        &self,
+        index: Index,
+        id: Uuid,
+        alias: &str,
+    ) -> Result<()> {
+        let script = "if (ctx._source.names.contains(params.alias)) {\
+                          ctx._source.names.remove(\
+                              ctx._source.names.indexOf(params.alias)\
+                          );\
+                      }";
+
+        self.index(index)
+            .update_doc(
+                id,
+                json!({
+                    "script": {
+                        "lang": "painless",
+                        "params": { "alias": alias },
+                        "source": script,
+                    }
+                }),
+            )
+            .await
+    }
+
+    async fn delete_post(&self, post: Uuid) -> Result<()> {
+        self.indices.post.delete_doc(post).await
+    }
+
+    async fn find_entities(
+        &self,
+        index: Index,
+        query: &ProfileQuery,
+    ) -> Result<SearchResult<Uuid>> {
+        let mut bool = json!({
+            "must": {
+                "multi_match": {
+                    "query": query.name,
+                    "type": "bool_prefix",
+                    "fields": ["names", "names._2gram", "names._3gram"]
+                }
+            },
+            "must_not": {
+                "ids": {
+                    "values": query.exclude
+                }
+            }
+        });
+
+        if let Some(category) = &query.category {
+            bool["filter"] = json!({
+                "term": {
+                    "category": category
+                }
+            });
+        }
+
+        let mut search = json!({
+            "_source": false,
+            "track_total_hits": true,
+            "query": {
+                "bool": bool
+            },
+            "sort": ["_score", { "id": "asc" }]
+        });
+
+        paginate(&mut search, &query.pagination, query.cursor.as_deref())?;
+
+        let hits = self.index(index).search(search).await?;
+
+        Ok(hits.into())
+    }
+
+    async fn find_posts(&self, query: &PostQuery) -> Result<SearchHits> {
+        let mut filter: Vec<Json> = vec![json!({
+            "term": {
+                "visibility": query.visibility
+            }
+        })];
+
+        if let Some(poster) = query.poster {
+            filter.push(json!({
+                "term": {
+                    "poster": {
+                        "value": poster
+                    }
+                }
+            }))
+        }
+
+        if !query.tags.is_empty() {
+            filter.push(json!({
+                "terms_set": {
+                    "tags": {
+                        "terms": query.tags,
+                        "minimum_should_match_script": {
+                            "source": query.tags.len().to_string()
+                        }
+                    }
+                }
+            }));
+        }
+
+        for group in query.any_tags.iter().filter(|group| !group.is_empty()) {
+            let should: Vec<Json> = group
+                .iter()
+                .map(|tag| json!({ "term": { "tags": { "value": tag } } }))
+                .collect();
+
+            filter.push(json!({
+                "bool": {
+                    "should": should,
+                    "minimum_should_match": 1
+                }
+            }));
+        }
+
+        for (field, range) in
+            [("created", &query.created), ("modified", &query.modified)]
+        {
+            if range.is_empty() {
+                continue;
+            }
+
+            let mut bounds = Map::new();
+
+            if let Some(after) = range.after {
+                bounds.insert("gte".into(), json!(after));
+            }
+
+            if let Some(before) = range.before {
+                bounds.insert("lt".into(), json!(before));
+            }
+
+            filter.push(json!({
+                "range": {
+                    field: bounds
+                }
+            }));
+        }
+
+        if has_media_filter(query) {
+            filter.push(json!({
+                "nested": {
+                    "path": "objects",
+                    "query": {
+                        "bool": {
+                            "filter": media_filter(query)
+                        }
+                    }
+                }
+            }));
+        }
+
+        let mut bool = Map::new();
+
+        bool.insert("filter".into(), Json::Array(filter));
+
+        if !query.exclude_tags.is_empty() {
+            bool.insert(
+                "must_not".into(),
+                json!({
+                    "terms": {
+                        "tags": query.exclude_tags
+                    }
+                }),
+            );
+        }
+
+        if !query.text.is_empty() {
+            bool.insert(
+                "must".into(),
+                json!({
+                    "multi_match": {
+                        "query": query.text,
+                        "fields": ["title^3", "description"]
+                    }
+                }),
+            );
+        }
+
+        let sort = match query.sort.value {
+            PostSortValue::Created => "created",
+            PostSortValue::Modified => "modified",
+            PostSortValue::Relevance => "_score",
+            PostSortValue::Title => "title.keyword",
+        };
+
+        let mut search = json!({
+            "_source": false,
+            "track_total_hits": true,
+            "query": {
+                "bool": bool
+            },
+            "sort": [{ sort: query.sort.order }, { "id": "asc" }]
+        });
+
+        paginate(&mut search, &query.pagination, query.cursor.as_deref())?;
+
+        if query.facets > 0 {
+            search["aggs"] = json!({
+                "tags": {
+                    "terms": {
+                        "field": "tags",
+                        "size": query.facets,
+                        "exclude": query.tags
+                    }
+                }
+            });
+        }
+
+        self.indices.post.search(search).await
+    }
+
+    async fn publish_post(
+        &self,
+        post: Uuid,
+        timestamp: DateTime,
+    ) -> Result<()> {
+        self.indices
+            .post
+            .update_doc(
+                post,
+                json!({
+                    "doc": {
+                        "visibility": Visibility::Public,
+                        "created": timestamp,
+                        "modified": timestamp
+                    }
+                }),
+            )
+            .await
+    }
+
+    async fn remove_post_tag(&self, post: Uuid, tag: Uuid) -> Result<()> {
+        let script = "if (ctx._source.tags.contains(params.tag)) {\
+                          ctx._source.tags.remove(\
+                              ctx._source.tags.indexOf(params.tag)\
+                          );\
+                      }";
+
+        self.indices
+            .post
+            .update_doc(
+                post,
+                json!({
+                    "script": {
+                        "lang": "painless",
+                        "params": { "tag": tag },
+                        "source": script
+                    }
+                }),
+            )
+            .await
+    }
+
+    async fn replace_post_tag(
+        &self,
+        post: Uuid,
+        old: Uuid,
+        new: Uuid,
+    ) -> Result<()> {
+        let script = "if (ctx._source.tags.contains(params.old)) {\
+                          ctx._source.tags.remove(\
+                              ctx._source.tags.indexOf(params.old)\
+                          );\
+                      }\
+                      if (!ctx._source.tags.contains(params.new)) {\
+                          ctx._source.tags.add(params.new);\
+                      }";
+
+        self.indices
+            .post
+            .update_doc(
+                post,
+                json!({
+                    "script": {
+                        "lang": "painless",
+                        "params": {
+                            "old": old,
+                            "new": new,
+                        },
+                        "source": script
+                    }
+                }),
+            )
+            .await
+    }
+
+    async fn update_entity_name(
+        &self,
+        index: Index,
+        id: Uuid,
+        old: &str,
+        new: &str,
+    ) -> Result<()> {
+        let script = "if (ctx._source.names.contains(params.old)) {\
+                          ctx._source.names.remove(\
+                              ctx._source.names.indexOf(params.old)\
+                          );\
+                          ctx._source.names.add(params.new);\
+                      }";
+
+        self.index(index)
+            .update_doc(
+                id,
+                json!({
+                    "script": {
+                        "lang": "painless",
+                        "params": {
+                            "old": old,
+                            "new": new,
+                        },
+                        "source": script
+                    }
+                }),
+            )
+            .await
+    }
+
+    async fn update_post_description(
+        &self,
+        post: Uuid,
+        description: &str,
+        modified: DateTime,
+    ) -> Result<()> {
+        self.indices
+            .post
+            .update_doc(
+                post,
+                json!({
+                    "doc": {
+                        "description": description,
+                        "modified": modified
+                    }
+                }),
+            )
+            .await
+    }
+
+    async fn update_post_objects(
+        &self,
+        post: Uuid,
+        objects: &[ObjectSearch],
+        modified: DateTime,
+    ) -> Result<()> {
+        self.indices
+            .post
+            .update_doc(
+                post,
+                json!({
+                    "doc": {
+                        "objects": objects,
+                        "modified": modified
+                    }
+                }),
+            )
+            .await
+    }
+
+    async fn update_post_title(
+        &self,
+        post: Uuid,
+        title: &str,
+        modified: DateTime,
+    ) -> Result<()> {
+        self.indices
+            .post
+            .update_doc(
+                post,
+                json!({
+                    "doc": {
+                        "title": title,
+                        "modified": modified
+                    }
+                }),
+            )
+            .await
+    }
+
+    async fn update_tag_category(
+        &self,
+        tag: Uuid,
+        category: Option<&str>,
+    ) -> Result<()> {
+        self.indices
+            .tag
+            .update_doc(
+                tag,
+                json!({
+                    "doc": { "category": category }
+                }),
+            )
+            .await
+    }
+}
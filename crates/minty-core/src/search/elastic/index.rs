@@ -1,9 +1,16 @@
 use super::ResponseExt;
 
-use crate::{db::Id, Result};
+use crate::{
+    db::Id,
+    search::{encode_cursor, SearchHits},
+    Error, Result,
+};
 
 use elasticsearch::{
-    indices::{IndicesCreateParts, IndicesDeleteParts, IndicesRefreshParts},
+    indices::{
+        IndicesCreateParts, IndicesDeleteParts, IndicesGetMappingParts,
+        IndicesRefreshParts,
+    },
     params::Refresh,
     BulkOperation, BulkParts, CreateParts, DeleteParts, Elasticsearch,
     SearchParts, UpdateParts,
@@ -13,6 +20,10 @@ use minty::Uuid;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value as Json};
 
+// Increment whenever an index mapping changes, so that servers refuse to
+// start until the indices have been rebuilt.
+const VERSION: u64 = 1;
+
 #[derive(Deserialize)]
 struct Total {
     value: u32,
@@ -22,6 +33,8 @@ struct Total {
 struct Hit {
     #[serde(rename = "_id")]
     id: Uuid,
+    #[serde(default)]
+    sort: Option<Json>,
 }
 
 #[derive(Deserialize)]
@@ -30,16 +43,53 @@ struct Hits {
     hits: Vec<Hit>,
 }
 
+#[derive(Deserialize)]
+struct Bucket {
+    key: Uuid,
+    doc_count: u32,
+}
+
+#[derive(Deserialize)]
+struct Terms {
+    buckets: Vec<Bucket>,
+}
+
+#[derive(Default, Deserialize)]
+struct Aggregations {
+    tags: Option<Terms>,
+}
+
 #[derive(Deserialize)]
 struct SearchResult {
     hits: Hits,
+    #[serde(default)]
+    aggregations: Aggregations,
 }
 
-impl From<SearchResult> for minty::SearchResult<Uuid> {
+impl From<SearchResult> for SearchHits {
     fn from(value: SearchResult) -> Self {
+        let cursor = value
+            .hits
+            .hits
+            .last()
+            .and_then(|hit| hit.sort.as_ref())
+            .map(encode_cursor);
+
         Self {
             total: value.hits.total.value,
             hits: value.hits.hits.into_iter().map(|hit| hit.id).collect(),
+            tags: value
+                .aggregations
+                .tags
+                .map(|terms| {
+                    terms
+                        .buckets
+                        .into_iter()
+                        .map(|bucket| (bucket.key, bucket.doc_count))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            cursor,
         }
     }
 }
@@ -90,6 +140,43 @@ impl Index {
         Ok(())
     }
 
+    pub async fn check_version(&self) -> Result<()> {
+        let mapping: Json = self
+            .client
+            .indices()
+            .get_mapping(IndicesGetMappingParts::Index(&[&self.name]))
+            .ignore_unavailable(true)
+            .send()
+            .await?
+            .check()
+            .await?
+            .json()
+            .await?;
+
+        let Some(index) = mapping.get(&self.name) else {
+            return Err(Error::Internal(format!(
+                "search index '{}' does not exist; \
+                run 'mintyd reindex' to create it",
+                self.name
+            )));
+        };
+
+        let version = index["mappings"]["_meta"]["version"]
+            .as_u64()
+            .unwrap_or_default();
+
+        if version != VERSION {
+            return Err(Error::Internal(format!(
+                "search index '{}' has schema version {version}, \
+                but version {VERSION} is required; \
+                run 'mintyd reindex' to rebuild it",
+                self.name
+            )));
+        }
+
+        Ok(())
+    }
+
     pub async fn create(&self) -> Result<()> {
         self.client
             .indices()
@@ -133,10 +220,7 @@ impl Index {
         Ok(())
     }
 
-    pub async fn search(
-        &self,
-        query: Json,
-    ) -> Result<minty::SearchResult<Uuid>> {
+    pub async fn search(&self, query: Json) -> Result<SearchHits> {
         debug!("search {}: {query}", self.name);
 
         let result: SearchResult = self
@@ -212,13 +296,7 @@ impl Indices {
         Self {
             client: client.clone(),
             post: Index::new(client.clone(), namespace, "post", post, refresh),
-            tag: Index::new(
-                client.clone(),
-                namespace,
-                "tag",
-                entity_profile,
-                refresh,
-            ),
+            tag: Index::new(client.clone(), namespace, "tag", tag, refresh),
             user: Index::new(
                 client.clone(),
                 namespace,
@@ -229,6 +307,14 @@ impl Indices {
         }
     }
 
+    pub async fn check_version(&self) -> Result<()> {
+        for index in self.all() {
+            index.check_version().await?;
+        }
+
+        Ok(())
+    }
+
     pub async fn create(&self) -> Result<()> {
         for index in self.all() {
             index.create().await?;
@@ -260,7 +346,13 @@ impl Indices {
 fn post() -> Json {
     json!({
         "mappings": {
+            "_meta": {
+                "version": VERSION
+            },
             "properties": {
+                "id": {
+                    "type": "keyword"
+                },
                 "poster": {
                     "type": "keyword"
                 },
@@ -286,6 +378,50 @@ fn post() -> Json {
                 },
                 "tags": {
                     "type": "keyword"
+                },
+                "objects": {
+                    "type": "nested",
+                    "properties": {
+                        "type": {
+                            "type": "keyword"
+                        },
+                        "subtype": {
+                            "type": "keyword"
+                        },
+                        "width": {
+                            "type": "integer"
+                        },
+                        "height": {
+                            "type": "integer"
+                        },
+                        "duration_ms": {
+                            "type": "long"
+                        },
+                        "orientation": {
+                            "type": "keyword"
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+fn tag() -> Json {
+    json!({
+        "mappings": {
+            "_meta": {
+                "version": VERSION
+            },
+            "properties": {
+                "id": {
+                    "type": "keyword"
+                },
+                "names": {
+                    "type": "search_as_you_type"
+                },
+                "category": {
+                    "type": "keyword"
                 }
             }
         }
@@ -295,7 +431,13 @@ fn post() -> Json {
 fn entity_profile() -> Json {
     json!({
         "mappings": {
+            "_meta": {
+                "version": VERSION
+            },
             "properties": {
+                "id": {
+                    "type": "keyword"
+                },
                 "names": {
                     "type": "search_as_you_type"
                 }
@@ -0,0 +1,326 @@
+use super::{
+    decode_cursor, encode_cursor, has_media_filter, media_type, Backend, Index,
+    SearchHits,
+};
+
+use crate::{
+    db::{
+        Database, Id, ObjectSearch, PostSearch, ProfileSearchResult, Visibility,
+    },
+    Error, Result,
+};
+
+use minty::{
+    DateTime, PostQuery, PostSortValue, ProfileQuery, SearchResult, SortOrder,
+    Uuid,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sqlx::types::Json;
+
+fn count(value: i64) -> Result<u32> {
+    value.try_into().map_err(|_| {
+        Error::Internal(format!("search count out of range: {value}"))
+    })
+}
+
+fn search_after(
+    cursor: Option<&str>,
+    sort: Option<PostSortValue>,
+) -> Result<Option<Value>> {
+    let Some(cursor) = cursor else {
+        return Ok(None);
+    };
+
+    let after = decode_cursor(cursor)?;
+
+    let valid = match after.as_array().map(Vec::as_slice) {
+        Some([value, Value::String(id)]) if Uuid::parse_str(id).is_ok() => {
+            match (sort, value) {
+                (
+                    Some(PostSortValue::Created | PostSortValue::Modified),
+                    Value::String(date),
+                ) => date.parse::<DateTime>().is_ok(),
+                (Some(PostSortValue::Title), Value::String(_)) => true,
+                (None | Some(PostSortValue::Relevance), Value::Number(_)) => {
+                    true
+                }
+                _ => false,
+            }
+        }
+        _ => false,
+    };
+
+    if valid {
+        Ok(Some(after))
+    } else {
+        Err(Error::InvalidInput("invalid search cursor".into()))
+    }
+}
+
+pub struct Postgres {
+    database: Database,
+}
+
+impl Postgres {
+    pub fn new(database: Database) -> Self {
+        Self { database }
+    }
+}
+
+impl TryFrom<ProfileSearchResult> for SearchResult<Uuid> {
+    type Error = Error;
+
+    fn try_from(value: ProfileSearchResult) -> Result<Self> {
+        Ok(Self {
+            total: count(value.total)?,
+            hits: value.hits,
+            cursor: value.cursor.as_ref().map(encode_cursor),
+        })
+    }
+}
+
+impl Backend for Postgres {
+    fn has_indices(&self) -> bool {
+        false
+    }
+
+    async fn check_indices(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn create_indices(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_indices(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn recreate_index(&self, _index: Index) -> Result<()> {
+        Ok(())
+    }
+
+    async fn refresh_index(&self, _index: Index) -> Result<()> {
+        Ok(())
+    }
+
+    async fn bulk_create<T>(&self, _index: Index, _items: &[T]) -> Result<()>
+    where
+        T: Id + Serialize,
+    {
+        Ok(())
+    }
+
+    async fn add_entity_alias(
+        &self,
+        _index: Index,
+        _id: Uuid,
+        _alias: &str,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn add_post(&self, _post: &PostSearch) -> Result<()> {
+        Ok(())
+    }
+
+    async fn add_post_tag(&self, _post: Uuid, _tag: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_entity(&self, _index: Index, _id: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_entity_alias(
+        &self,
+        _index: Index,
+        _id: Uuid,
+        _alias: &str,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_post(&self, _post: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn find_entities(
+        &self,
+        index: Index,
+        query: &ProfileQuery,
+    ) -> Result<SearchResult<Uuid>> {
+        let after = search_after(query.cursor.as_deref(), None)?;
+        let from = if after.is_some() {
+            0
+        } else {
+            query.pagination.from.into()
+        };
+        let size = query.pagination.size.into();
+
+        let result = match index {
+            Index::Tag => {
+                self.database
+                    .search_tags(
+                        &query.name,
+                        query.category.as_deref(),
+                        &query.exclude,
+                        after.as_ref(),
+                        from,
+                        size,
+                    )
+                    .await?
+            }
+            Index::User => {
+                self.database
+                    .search_users(
+                        &query.name,
+                        &query.exclude,
+                        after.as_ref(),
+                        from,
+                        size,
+                    )
+                    .await?
+            }
+            Index::Post => {
+                return Err(Error::Internal(
+                    "posts cannot be searched as entities".into(),
+                ))
+            }
+        };
+
+        result.try_into()
+    }
+
+    async fn find_posts(&self, query: &PostQuery) -> Result<SearchHits> {
+        let sort = match query.sort.value {
+            PostSortValue::Created => "created",
+            PostSortValue::Modified => "modified",
+            PostSortValue::Relevance => "relevance",
+            PostSortValue::Title => "title",
+        };
+
+        let after =
+            search_after(query.cursor.as_deref(), Some(query.sort.value))?;
+        let from = if after.is_some() {
+            0
+        } else {
+            query.pagination.from.into()
+        };
+
+        let media = has_media_filter(query).then(|| {
+            let (r#type, subtype) = media_type(query).unzip();
+
+            json!({
+                "type": r#type,
+                "subtype": subtype.flatten(),
+                "width": query.width,
+                "height": query.height,
+                "duration_ms": query.duration_ms,
+                "orientation": query.orientation
+            })
+        });
+
+        let result = self
+            .database
+            .search_posts(
+                &query.text,
+                Visibility::from_minty(query.visibility),
+                query.poster,
+                &query.tags,
+                Json(query.any_tags.as_slice()),
+                &query.exclude_tags,
+                query.created.after,
+                query.created.before,
+                query.modified.after,
+                query.modified.before,
+                media.as_ref(),
+                sort,
+                query.sort.order == SortOrder::Ascending,
+                after.as_ref(),
+                from,
+                query.pagination.size.into(),
+                query.facets.into(),
+            )
+            .await?;
+
+        Ok(SearchHits {
+            total: count(result.total)?,
+            hits: result.hits,
+            tags: result
+                .facet_tags
+                .into_iter()
+                .zip(result.facet_counts)
+                .map(|(tag, total)| count(total).map(|total| (tag, total)))
+                .collect::<Result<_>>()?,
+            cursor: result.cursor.as_ref().map(encode_cursor),
+        })
+    }
+
+    async fn publish_post(
+        &self,
+        _post: Uuid,
+        _timestamp: DateTime,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn remove_post_tag(&self, _post: Uuid, _tag: Uuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn replace_post_tag(
+        &self,
+        _post: Uuid,
+        _old: Uuid,
+        _new: Uuid,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn update_entity_name(
+        &self,
+        _index: Index,
+        _id: Uuid,
+        _old: &str,
+        _new: &str,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn update_post_description(
+        &self,
+        _post: Uuid,
+        _description: &str,
+        _modified: DateTime,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn update_post_objects(
+        &self,
+        _post: Uuid,
+        _objects: &[ObjectSearch],
+        _modified: DateTime,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn update_post_title(
+        &self,
+        _post: Uuid,
+        _title: &str,
+        _modified: DateTime,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn update_tag_category(
+        &self,
+        _tag: Uuid,
+        _category: Option<&str>,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
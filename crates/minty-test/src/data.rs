@@ -1,5 +1,10 @@
 use uuid::{uuid, Uuid};
 
+pub mod archive {
+    /// Size limit for archive entries set by the test server.
+    pub const MAX_ENTRY_SIZE: u64 = 1024 * 1024;
+}
+
 pub mod objects {
     use super::*;
 
@@ -35,3 +40,9 @@ pub mod users {
 
     pub const MINTY: Uuid = uuid!("99786976-95bd-49ff-892e-cd76580aec5a");
 }
+
+pub mod preview {
+    /// Video codec reported by the test server's preview generator for the
+    /// objects it handles.
+    pub const CODEC: &str = "minty-test";
+}
@@ -0,0 +1,32 @@
+use minty_test::preview::CODEC;
+
+use bytes::Bytes;
+use minty::MediaMetadata;
+use minty_core::{conf::PreviewConfig, PreviewGenerator, Previews};
+
+/// Handles PPM images, which the built-in image generator also supports.
+pub struct TestGenerator;
+
+impl PreviewGenerator for TestGenerator {
+    fn supports(&self, r#type: &str, subtype: &str) -> bool {
+        r#type == "image" && subtype == "x-portable-pixmap"
+    }
+
+    fn priority(&self) -> i32 {
+        1
+    }
+
+    fn generate(
+        &self,
+        _data: Bytes,
+        _config: &PreviewConfig,
+    ) -> Result<Previews, String> {
+        Ok(Previews {
+            metadata: MediaMetadata {
+                video_codec: Some(CODEC.into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+}
@@ -1,16 +1,22 @@
+mod generator;
+
+use generator::TestGenerator;
+
 use fstore::http::Client;
 use log::info;
 use minty::model::export::Data;
 use minty_core::{
-    conf::{BucketConfig, Refresh, RepoConfig},
+    conf::{BucketConfig, Refresh, RepoConfig, SearchBackend, SearchConfig},
     Repo,
 };
+use minty_test::archive;
 use mintyd::{server, Config};
 use std::{
-    error::Error, fs::File, io::BufReader, path::Path, result, sync::Arc,
+    env, error::Error, fs::File, io::BufReader, path::Path, result, sync::Arc,
 };
 
 const CONFIG: &str = "minty-test.toml";
+const SEARCH_VAR: &str = "MINTY_TEST_SEARCH";
 
 pub type BoxError = Box<dyn Error + Sync + Send + 'static>;
 pub type Result<T> = result::Result<T, BoxError>;
@@ -18,7 +24,8 @@ pub type Result<T> = result::Result<T, BoxError>;
 fn main() -> Result<()> {
     let mut config = Config::read(Path::new(CONFIG))?;
     config.set_logger()?;
-    config.repo.search.refresh = Refresh::WaitFor;
+    configure_search(&mut config.repo.search)?;
+    config.repo.archive.max_entry_size = archive::MAX_ENTRY_SIZE;
 
     let _env = minty_core::initialize();
 
@@ -83,10 +90,33 @@ async fn clone_bucket(config: &mut BucketConfig) -> Result<()> {
     Ok(())
 }
 
+fn configure_search(config: &mut SearchConfig) -> Result<()> {
+    match env::var(SEARCH_VAR).as_deref() {
+        Ok("elasticsearch") => config.backend = SearchBackend::Elasticsearch,
+        Ok("postgres") => config.backend = SearchBackend::Postgres,
+        Ok(backend) => {
+            return Err(format!(
+                "unknown search backend '{backend}' set in {SEARCH_VAR}"
+            )
+            .into())
+        }
+        Err(_) => (),
+    }
+
+    if let Some(elasticsearch) = config.elasticsearch.as_mut() {
+        elasticsearch.refresh = Refresh::WaitFor;
+    }
+
+    Ok(())
+}
+
 async fn create_repo(config: &RepoConfig) -> Result<Arc<Repo>> {
     const BATCH_SIZE: usize = 100;
 
-    let repo = Arc::new(Repo::new(config).await?);
+    let repo = Arc::new(
+        Repo::with_preview_generators(config, vec![Box::new(TestGenerator)])
+            .await?,
+    );
 
     info!("Initializing database");
     repo.reset().await?;
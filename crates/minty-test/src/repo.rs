@@ -38,6 +38,13 @@ pub async fn admin() -> http::Repo {
         .unwrap()
 }
 
+pub fn with_token(secret: &str) -> http::Repo {
+    http::Repo::build(url())
+        .credentials(Credentials::Token(secret.into()))
+        .build()
+        .unwrap()
+}
+
 pub fn sign_up_info(name: &str) -> SignUp {
     let email = format!("{name}@example.com");
     let password = format!("{name} password");
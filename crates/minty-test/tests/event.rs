@@ -0,0 +1,141 @@
+use minty_test::{admin, next_user, repo};
+
+use bytes::Bytes;
+use futures::{stream, Stream, StreamExt};
+use minty::{
+    text::{Name, PostTitle},
+    Event, PostParts, Repo, Uuid, Visibility,
+};
+use std::{io, pin::pin, time::Duration};
+use tokio::{test, time};
+
+async fn next_event<S>(stream: S, f: impl Fn(&Event) -> bool) -> Event
+where
+    S: Stream<Item = minty::Result<Event>>,
+{
+    let mut stream = pin!(stream);
+
+    time::timeout(Duration::from_secs(10), async {
+        loop {
+            let event = stream
+                .next()
+                .await
+                .expect("event stream should not end")
+                .unwrap();
+
+            if f(&event) {
+                break event;
+            }
+        }
+    })
+    .await
+    .expect("event should be received")
+}
+
+fn is_post_event(event: &Event, id: Uuid) -> bool {
+    match *event {
+        Event::CommentAdded { post, .. }
+        | Event::PostCreated { post }
+        | Event::PostDeleted { post }
+        | Event::PostEdited { post }
+        | Event::PostPublished { post } => post == id,
+        _ => false,
+    }
+}
+
+#[test]
+async fn object_events() {
+    let user = next_user().await;
+    let anonymous = repo();
+
+    let mut events = pin!(user.subscribe().await.unwrap());
+    let mut anonymous_events = pin!(anonymous.subscribe().await.unwrap());
+
+    let object = user
+        .add_object(stream::iter([Ok::<_, io::Error>(Bytes::from(
+            "object event test",
+        ))]))
+        .await
+        .unwrap()
+        .object
+        .id;
+
+    user.create_post(&PostParts {
+        visibility: Some(Visibility::Draft),
+        objects: Some(vec![object]),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let ready = Event::ObjectPreviewReady { object };
+    let event = next_event(&mut events, |e| *e == ready).await;
+    assert_eq!(event, ready);
+
+    // Events are delivered in order, so the draft's preview event would have
+    // reached anonymous subscribers before this post's creation.
+    let public = user.create_post(&Default::default()).await.unwrap();
+    let created = Event::PostCreated { post: public };
+
+    let event =
+        next_event(&mut anonymous_events, |e| *e == ready || *e == created)
+            .await;
+    assert_eq!(event, created);
+}
+
+#[test]
+async fn post_events() {
+    let repo = admin().await;
+    let other = next_user().await;
+
+    let mut events = pin!(repo.subscribe().await.unwrap());
+    let mut other_events = pin!(other.subscribe().await.unwrap());
+
+    let id = repo
+        .create_post(&PostParts {
+            title: Some(PostTitle::new("Event Test").unwrap()),
+            visibility: Some(Visibility::Draft),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let event = next_event(&mut events, |e| is_post_event(e, id)).await;
+    assert_eq!(event, Event::PostCreated { post: id });
+
+    repo.set_post_title(id, PostTitle::new("New Title").unwrap())
+        .await
+        .unwrap();
+
+    let event = next_event(&mut events, |e| is_post_event(e, id)).await;
+    assert_eq!(event, Event::PostEdited { post: id });
+
+    repo.publish_post(id).await.unwrap();
+
+    let event = next_event(&mut events, |e| is_post_event(e, id)).await;
+    assert_eq!(event, Event::PostPublished { post: id });
+
+    // Events for the post were private to its poster until it was published.
+    let event = next_event(&mut other_events, |e| is_post_event(e, id)).await;
+    assert_eq!(event, Event::PostPublished { post: id });
+
+    repo.delete_post(id).await.unwrap();
+
+    let event = next_event(&mut other_events, |e| is_post_event(e, id)).await;
+    assert_eq!(event, Event::PostDeleted { post: id });
+}
+
+#[test]
+async fn tag_events() {
+    let repo = admin().await;
+    let mut events = pin!(repo.subscribe().await.unwrap());
+
+    let tag = repo.add_tag(Name::new("Event Tag").unwrap()).await.unwrap();
+
+    let event = next_event(
+        &mut events,
+        |e| matches!(e, Event::TagChanged { tag: id } if *id == tag),
+    )
+    .await;
+    assert_eq!(event, Event::TagChanged { tag });
+}
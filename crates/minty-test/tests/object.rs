@@ -1,11 +1,15 @@
-use minty_test::{admin, not_found, objects, posts};
+use minty_test::{admin, not_found, objects, posts, preview, repo};
 
 use bytes::Bytes;
-use futures::Stream;
-use minty::Repo;
+use futures::{stream, Stream};
+use minty::{ErrorKind, Object, PreviewState, PreviewStatus, Repo};
+use reqwest::{
+    header::{CACHE_CONTROL, ETAG, IF_NONE_MATCH, LAST_MODIFIED},
+    StatusCode,
+};
 use sha2::{Digest, Sha256};
-use std::marker::Unpin;
-use tokio::{io::AsyncReadExt, test};
+use std::{marker::Unpin, time::Duration};
+use tokio::{io::AsyncReadExt, net::TcpListener, test, time};
 use tokio_util::io::StreamReader;
 use uuid::{uuid, Uuid};
 
@@ -32,6 +36,19 @@ async fn get_object() {
     assert_eq!(object.r#type, "video");
     assert_eq!(object.subtype, "mp4");
 
+    let metadata = &object.metadata;
+    assert!(metadata.width.is_some_and(|width| width > 0));
+    assert!(metadata.height.is_some_and(|height| height > 0));
+    assert!(metadata.duration_ms.is_some_and(|duration| duration > 0));
+    assert!(metadata.frame_rate.is_some());
+    assert!(metadata.video_codec.is_some());
+
+    let storyboard = object.storyboard.expect("video should have a storyboard");
+    assert!(storyboard.frames > 0);
+    assert!(storyboard.columns * storyboard.rows >= storyboard.frames);
+    assert!(storyboard.frame_width > 0 && storyboard.frame_height > 0);
+    assert!(object.animation_id.is_some());
+
     let posts: Vec<_> = object.posts.iter().map(|post| post.id).collect();
     assert!(posts.contains(&posts::BUNNY));
 
@@ -60,6 +77,281 @@ async fn get_object_data() {
     assert_eq!(hash, VIDEO_HASH);
 }
 
+#[test]
+async fn get_object_data_from() {
+    let repo = admin().await;
+
+    let (_, stream) = repo.get_object_data(IMAGE).await.unwrap();
+    let full = read(stream).await;
+
+    let offset = full.len() as u64 / 2;
+    let (summary, start, stream) =
+        repo.get_object_data_from(IMAGE, offset).await.unwrap();
+
+    assert_eq!(start, offset);
+    assert_eq!(summary.size, full.len() as u64);
+    assert_eq!(read(stream).await, full[offset as usize..]);
+
+    let err = repo
+        .get_object_data_from(IMAGE, full.len() as u64)
+        .await
+        .map(|(summary, ..)| summary)
+        .expect_err("range past the end should not be satisfiable");
+    assert!(matches!(err.kind(), ErrorKind::Client), "{err}");
+}
+
+#[test]
+async fn get_object_data_not_modified() {
+    let hash = admin().await.get_object(IMAGE).await.unwrap().hash;
+
+    let mut url = repo().url().clone();
+    url.set_path(&format!("object/{IMAGE}/data"));
+
+    let client = reqwest::Client::new();
+
+    let response = client.get(url.clone()).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let headers = response.headers();
+    let etag = headers.get(ETAG).expect("ETag should be set").clone();
+    assert_eq!(etag, format!(r#""{hash}""#).as_str());
+    assert!(headers.contains_key(CACHE_CONTROL));
+    assert!(headers.contains_key(LAST_MODIFIED));
+
+    let response = client
+        .get(url.clone())
+        .header(IF_NONE_MATCH, &etag)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    let headers = response.headers();
+    assert_eq!(headers.get(ETAG), Some(&etag));
+    assert!(headers.contains_key(CACHE_CONTROL));
+    assert!(response.bytes().await.unwrap().is_empty());
+
+    let response = client
+        .get(url)
+        .header(IF_NONE_MATCH, format!(r#""{}""#, "0".repeat(64)))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[test]
+async fn duplicate_objects() {
+    let repo = admin().await;
+
+    let (_, data) = repo.get_object_data(IMAGE).await.unwrap();
+    let mut bytes = Vec::new();
+    StreamReader::new(data)
+        .read_to_end(&mut bytes)
+        .await
+        .unwrap();
+    let bytes = Bytes::from(bytes);
+
+    let upload = |bytes: Bytes| {
+        repo.add_object(stream::iter([Ok::<_, std::io::Error>(bytes)]))
+    };
+
+    let first = upload(bytes.clone()).await.unwrap();
+    let second = upload(bytes).await.unwrap();
+
+    assert!(second
+        .duplicates
+        .iter()
+        .all(|duplicate| duplicate.object.id != second.object.id));
+
+    if second.object.id != first.object.id {
+        assert!(second
+            .duplicates
+            .iter()
+            .any(|duplicate| duplicate.object.id == first.object.id));
+    }
+
+    let hash = repo.get_object(first.object.id).await.unwrap().hash;
+    let duplicates = repo.get_objects_by_hash(&hash).await.unwrap();
+    assert!(duplicates
+        .iter()
+        .any(|duplicate| duplicate.object.id == first.object.id));
+
+    let duplicates = repo.get_objects_by_hash(&"0".repeat(64)).await;
+    assert!(duplicates.unwrap().is_empty());
+}
+
+#[test]
+async fn preview_queue() {
+    let repo = admin().await;
+
+    let (_, stream) = repo.get_object_data(IMAGE).await.unwrap();
+    let bytes = Bytes::from(read(stream).await);
+
+    let upload = repo
+        .add_object(stream::iter([Ok::<_, std::io::Error>(bytes)]))
+        .await
+        .unwrap();
+    let id = upload.object.id;
+
+    assert_eq!(upload.preview_state, PreviewState::Pending);
+    assert!(upload.object.preview_id.is_none());
+
+    let status = wait_for_preview(&repo, id).await;
+
+    assert_eq!(status.id, id);
+    assert_eq!(status.state, PreviewState::Done, "{:?}", status.error);
+    assert!(status.error.is_none());
+
+    let object = repo.get_object(id).await.unwrap();
+    assert!(object.preview_id.is_some());
+
+    let id = uuid!("d0b2ed4c-47f4-4a34-a1f9-3f2d0c3a0d59");
+    not_found!(repo.get_object_preview_status(id).await, "object", id);
+}
+
+#[test]
+async fn custom_preview_generator() {
+    // A 1x1 binary PPM image, supported by both the built-in image generator
+    // and the higher priority generator registered by the test server.
+    const PPM: &[u8] = b"P6\n1 1\n255\n\xff\x00\x00";
+
+    let repo = admin().await;
+
+    let (_, stream) = repo.get_object_data(IMAGE).await.unwrap();
+    let image = Bytes::from(read(stream).await);
+
+    for (bytes, custom) in [(Bytes::from_static(PPM), true), (image, false)] {
+        let upload = repo
+            .add_object(stream::iter([Ok::<_, std::io::Error>(bytes)]))
+            .await
+            .unwrap();
+        let id = upload.object.id;
+
+        let status = wait_for_preview(&repo, id).await;
+        assert_eq!(status.state, PreviewState::Done, "{:?}", status.error);
+
+        let object = repo.get_object(id).await.unwrap();
+        let codec = object.metadata.video_codec.as_deref();
+
+        if custom {
+            assert_eq!(object.subtype, "x-portable-pixmap");
+            assert_eq!(codec, Some(preview::CODEC));
+            assert!(object.preview_id.is_none());
+        } else {
+            assert_ne!(codec, Some(preview::CODEC));
+            assert!(object.preview_id.is_some());
+        }
+    }
+}
+
+#[test]
+async fn cbz_preview() {
+    // Contains a hidden resource fork and an invalid '10.png' alongside the
+    // first page, '2.png', which is the only valid image.
+    let object =
+        upload_preview(&include_bytes!("../fixtures/comic.cbz")[..]).await;
+
+    assert!(object.preview_id.is_some());
+}
+
+#[test]
+async fn epub_preview() {
+    // The package document lists the cover after an invalid image that
+    // would otherwise be used as the first image in the archive.
+    let object =
+        upload_preview(&include_bytes!("../fixtures/book.epub")[..]).await;
+
+    assert_eq!(object.r#type, "application");
+    assert!(object.preview_id.is_some());
+}
+
+#[test]
+async fn svg_preview() {
+    const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="300" height="200">
+  <rect width="300" height="200" fill="teal"/>
+  <circle cx="150" cy="100" r="80" fill="gold"/>
+</svg>
+"#;
+
+    let object = upload_preview(SVG).await;
+
+    assert_eq!(object.subtype, "svg+xml");
+    assert_eq!(object.metadata.width, Some(300));
+    assert_eq!(object.metadata.height, Some(200));
+
+    let preview = object.preview_id.expect("SVG should have a thumbnail");
+    assert_eq!(object.previews.get("thumbnail"), Some(&preview));
+}
+
+#[test]
+async fn svg_preview_external_resources() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="100" height="100">
+  <rect width="100" height="100" fill="teal"/>
+  <image href="http://{address}/image.png" width="100" height="100"/>
+  <image xlink:href="http://{address}/legacy.png" width="100" height="100"/>
+  <image href="file:///etc/hostname" width="100" height="100"/>
+</svg>
+"#
+    );
+
+    let object = upload_preview(svg).await;
+    assert!(object.preview_id.is_some());
+
+    let connection =
+        time::timeout(Duration::from_millis(100), listener.accept()).await;
+    assert!(connection.is_err(), "SVG preview fetched an external image");
+}
+
+#[test]
+async fn svg_preview_limits() {
+    const HEADER: &str =
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10">"#;
+    const FOOTER: &str = "</svg>\n";
+
+    // Exceeds the default file size limit of 5 MiB.
+    let large =
+        format!("{HEADER}<!-- {} -->{FOOTER}", "x".repeat(5 * 1024 * 1024));
+
+    // Exceeds the default limit of 100,000 XML nodes.
+    let complex = format!("{HEADER}{}{FOOTER}", "<g/>".repeat(100_001));
+
+    for (svg, message) in [
+        (large, "SVG file size exceeds limit"),
+        (complex, "failed to parse SVG"),
+    ] {
+        let status = generate_preview(svg).await;
+        let error = status.error.expect("SVG preview should fail");
+
+        assert_ne!(status.state, PreviewState::Done);
+        assert!(error.contains(message), "{error}");
+    }
+}
+
+#[test]
+async fn similar_objects() {
+    let repo = admin().await;
+
+    let similar = repo.get_similar_objects(IMAGE, Some(64)).await.unwrap();
+    assert!(similar.iter().all(|similar| similar.object.id != IMAGE));
+    assert!(similar
+        .windows(2)
+        .all(|pair| pair[0].distance <= pair[1].distance));
+
+    let err = repo
+        .get_similar_objects(IMAGE, Some(65))
+        .await
+        .expect_err("distance should be limited to the hash size");
+    assert!(matches!(err.kind(), ErrorKind::Client), "{err}");
+
+    let id = uuid!("5909db6d-2ced-47fc-8824-781f0e68cf8f");
+    not_found!(repo.get_similar_objects(id, None).await, "object", id);
+}
+
 #[test]
 async fn image_preview() {
     test_preview(IMAGE, IMAGE_PREVIEW_HASH).await;
@@ -72,13 +364,66 @@ async fn video_preview() {
 
 async fn test_preview(object: Uuid, expected_hash: &str) {
     let repo = admin().await;
-    let preview = repo.get_object(object).await.unwrap().preview_id.unwrap();
+    let object = repo.get_object(object).await.unwrap();
+    let preview = object.preview_id.unwrap();
+
+    assert_eq!(object.previews.get("thumbnail"), Some(&preview));
+
     let (_, stream) = repo.get_object_data(preview).await.unwrap();
     let hash = sha256sum(stream).await;
 
     assert_eq!(expected_hash, hash);
 }
 
+async fn generate_preview(data: impl Into<Bytes>) -> PreviewStatus {
+    let repo = admin().await;
+
+    let upload = repo
+        .add_object(stream::iter([Ok::<_, std::io::Error>(data.into())]))
+        .await
+        .unwrap();
+
+    wait_for_preview(&repo, upload.object.id).await
+}
+
+async fn upload_preview(data: impl Into<Bytes>) -> Object {
+    let status = generate_preview(data).await;
+    assert_eq!(status.state, PreviewState::Done, "{:?}", status.error);
+
+    admin().await.get_object(status.id).await.unwrap()
+}
+
+/// Waits until preview generation finishes or its first attempt fails.
+async fn wait_for_preview(repo: &impl Repo, id: Uuid) -> PreviewStatus {
+    time::timeout(Duration::from_secs(30), async {
+        loop {
+            let status = repo.get_object_preview_status(id).await.unwrap();
+
+            match status.state {
+                PreviewState::Pending | PreviewState::Running
+                    if status.error.is_none() =>
+                {
+                    time::sleep(Duration::from_millis(100)).await
+                }
+                _ => break status,
+            }
+        }
+    })
+    .await
+    .expect("preview generation should finish")
+}
+
+async fn read(
+    stream: impl Stream<Item = std::io::Result<Bytes>> + Unpin,
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    StreamReader::new(stream)
+        .read_to_end(&mut bytes)
+        .await
+        .unwrap();
+    bytes
+}
+
 async fn sha256sum(
     stream: impl Stream<Item = std::io::Result<Bytes>> + Unpin,
 ) -> String {
@@ -0,0 +1,88 @@
+use minty_test::repo;
+
+use minty::Repo;
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+use std::collections::HashSet;
+use tokio::test;
+use uuid::Uuid;
+
+const METHODS: &[&str] = &["delete", "get", "patch", "post", "put"];
+
+// Replaces each path parameter with a value every parameter type accepts.
+fn request_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            if segment.starts_with('{') {
+                Uuid::nil().to_string()
+            } else {
+                segment.to_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[test]
+async fn routes_documented() {
+    let doc = serde_json::to_value(mintyd::server::openapi()).unwrap();
+
+    let client = reqwest::Client::new();
+    let mut ids = HashSet::new();
+    let mut unrouted = Vec::new();
+
+    for (path, item) in doc["paths"].as_object().unwrap() {
+        for (method, operation) in item.as_object().unwrap() {
+            if !METHODS.contains(&method.as_str()) {
+                continue;
+            }
+
+            let id = operation["operationId"].as_str().unwrap();
+            assert!(ids.insert(id.to_owned()), "duplicate operation ID '{id}'");
+
+            let mut url = repo().url().clone();
+            url.set_path(&request_path(path));
+
+            let method =
+                Method::from_bytes(method.to_uppercase().as_bytes()).unwrap();
+            let response =
+                client.request(method.clone(), url).send().await.unwrap();
+
+            // Without a matching route, the router answers with an empty
+            // 404 or a 405 before any handler runs.
+            let routed = match response.status() {
+                StatusCode::METHOD_NOT_ALLOWED => false,
+                StatusCode::NOT_FOUND => {
+                    !response.bytes().await.unwrap().is_empty()
+                }
+                _ => true,
+            };
+
+            if !routed {
+                unrouted.push(format!("{method} {path}"));
+            }
+        }
+    }
+
+    assert!(!ids.is_empty());
+    assert!(
+        unrouted.is_empty(),
+        "OpenAPI document contains unrouted operations: {unrouted:?}"
+    );
+
+    let schemas = doc["components"]["schemas"].as_object().unwrap();
+
+    for name in ["Post", "PostPreview", "Tag", "User", "export.Data"] {
+        assert!(schemas.contains_key(name), "missing schema '{name}'");
+    }
+
+    for name in ["session", "token"] {
+        assert!(
+            matches!(
+                &doc["components"]["securitySchemes"][name],
+                Value::Object(_)
+            ),
+            "missing security scheme '{name}'"
+        );
+    }
+}
@@ -1,9 +1,12 @@
-use minty_test::{admin, not_found, objects, posts, tags, users};
+use minty_test::{admin, archive, not_found, objects, posts, tags, users};
 
+use bytes::Bytes;
+use futures::{stream, TryStreamExt};
 use minty::{
     text::{Description, Name, PostTitle},
     ErrorKind, Post, PostParts, Repo, Uuid, Visibility,
 };
+use std::io;
 use tokio::test;
 use uuid::uuid;
 
@@ -158,6 +161,93 @@ async fn create_post() {
     assert_eq!(tags.first().map(|tag| tag.id), Some(TAG));
 }
 
+#[test]
+async fn create_post_from_archive() {
+    const TITLE: &str = "Scans";
+    const FILES: &[&str] = &["page10.txt", ".hidden", "page2.txt", "page1.txt"];
+
+    let repo = admin().await;
+
+    let files: Vec<_> =
+        FILES.iter().map(|name| (*name, name.as_bytes())).collect();
+    let archive = tar(&files);
+
+    let upload = repo
+        .create_post_from_archive(
+            &PostParts {
+                title: Some(PostTitle::new(TITLE).unwrap()),
+                ..Default::default()
+            },
+            stream::iter([Ok::<_, io::Error>(archive)]),
+        )
+        .await
+        .unwrap();
+
+    assert!(upload.errors.is_empty());
+    assert_eq!(upload.objects.len(), 3);
+
+    let post = repo.get_post(upload.post_id).await.unwrap();
+    assert_eq!(post.title, TITLE);
+
+    let objects: Vec<_> = post.objects.iter().map(|object| object.id).collect();
+    let uploaded: Vec<_> =
+        upload.objects.iter().map(|object| object.id).collect();
+    assert_eq!(objects, uploaded);
+
+    let mut contents = Vec::new();
+    for id in objects {
+        let (_, data) = repo.get_object_data(id).await.unwrap();
+        let chunks: Vec<Bytes> = data.try_collect().await.unwrap();
+        contents.push(chunks.concat());
+    }
+    assert_eq!(contents, [&b"page1.txt"[..], b"page2.txt", b"page10.txt"]);
+
+    let err = repo
+        .create_post_from_archive(
+            &Default::default(),
+            stream::iter([Ok::<_, io::Error>(Bytes::from("not an archive"))]),
+        )
+        .await
+        .expect_err("unrecognized archive should be rejected");
+    assert!(matches!(err.kind(), ErrorKind::Client), "{err}");
+}
+
+#[test]
+async fn create_post_from_archive_limits() {
+    let repo = admin().await;
+
+    let large = vec![0; archive::MAX_ENTRY_SIZE as usize + 1];
+    let archive = tar(&[("large.bin", &large), ("small.txt", b"small")]);
+
+    let upload = repo
+        .create_post_from_archive(
+            &Default::default(),
+            stream::iter([Ok::<_, io::Error>(archive)]),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(upload.objects.len(), 1);
+    assert_eq!(upload.errors.len(), 1);
+
+    let error = &upload.errors[0];
+    assert_eq!(error.name, "large.bin");
+    assert!(
+        error.message.contains("exceeds size limit"),
+        "{}",
+        error.message
+    );
+
+    let err = repo
+        .create_post_from_archive(
+            &Default::default(),
+            stream::iter([Ok::<_, io::Error>(tar(&[("large.bin", &large)]))]),
+        )
+        .await
+        .expect_err("archive with only oversized entries should be rejected");
+    assert!(matches!(err.kind(), ErrorKind::Client), "{err}");
+}
+
 #[test]
 async fn delete_post() {
     let repo = admin().await;
@@ -336,3 +426,17 @@ async fn set_post_title() {
     assert_eq!(post.modified, update.date_modified);
     assert!(post.created < post.modified);
 }
+
+fn tar(files: &[(&str, &[u8])]) -> Bytes {
+    let mut archive = tar::Builder::new(Vec::new());
+
+    for (name, data) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        archive.append_data(&mut header, name, *data).unwrap();
+    }
+
+    Bytes::from(archive.into_inner().unwrap())
+}
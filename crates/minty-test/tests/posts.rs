@@ -1,7 +1,12 @@
-use minty_test::{admin, posts::*, tags::LANGUAGES};
+use minty_test::{
+    admin,
+    posts::*,
+    tags::{LANGUAGES, PHOTOS},
+};
 
 use minty::{
-    Pagination, PostQuery, PostSort, PostSortValue::*, Repo, SortOrder::*,
+    ErrorKind, NumberRange, Orientation, Pagination, PostQuery, PostSort,
+    PostSortValue::*, Repo, SortOrder::*,
 };
 use tokio::test;
 use uuid::Uuid;
@@ -91,16 +96,256 @@ async fn sort_title_descending() {
     .await;
 }
 
+#[test]
+async fn any_tags() {
+    find(
+        PostQuery {
+            any_tags: vec![vec![PHOTOS, LANGUAGES]],
+            sort: PostSort::TITLE,
+            ..Default::default()
+        },
+        [C, CPP, JAVA, JS, RUST],
+    )
+    .await;
+}
+
+#[test]
+async fn exclude_tags() {
+    search(
+        PostQuery {
+            any_tags: vec![vec![PHOTOS, LANGUAGES]],
+            exclude_tags: vec![LANGUAGES],
+            ..Default::default()
+        },
+        [SAND],
+    )
+    .await;
+}
+
+#[test]
+async fn created_range() {
+    find(
+        PostQuery {
+            created: "2024-04-12T23:50:00-04:00..2024-04-12T23:50:30-04:00"
+                .parse()
+                .unwrap(),
+            sort: PostSort::TITLE,
+            ..Default::default()
+        },
+        [JAVA, JS],
+    )
+    .await;
+
+    find(
+        PostQuery {
+            created: "..2024-04-12T23:50:00-04:00".parse().unwrap(),
+            sort: PostSort::TITLE,
+            ..Default::default()
+        },
+        [C, CPP],
+    )
+    .await;
+}
+
+#[test]
+async fn created_day_boundary() {
+    let day = admin()
+        .await
+        .get_post(C)
+        .await
+        .unwrap()
+        .created
+        .date_naive();
+    let before = day.pred_opt().unwrap();
+    let after = day.succ_opt().unwrap();
+    let languages = [C, CPP, JAVA, JS, RUST];
+
+    for (range, expected) in [
+        (day.to_string(), languages.as_slice()),
+        (format!(">{before}"), languages.as_slice()),
+        (format!(">{day}"), [].as_slice()),
+        (format!("<{day}"), [].as_slice()),
+        (format!("<{after}"), languages.as_slice()),
+    ] {
+        find(
+            PostQuery {
+                created: range.parse().unwrap(),
+                sort: PostSort::TITLE,
+                ..Default::default()
+            },
+            expected,
+        )
+        .await;
+    }
+}
+
+#[test]
+async fn tag_facets() {
+    let repo = admin().await;
+
+    let result = repo
+        .get_posts(&PostQuery {
+            any_tags: vec![vec![PHOTOS, LANGUAGES]],
+            facets: 10,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let facets: Vec<_> = result
+        .facets
+        .iter()
+        .map(|facet| (facet.tag.id, facet.count))
+        .collect();
+    assert_eq!([(LANGUAGES, 5), (PHOTOS, 1)].as_slice(), facets);
+
+    let result = repo
+        .get_posts(&PostQuery {
+            tags: vec![LANGUAGES],
+            facets: 10,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    assert!(result.facets.is_empty());
+}
+
+#[test]
+async fn search_cursor() {
+    let repo = admin().await;
+    let mut query = PostQuery {
+        pagination: Pagination { from: 0, size: 2 },
+        tags: vec![LANGUAGES],
+        sort: PostSort::TITLE,
+        ..Default::default()
+    };
+    let mut hits = Vec::new();
+
+    loop {
+        let result = repo.get_posts(&query).await.unwrap().posts;
+        assert_eq!(5, result.total);
+
+        hits.extend(result.hits.iter().map(|hit| hit.id));
+
+        match result.cursor {
+            Some(cursor) => query.cursor = Some(cursor),
+            None => break,
+        }
+    }
+
+    assert_eq!([C, CPP, JAVA, JS, RUST].as_slice(), hits);
+
+    query.cursor = Some("invalid".into());
+
+    let err = repo
+        .get_posts(&query)
+        .await
+        .expect_err("expected invalid cursor to fail");
+    assert!(matches!(err.kind(), ErrorKind::Client), "{err}");
+}
+
+#[test]
+async fn search_media() {
+    search(
+        PostQuery {
+            media_type: Some("video".into()),
+            ..Default::default()
+        },
+        [BUNNY],
+    )
+    .await;
+    search(
+        PostQuery {
+            media_type: Some("video/mp4".into()),
+            duration_ms: NumberRange {
+                min: Some(1),
+                max: None,
+            },
+            ..Default::default()
+        },
+        [BUNNY],
+    )
+    .await;
+    search(
+        PostQuery {
+            media_type: Some("image".into()),
+            ..Default::default()
+        },
+        [SAND],
+    )
+    .await;
+    search(
+        PostQuery {
+            media_type: Some("video".into()),
+            orientation: Some(Orientation::Landscape),
+            ..Default::default()
+        },
+        [BUNNY],
+    )
+    .await;
+}
+
+#[test]
+async fn search_terms() {
+    find_terms(
+        "\"programming languages\" sort:title",
+        [C, CPP, JAVA, JS, RUST],
+    )
+    .await;
+    find_terms(&format!("{LANGUAGES} text:java"), [JAVA]).await;
+    find_terms(
+        &format!("{LANGUAGES} created:>2024-04-12T23:50:30-04:00"),
+        [RUST],
+    )
+    .await;
+    find_terms(
+        "photos|tag:\"Programming Languages\" -\"programming languages\"",
+        [SAND],
+    )
+    .await;
+    find_terms("media:video duration:>1", [BUNNY]).await;
+}
+
+#[test]
+async fn search_terms_invalid() {
+    let repo = admin().await;
+
+    for terms in [
+        "sort:size",
+        "vis:hidden",
+        "-sort:title",
+        "tag:\"photos",
+        "width:wide",
+        "orientation:diagonal",
+        "sortt:title",
+        "photos|sortt:title",
+    ] {
+        let err = repo
+            .search_posts(terms, &Default::default())
+            .await
+            .expect_err("expected invalid search terms to fail");
+
+        assert!(matches!(err.kind(), ErrorKind::Client), "{terms}: {err}");
+    }
+}
+
 async fn find<T>(mut query: PostQuery, expected: T)
+where
+    T: AsRef<[Uuid]>,
+{
+    query.tags.push(LANGUAGES);
+    search(query, expected).await;
+}
+
+async fn search<T>(query: PostQuery, expected: T)
 where
     T: AsRef<[Uuid]>,
 {
     let expected = expected.as_ref();
     let repo = admin().await;
 
-    query.tags.push(LANGUAGES);
-
-    let result = repo.get_posts(&query).await.unwrap();
+    let result = repo.get_posts(&query).await.unwrap().posts;
     assert_eq!(expected.len(), result.hits.len());
 
     let hits: Vec<_> = result.hits.iter().map(|hit| hit.id).collect();
@@ -121,3 +366,19 @@ where
     )
     .await;
 }
+
+async fn find_terms<T>(terms: &str, expected: T)
+where
+    T: AsRef<[Uuid]>,
+{
+    let expected = expected.as_ref();
+    let repo = admin().await;
+
+    let result = repo
+        .search_posts(terms, &Default::default())
+        .await
+        .unwrap()
+        .posts;
+    let hits: Vec<_> = result.hits.iter().map(|hit| hit.id).collect();
+    assert_eq!(expected, hits);
+}
@@ -1,8 +1,9 @@
 use minty_test::{admin, not_found};
 
 use minty::{
-    text::{Description, Name},
-    Pagination, ProfileQuery, Repo, Url,
+    text::{Category, Description, Name},
+    ErrorKind, Pagination, PostParts, PostQuery, ProfileQuery, Repo, Url,
+    Visibility,
 };
 use tokio::test;
 use uuid::uuid;
@@ -160,6 +161,160 @@ async fn delete_tag_sources() {
     );
 }
 
+#[test]
+async fn tag_implications() {
+    let repo = admin().await;
+
+    let mut tags = Vec::new();
+
+    for name in ["Siamese", "Cat", "Animal"] {
+        let name = Name::new(name).unwrap();
+        tags.push(repo.add_tag(name).await.unwrap());
+    }
+
+    let [siamese, cat, animal] = tags[..] else {
+        unreachable!()
+    };
+
+    for _ in 0..2 {
+        repo.add_tag_implication(siamese, cat).await.unwrap();
+    }
+
+    repo.add_tag_implication(cat, animal).await.unwrap();
+
+    let implied = repo.get_tag_implications(siamese).await.unwrap();
+    assert_eq!(implied.len(), 1);
+    assert_eq!(implied.first().unwrap().id, cat);
+
+    for (tag, implied) in [(animal, siamese), (cat, cat)] {
+        let err = repo
+            .add_tag_implication(tag, implied)
+            .await
+            .expect_err("tag implications should not form a cycle");
+        match err.kind() {
+            ErrorKind::Client => (),
+            _ => panic!("unexpected error: {err:?}"),
+        }
+    }
+
+    let id = uuid!("bd6e4b4e-3bf0-4fbf-9b4e-5f2c5b0e4bd6");
+    not_found!(repo.add_tag_implication(siamese, id).await, "tag", id);
+    not_found!(repo.add_tag_implication(id, siamese).await, "tag", id);
+
+    let post = repo.create_post(&Default::default()).await.unwrap();
+    repo.add_post_tag(post, siamese).await.unwrap();
+
+    let mut post_tags: Vec<_> = repo
+        .get_post(post)
+        .await
+        .unwrap()
+        .tags
+        .into_iter()
+        .map(|tag| tag.id)
+        .collect();
+    post_tags.sort();
+    tags.sort();
+    assert_eq!(post_tags, tags);
+
+    assert_eq!(repo.get_tag(animal).await.unwrap().post_count, 1);
+
+    repo.delete_tag_implication(siamese, cat).await.unwrap();
+    assert!(repo.get_tag_implications(siamese).await.unwrap().is_empty());
+
+    not_found!(
+        repo.delete_tag_implication(siamese, cat).await,
+        "implication not found"
+    );
+}
+
+#[test]
+async fn tag_categories() {
+    const CATEGORY: &str = "breed";
+
+    let repo = admin().await;
+
+    assert!(Category::new("tag").is_err());
+    assert!(Category::new("Breed").is_err());
+
+    let category = Category::new(CATEGORY).unwrap();
+    repo.add_tag_category(category).await.unwrap();
+    assert!(repo
+        .get_tag_categories()
+        .await
+        .unwrap()
+        .iter()
+        .any(|category| category == CATEGORY));
+
+    let name = Name::new("Maine Coon").unwrap();
+    let breed = repo.add_tag(name.clone()).await.unwrap();
+    let other = repo.add_tag(name).await.unwrap();
+
+    repo.set_tag_category(breed, Some(CATEGORY)).await.unwrap();
+    assert_eq!(
+        Some(CATEGORY),
+        repo.get_tag(breed).await.unwrap().category.as_deref()
+    );
+
+    let err = repo
+        .set_tag_category(other, Some("colour"))
+        .await
+        .expect_err("tag category should not exist");
+    match err.kind() {
+        ErrorKind::Client => (),
+        _ => panic!("unexpected error: {err:?}"),
+    }
+
+    let query = ProfileQuery {
+        pagination: Pagination {
+            from: 0,
+            size: 1_000,
+        },
+        name: "maine".into(),
+        exclude: Default::default(),
+        category: Some(CATEGORY.into()),
+        cursor: None,
+    };
+    let hits: Vec<_> = repo
+        .search_tags(&query)
+        .await
+        .unwrap()
+        .hits
+        .into_iter()
+        .map(|hit| hit.id)
+        .collect();
+    assert!(hits.contains(&breed));
+    assert!(!hits.contains(&other));
+
+    let mut posts = Vec::new();
+
+    for tag in [breed, other] {
+        let parts = PostParts {
+            tags: Some(vec![tag]),
+            ..Default::default()
+        };
+        posts.push(repo.create_post(&parts).await.unwrap());
+    }
+
+    let hits: Vec<_> = repo
+        .search_posts("breed:\"maine coon\"", &Default::default())
+        .await
+        .unwrap()
+        .posts
+        .hits
+        .into_iter()
+        .map(|hit| hit.id)
+        .collect();
+    assert_eq!(posts[..1], hits);
+
+    repo.delete_tag_category(CATEGORY).await.unwrap();
+    assert!(repo.get_tag(breed).await.unwrap().category.is_none());
+
+    not_found!(
+        repo.delete_tag_category(CATEGORY).await,
+        "tag category should not exist"
+    );
+}
+
 #[test]
 async fn get_tags() {
     let repo = admin().await;
@@ -176,6 +331,8 @@ async fn get_tags() {
         },
         name: "java".into(),
         exclude: Default::default(),
+        category: None,
+        cursor: None,
     };
 
     let mut result = repo.search_tags(&query).await.unwrap();
@@ -195,6 +352,99 @@ async fn get_tags() {
     assert!(!hits.contains(&java));
 }
 
+#[test]
+async fn merge_tag() {
+    let repo = admin().await;
+
+    let id = repo
+        .add_tag(Name::new("Science Fiction").unwrap())
+        .await
+        .unwrap();
+    let other = repo.add_tag(Name::new("scifi").unwrap()).await.unwrap();
+
+    repo.add_tag_alias(other, Name::new("sf").unwrap())
+        .await
+        .unwrap();
+    repo.set_tag_description(other, Description::new("Speculative").unwrap())
+        .await
+        .unwrap();
+
+    let url = Url::parse("https://example.com/scifi").unwrap();
+    repo.add_tag_source(other, &url).await.unwrap();
+
+    let first = repo.create_post(&Default::default()).await.unwrap();
+    let second = repo.create_post(&Default::default()).await.unwrap();
+
+    repo.add_post_tag(first, other).await.unwrap();
+    repo.add_post_tag(second, id).await.unwrap();
+    repo.add_post_tag(second, other).await.unwrap();
+
+    let tag = repo.merge_tag(id, other).await.unwrap();
+
+    assert_eq!(tag.profile.name, "Science Fiction");
+    assert_eq!(tag.profile.aliases, ["scifi", "sf"]);
+    assert_eq!(tag.profile.description, "Speculative");
+    assert_eq!(tag.profile.sources.len(), 1);
+    assert_eq!(tag.profile.sources.first().unwrap().url, url);
+    assert_eq!(tag.post_count, 2);
+
+    not_found!(repo.get_tag(other).await, "tag", other);
+
+    for post in [first, second] {
+        let tags = repo.get_post(post).await.unwrap().tags;
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags.first().unwrap().id, id);
+    }
+
+    let mut query = PostQuery {
+        visibility: Visibility::Draft,
+        ..Default::default()
+    };
+
+    query.tags.push(id);
+    assert_eq!(repo.get_posts(&query).await.unwrap().posts.total, 2);
+
+    query.tags = vec![other];
+    assert_eq!(repo.get_posts(&query).await.unwrap().posts.total, 0);
+
+    let err = repo
+        .merge_tag(id, id)
+        .await
+        .expect_err("tag cannot be merged with itself");
+    match err.kind() {
+        ErrorKind::Client => (),
+        _ => panic!("unexpected error: {err:?}"),
+    }
+
+    not_found!(repo.merge_tag(id, other).await, "tag", other);
+}
+
+#[test]
+async fn merge_tag_implications() {
+    let repo = admin().await;
+
+    let kitten = repo.add_tag(Name::new("Kitten").unwrap()).await.unwrap();
+    let cat = repo.add_tag(Name::new("Housecat").unwrap()).await.unwrap();
+    let pet = repo.add_tag(Name::new("Pet").unwrap()).await.unwrap();
+    let animal = repo.add_tag(Name::new("Critter").unwrap()).await.unwrap();
+
+    repo.add_tag_implication(kitten, cat).await.unwrap();
+    repo.add_tag_implication(cat, pet).await.unwrap();
+    repo.add_tag_implication(pet, animal).await.unwrap();
+
+    let err = repo
+        .merge_tag(animal, cat)
+        .await
+        .expect_err("merge should not create an implication cycle");
+    assert!(matches!(err.kind(), ErrorKind::Client), "{err}");
+    repo.get_tag(cat).await.unwrap();
+
+    repo.merge_tag(cat, kitten).await.unwrap();
+    let implied = repo.get_tag_implications(cat).await.unwrap();
+    let implied: Vec<_> = implied.iter().map(|tag| tag.id).collect();
+    assert_eq!(implied, [pet]);
+}
+
 #[test]
 async fn set_tag_description() {
     const NAME: &str = "Tag Name";
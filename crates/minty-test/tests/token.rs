@@ -0,0 +1,152 @@
+use minty_test::{next_user, with_token, ResultExt};
+
+use minty::{
+    text::{Description, Email, Name, PostTitle},
+    DateTime, ErrorKind, NewToken, PostParts, Repo, TokenScope,
+};
+use std::time::{Duration, SystemTime};
+use tokio::test;
+
+fn new_token(name: &str, scopes: &[TokenScope]) -> NewToken {
+    NewToken {
+        name: Name::new(name).unwrap(),
+        scopes: scopes.to_vec(),
+        expiration: None,
+    }
+}
+
+#[test]
+async fn create_token() {
+    let repo = next_user().await;
+    let user = repo.get_authenticated_user().await.unwrap();
+
+    let week = Duration::from_secs(7 * 24 * 60 * 60);
+    let expiration: DateTime = (SystemTime::now() + week).into();
+    let secret = repo
+        .create_token(&NewToken {
+            expiration: Some(expiration),
+            ..new_token("Create Token", &[TokenScope::Posts, TokenScope::Read])
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(secret.token.name, "Create Token");
+    assert_eq!(secret.token.scopes, [TokenScope::Read, TokenScope::Posts]);
+    assert!(secret.token.last_used.is_none());
+
+    let client = with_token(&secret.secret);
+    assert_eq!(client.get_authenticated_user().await.unwrap().id, user.id);
+
+    let tokens = repo.get_tokens().await.unwrap();
+    assert_eq!(tokens.len(), 1);
+
+    let token = tokens.first().unwrap();
+    assert_eq!(token.id, secret.token.id);
+    assert_eq!(
+        token.expiration.unwrap().timestamp(),
+        expiration.timestamp()
+    );
+    assert!(token.last_used.is_some());
+}
+
+#[test]
+async fn token_scopes() {
+    let repo = next_user().await;
+    let secret = repo
+        .create_token(&new_token("Read Only", &[TokenScope::Read]))
+        .await
+        .unwrap();
+
+    let client = with_token(&secret.secret);
+    let parts = PostParts {
+        title: Some(PostTitle::new("Token Post").unwrap()),
+        ..Default::default()
+    };
+
+    let err = client.create_post(&parts).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Client);
+
+    let err = client
+        .add_tag(Name::new("Token Tag").unwrap())
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Client);
+
+    let secret = repo
+        .create_token(&new_token("Posts", &[TokenScope::Posts]))
+        .await
+        .unwrap();
+
+    let client = with_token(&secret.secret);
+    let post = client.create_post(&parts).await.unwrap();
+    assert_eq!(client.get_post(post).await.unwrap().id, post);
+
+    let err = client
+        .add_tag(Name::new("Token Tag").unwrap())
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Client);
+
+    let err = client
+        .create_token(&new_token("Escalate", &[TokenScope::Admin]))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Client);
+
+    let secret = repo
+        .create_token(&new_token("Account", &[TokenScope::Account]))
+        .await
+        .unwrap();
+
+    let client = with_token(&secret.secret);
+    let description = Description::new("Token Description").unwrap();
+    client.set_user_description(description).await.unwrap();
+
+    let err = client
+        .set_user_email(Email::new("token@example.com").unwrap())
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Client);
+
+    let err = client
+        .create_token(&new_token("Escalate", &[TokenScope::Admin]))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Client);
+}
+
+#[test]
+async fn revoke_token() {
+    let repo = next_user().await;
+    let secret = repo
+        .create_token(&new_token("Revoke Token", &[TokenScope::Read]))
+        .await
+        .unwrap();
+
+    let client = with_token(&secret.secret);
+    client.get_authenticated_user().await.unwrap();
+
+    repo.revoke_token(secret.token.id).await.unwrap();
+
+    client
+        .get_authenticated_user()
+        .await
+        .expect_unauthenticated();
+    assert!(repo.get_tokens().await.unwrap().is_empty());
+    repo.revoke_token(secret.token.id).await.expect_not_found();
+}
+
+#[test]
+async fn invalid_token() {
+    with_token("invalid")
+        .get_authenticated_user()
+        .await
+        .expect_unauthenticated();
+
+    let repo = next_user().await;
+    let err = repo
+        .create_token(&new_token("No Scopes", &[]))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Client);
+}
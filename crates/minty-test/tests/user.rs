@@ -176,6 +176,8 @@ async fn get_users() {
         },
         name: "minty".into(),
         exclude: Default::default(),
+        category: None,
+        cursor: None,
     };
 
     let result = repo.get_users(&query).await.unwrap();
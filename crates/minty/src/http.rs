@@ -3,6 +3,7 @@ pub mod query;
 
 mod client;
 mod model;
+mod sse;
 
 pub use model::*;
 
@@ -13,7 +14,10 @@ use crate::{model::*, text, Error, Result};
 
 use bytes::Bytes;
 use futures_core::{Stream, TryStream};
-use reqwest::ClientBuilder;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, AUTHORIZATION},
+    ClientBuilder,
+};
 use std::{error::Error as StdError, io, sync::Arc};
 
 #[derive(Clone, Debug)]
@@ -22,11 +26,13 @@ pub enum Credentials {
     Cookies,
     CookieJar(Arc<Jar>),
     CookieFile(Arc<CookieFile>),
+    Token(String),
 }
 
 #[derive(Debug)]
 pub struct RepoBuilder {
     builder: ClientBuilder,
+    token: Option<String>,
     url: Url,
 }
 
@@ -34,11 +40,21 @@ impl RepoBuilder {
     fn new(url: Url) -> Self {
         Self {
             builder: ClientBuilder::new(),
+            token: None,
             url,
         }
     }
 
-    pub fn build(self) -> Result<Repo> {
+    pub fn build(mut self) -> Result<Repo> {
+        if let Some(token) = self.token.take() {
+            let mut value = HeaderValue::try_from(format!("Bearer {token}"))
+                .map_err(|err| Error::other(format!("invalid token: {err}")))?;
+            value.set_sensitive(true);
+
+            let headers = HeaderMap::from_iter([(AUTHORIZATION, value)]);
+            self.builder = self.builder.default_headers(headers);
+        }
+
         let client = self.builder.build().map_err(|err| {
             Error::other(format!("failed to build HTTP client: {err}"))
         })?;
@@ -49,11 +65,17 @@ impl RepoBuilder {
     }
 
     pub fn credentials(mut self, credentials: Credentials) -> Self {
+        self.token = None;
+
         self.builder = match credentials {
             Credentials::None => self.builder.cookie_store(false),
             Credentials::Cookies => self.builder.cookie_store(true),
             Credentials::CookieJar(jar) => self.builder.cookie_provider(jar),
             Credentials::CookieFile(file) => self.builder.cookie_provider(file),
+            Credentials::Token(token) => {
+                self.token = Some(token);
+                self.builder.cookie_store(false)
+            }
         };
 
         self
@@ -105,7 +127,7 @@ impl crate::Repo for Repo {
             .await
     }
 
-    async fn add_object<S>(&self, stream: S) -> Result<ObjectPreview>
+    async fn add_object<S>(&self, stream: S) -> Result<ObjectUpload>
     where
         S: TryStream + Send + Sync + 'static,
         S::Error: Into<Box<dyn StdError + Send + Sync>>,
@@ -178,6 +200,24 @@ impl crate::Repo for Repo {
             .await
     }
 
+    async fn add_tag_category(&self, name: text::Category) -> Result<()> {
+        self.client.put(format!("categories/{name}")).send().await?;
+        Ok(())
+    }
+
+    async fn add_tag_implication(
+        &self,
+        tag_id: Uuid,
+        implied_tag_id: Uuid,
+    ) -> Result<()> {
+        self.client
+            .put(format!("tag/{tag_id}/implies/{implied_tag_id}"))
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
     async fn add_tag_source(&self, tag_id: Uuid, url: &Url) -> Result<Source> {
         self.client
             .post(format!("tag/{tag_id}/source"))
@@ -241,6 +281,36 @@ impl crate::Repo for Repo {
             .await
     }
 
+    async fn create_post_from_archive<S>(
+        &self,
+        parts: &PostParts,
+        stream: S,
+    ) -> Result<ArchiveUpload>
+    where
+        S: TryStream + Send + Sync + 'static,
+        S::Error: Into<Box<dyn StdError + Send + Sync>>,
+        Bytes: From<S::Ok>,
+    {
+        self.client
+            .post("post/archive")
+            .query(&query::PostArchive::from(parts.clone()))
+            .stream(stream)
+            .send()
+            .await?
+            .deserialize()
+            .await
+    }
+
+    async fn create_token(&self, token: &NewToken) -> Result<TokenSecret> {
+        self.client
+            .post("user/token")
+            .json(token)
+            .send()
+            .await?
+            .deserialize()
+            .await
+    }
+
     async fn delete_comment(&self, id: Uuid, recursive: bool) -> Result<()> {
         self.client
             .delete(format!("comment/{id}"))
@@ -310,6 +380,27 @@ impl crate::Repo for Repo {
             .await
     }
 
+    async fn delete_tag_category(&self, name: &str) -> Result<()> {
+        self.client
+            .delete(format!("categories/{name}"))
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_tag_implication(
+        &self,
+        tag_id: Uuid,
+        implied_tag_id: Uuid,
+    ) -> Result<()> {
+        self.client
+            .delete(format!("tag/{tag_id}/implies/{implied_tag_id}"))
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
     async fn delete_tag_source(
         &self,
         tag_id: Uuid,
@@ -430,6 +521,20 @@ impl crate::Repo for Repo {
             .object()
     }
 
+    async fn get_object_data_from(
+        &self,
+        id: Uuid,
+        offset: u64,
+    ) -> Result<(ObjectSummary, u64, impl Stream<Item = io::Result<Bytes>>)>
+    {
+        self.client
+            .get(format!("object/{id}/data"))
+            .range(offset)
+            .send()
+            .await?
+            .object_range()
+    }
+
     async fn get_object_preview_errors(&self) -> Result<Vec<ObjectError>> {
         self.client
             .get("objects/errors")
@@ -439,6 +544,31 @@ impl crate::Repo for Repo {
             .await
     }
 
+    async fn get_object_preview_status(
+        &self,
+        id: Uuid,
+    ) -> Result<PreviewStatus> {
+        self.client
+            .get(format!("objects/{id}/preview"))
+            .send()
+            .await?
+            .deserialize()
+            .await
+    }
+
+    async fn get_objects_by_hash(
+        &self,
+        hash: &str,
+    ) -> Result<Vec<DuplicateObject>> {
+        self.client
+            .get("objects")
+            .query(&query::ObjectHash { hash: hash.into() })
+            .send()
+            .await?
+            .deserialize()
+            .await
+    }
+
     async fn get_post(&self, id: Uuid) -> Result<Post> {
         self.client
             .get(format!("post/{id}"))
@@ -448,10 +578,7 @@ impl crate::Repo for Repo {
             .await
     }
 
-    async fn get_posts(
-        &self,
-        query: &PostQuery,
-    ) -> Result<SearchResult<PostPreview>> {
+    async fn get_posts(&self, query: &PostQuery) -> Result<PostSearchResult> {
         let query: query::PostQuery = query.clone().into();
         self.client
             .get("posts")
@@ -462,6 +589,20 @@ impl crate::Repo for Repo {
             .await
     }
 
+    async fn get_similar_objects(
+        &self,
+        id: Uuid,
+        distance: Option<u32>,
+    ) -> Result<Vec<SimilarObject>> {
+        self.client
+            .get(format!("objects/{id}/similar"))
+            .query(&query::SimilarObjects { distance })
+            .send()
+            .await?
+            .deserialize()
+            .await
+    }
+
     async fn get_tag(&self, id: Uuid) -> Result<Tag> {
         self.client
             .get(format!("tag/{id}"))
@@ -471,6 +612,27 @@ impl crate::Repo for Repo {
             .await
     }
 
+    async fn get_tag_categories(&self) -> Result<Vec<String>> {
+        self.client
+            .get("categories")
+            .send()
+            .await?
+            .deserialize()
+            .await
+    }
+
+    async fn get_tag_implications(
+        &self,
+        tag_id: Uuid,
+    ) -> Result<Vec<TagPreview>> {
+        self.client
+            .get(format!("tag/{tag_id}/implies"))
+            .send()
+            .await?
+            .deserialize()
+            .await
+    }
+
     async fn get_tags(&self, ids: &[Uuid]) -> Result<Vec<TagPreview>> {
         if ids.is_empty() {
             return Ok(Vec::new());
@@ -490,6 +652,15 @@ impl crate::Repo for Repo {
             .await
     }
 
+    async fn get_tokens(&self) -> Result<Vec<Token>> {
+        self.client
+            .get("user/token")
+            .send()
+            .await?
+            .deserialize()
+            .await
+    }
+
     async fn get_user(&self, id: Uuid) -> Result<User> {
         self.client
             .get(format!("user/{id}"))
@@ -536,6 +707,16 @@ impl crate::Repo for Repo {
             .await
     }
 
+    async fn merge_tag(&self, tag_id: Uuid, other: Uuid) -> Result<Tag> {
+        self.client
+            .post(format!("tag/{tag_id}/merge"))
+            .json(&other)
+            .send()
+            .await?
+            .deserialize()
+            .await
+    }
+
     async fn publish_post(&self, post_id: Uuid) -> Result<()> {
         self.client.put(format!("post/{post_id}")).send().await?;
         Ok(())
@@ -549,6 +730,31 @@ impl crate::Repo for Repo {
         Ok(())
     }
 
+    async fn revoke_token(&self, token_id: Uuid) -> Result<()> {
+        self.client
+            .delete(format!("user/token/{token_id}"))
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    async fn search_posts(
+        &self,
+        terms: &str,
+        query: &PostQuery,
+    ) -> Result<PostSearchResult> {
+        let mut query: query::PostQuery = query.clone().into();
+        query.search = Some(terms.into());
+
+        self.client
+            .get("posts")
+            .query(&query)
+            .send()
+            .await?
+            .deserialize()
+            .await
+    }
+
     async fn search_tags(
         &self,
         query: &ProfileQuery,
@@ -605,6 +811,22 @@ impl crate::Repo for Repo {
             .await
     }
 
+    async fn set_tag_category(
+        &self,
+        tag_id: Uuid,
+        category: Option<&str>,
+    ) -> Result<()> {
+        let request = match category {
+            Some(category) => {
+                self.client.put(format!("tag/{tag_id}/category/{category}"))
+            }
+            None => self.client.delete(format!("tag/{tag_id}/category")),
+        };
+
+        request.send().await?;
+        Ok(())
+    }
+
     async fn set_tag_description(
         &self,
         tag_id: Uuid,
@@ -699,4 +921,8 @@ impl crate::Repo for Repo {
             .uuid()
             .await
     }
+
+    async fn subscribe(&self) -> Result<impl Stream<Item = Result<Event>>> {
+        Ok(self.client.get("events").send().await?.events())
+    }
 }
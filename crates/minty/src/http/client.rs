@@ -1,10 +1,15 @@
+use super::sse::EventStream;
+
 use crate::{DateTime, Error, ErrorKind, ObjectSummary, Result, Url, Uuid};
 
 use bytes::Bytes;
 use futures_core::{Stream, TryStream};
 use log::debug;
 use mime::{Mime, TEXT_PLAIN_UTF_8};
-use reqwest::{header::CONTENT_TYPE, Body, Method, Request, StatusCode};
+use reqwest::{
+    header::{CONTENT_RANGE, CONTENT_TYPE, RANGE},
+    Body, Method, Request, StatusCode,
+};
 use serde::{de::DeserializeOwned, Serialize};
 use std::{error, io};
 use tokio_stream::StreamExt;
@@ -56,6 +61,10 @@ impl Response {
         })
     }
 
+    pub fn events(self) -> EventStream {
+        EventStream::new(Box::pin(self.inner.bytes_stream()))
+    }
+
     pub fn object(
         self,
     ) -> Result<(ObjectSummary, impl Stream<Item = io::Result<Bytes>>)> {
@@ -90,6 +99,44 @@ impl Response {
 
         Ok((summary, stream))
     }
+
+    pub fn object_range(
+        self,
+    ) -> Result<(ObjectSummary, u64, impl Stream<Item = io::Result<Bytes>>)>
+    {
+        if self.inner.status() != StatusCode::PARTIAL_CONTENT {
+            let (summary, stream) = self.object()?;
+            return Ok((summary, 0, stream));
+        }
+
+        let content_range = self
+            .inner
+            .headers()
+            .get(CONTENT_RANGE)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| {
+                Error::other("partial response is missing content-range".into())
+            })?;
+
+        let (start, size) = content_range
+            .strip_prefix("bytes ")
+            .and_then(|range| range.split_once('/'))
+            .and_then(|(range, size)| {
+                let (start, _) = range.split_once('-')?;
+                Some((start.parse().ok()?, size.parse().ok()?))
+            })
+            .ok_or_else(|| {
+                Error::other(format!(
+                    "received invalid content-range from server \
+                    '{content_range}'"
+                ))
+            })?;
+
+        let (mut summary, stream) = self.object()?;
+        summary.size = size;
+
+        Ok((summary, start, stream))
+    }
 }
 
 #[derive(Debug)]
@@ -112,6 +159,11 @@ impl RequestBuilder {
         self
     }
 
+    pub fn range(mut self, start: u64) -> Self {
+        self.inner = self.inner.header(RANGE, format!("bytes={start}-"));
+        self
+    }
+
     fn content_type(mut self, mime: Mime) -> Self {
         self.inner = self.inner.header(CONTENT_TYPE, mime.as_ref());
         self
@@ -1,12 +1,123 @@
-use crate::model::{PostSort, PostSortValue, SortOrder, Uuid, Visibility};
+use crate::{
+    model::{
+        DateRange, DateTime, NumberRange, Orientation, PostParts, PostSort,
+        PostSortValue, SortOrder, Uuid, Visibility,
+    },
+    text,
+};
 
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "openapi")]
+use utoipa::IntoParams;
+
+const GROUP_SEPARATOR: &str = ";";
+const ID_SEPARATOR: &str = ",";
+
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
 pub struct DeleteComment {
     pub recursive: Option<bool>,
 }
 
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct IdGroups(Vec<Ids>);
+
+impl IdGroups {
+    fn new(groups: &[Vec<Uuid>]) -> Option<Self> {
+        let groups: Vec<Ids> =
+            groups.iter().filter_map(|group| Ids::new(group)).collect();
+
+        if groups.is_empty() {
+            None
+        } else {
+            Some(Self(groups))
+        }
+    }
+}
+
+impl From<IdGroups> for String {
+    fn from(IdGroups(groups): IdGroups) -> Self {
+        groups
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<String>>()
+            .join(GROUP_SEPARATOR)
+    }
+}
+
+impl From<IdGroups> for Vec<Vec<Uuid>> {
+    fn from(IdGroups(groups): IdGroups) -> Self {
+        groups.into_iter().map(Vec::from).collect()
+    }
+}
+
+impl TryFrom<String> for IdGroups {
+    type Error = uuid::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value
+            .split(GROUP_SEPARATOR)
+            .filter(|group| !group.is_empty())
+            .map(Ids::parse)
+            .collect::<Result<Vec<Ids>, Self::Error>>()
+            .map(Self)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Ids(Vec<Uuid>);
+
+impl Ids {
+    fn new(ids: &[Uuid]) -> Option<Self> {
+        if ids.is_empty() {
+            None
+        } else {
+            Some(Self(ids.to_vec()))
+        }
+    }
+
+    fn parse(ids: &str) -> Result<Self, uuid::Error> {
+        ids.split(ID_SEPARATOR)
+            .map(Uuid::parse_str)
+            .collect::<Result<Vec<Uuid>, uuid::Error>>()
+            .map(Self)
+    }
+}
+
+impl From<Ids> for String {
+    fn from(Ids(ids): Ids) -> Self {
+        ids.iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<String>>()
+            .join(ID_SEPARATOR)
+    }
+}
+
+impl From<Ids> for Vec<Uuid> {
+    fn from(Ids(ids): Ids) -> Self {
+        ids
+    }
+}
+
+impl TryFrom<String> for Ids {
+    type Error = uuid::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct ObjectHash {
+    pub hash: String,
+}
+
 struct Pagination {
     pub from: Option<u32>,
     pub size: Option<u32>,
@@ -35,15 +146,92 @@ impl From<crate::Pagination> for Pagination {
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct PostArchive {
+    pub title: Option<text::PostTitle>,
+    pub description: Option<text::Description>,
+    pub vis: Option<Visibility>,
+    #[cfg_attr(feature = "openapi", param(value_type = Option<String>))]
+    pub posts: Option<Ids>,
+    #[cfg_attr(feature = "openapi", param(value_type = Option<String>))]
+    pub tags: Option<Ids>,
+}
+
+impl From<PostArchive> for PostParts {
+    fn from(
+        PostArchive {
+            title,
+            description,
+            vis,
+            posts,
+            tags,
+        }: PostArchive,
+    ) -> Self {
+        Self {
+            title,
+            description,
+            visibility: vis,
+            objects: None,
+            posts: posts.map(Vec::from),
+            tags: tags.map(Vec::from),
+        }
+    }
+}
+
+impl From<PostParts> for PostArchive {
+    fn from(
+        PostParts {
+            title,
+            description,
+            visibility,
+            objects: _,
+            posts,
+            tags,
+        }: PostParts,
+    ) -> Self {
+        Self {
+            title,
+            description,
+            vis: visibility,
+            posts: posts.as_deref().and_then(Ids::new),
+            tags: tags.as_deref().and_then(Ids::new),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
 pub struct PostQuery {
     pub from: Option<u32>,
     pub size: Option<u32>,
     pub u: Option<Uuid>,
     pub q: Option<String>,
-    pub tags: Option<String>,
+    pub search: Option<String>,
+    #[cfg_attr(feature = "openapi", param(value_type = Option<String>))]
+    pub tags: Option<Ids>,
+    #[cfg_attr(feature = "openapi", param(value_type = Option<String>))]
+    pub xtags: Option<Ids>,
+    #[cfg_attr(feature = "openapi", param(value_type = Option<String>))]
+    pub any: Option<IdGroups>,
+    pub created_after: Option<DateTime>,
+    pub created_before: Option<DateTime>,
+    pub modified_after: Option<DateTime>,
+    pub modified_before: Option<DateTime>,
+    pub media: Option<String>,
+    pub min_width: Option<u64>,
+    pub max_width: Option<u64>,
+    pub min_height: Option<u64>,
+    pub max_height: Option<u64>,
+    pub min_duration: Option<u64>,
+    pub max_duration: Option<u64>,
+    pub orientation: Option<Orientation>,
     pub vis: Option<Visibility>,
     pub sort: Option<PostSortValue>,
     pub order: Option<SortOrder>,
+    pub facets: Option<u32>,
+    pub cursor: Option<String>,
 }
 
 impl From<PostQuery> for crate::PostQuery {
@@ -53,10 +241,27 @@ impl From<PostQuery> for crate::PostQuery {
             size,
             u,
             q,
+            search: _,
             tags,
+            xtags,
+            any,
+            created_after,
+            created_before,
+            modified_after,
+            modified_before,
+            media,
+            min_width,
+            max_width,
+            min_height,
+            max_height,
+            min_duration,
+            max_duration,
+            orientation,
             vis,
             sort,
             order,
+            facets,
+            cursor,
         }: PostQuery,
     ) -> Self {
         let sort_value = sort.unwrap_or_default();
@@ -65,18 +270,38 @@ impl From<PostQuery> for crate::PostQuery {
             pagination: Pagination { from, size }.into(),
             poster: u,
             text: q.unwrap_or_default(),
-            tags: tags
-                .map(|tags| {
-                    tags.split(',')
-                        .map(|tag| Uuid::parse_str(tag).unwrap())
-                        .collect()
-                })
-                .unwrap_or_default(),
+            tags: tags.map(Vec::from).unwrap_or_default(),
+            exclude_tags: xtags.map(Vec::from).unwrap_or_default(),
+            any_tags: any.map(Vec::from).unwrap_or_default(),
+            created: DateRange {
+                after: created_after,
+                before: created_before,
+            },
+            modified: DateRange {
+                after: modified_after,
+                before: modified_before,
+            },
+            media_type: media,
+            width: NumberRange {
+                min: min_width,
+                max: max_width,
+            },
+            height: NumberRange {
+                min: min_height,
+                max: max_height,
+            },
+            duration_ms: NumberRange {
+                min: min_duration,
+                max: max_duration,
+            },
+            orientation,
             visibility: vis.unwrap_or_default(),
             sort: PostSort {
                 value: sort_value,
                 order: order.unwrap_or(sort_value.default_order()),
             },
+            facets: facets.unwrap_or_default(),
+            cursor,
         }
     }
 }
@@ -88,8 +313,19 @@ impl From<crate::PostQuery> for PostQuery {
             poster,
             text,
             tags,
+            exclude_tags,
+            any_tags,
+            created,
+            modified,
+            media_type,
+            width,
+            height,
+            duration_ms,
+            orientation,
             visibility,
             sort,
+            facets,
+            cursor,
         }: crate::PostQuery,
     ) -> Self {
         let Pagination { from, size } = pagination.into();
@@ -106,17 +342,22 @@ impl From<crate::PostQuery> for PostQuery {
                     Some(text.into())
                 }
             },
-            tags: if tags.is_empty() {
-                None
-            } else {
-                let tags = tags
-                    .into_iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                Some(tags)
-            },
+            search: None,
+            tags: Ids::new(&tags),
+            xtags: Ids::new(&exclude_tags),
+            any: IdGroups::new(&any_tags),
+            created_after: created.after,
+            created_before: created.before,
+            modified_after: modified.after,
+            modified_before: modified.before,
+            media: media_type,
+            min_width: width.min,
+            max_width: width.max,
+            min_height: height.min,
+            max_height: height.max,
+            min_duration: duration_ms.min,
+            max_duration: duration_ms.max,
+            orientation,
             vis: if visibility != Visibility::default() {
                 Some(visibility)
             } else {
@@ -132,16 +373,23 @@ impl From<crate::PostQuery> for PostQuery {
             } else {
                 None
             },
+            facets: if facets > 0 { Some(facets) } else { None },
+            cursor,
         }
     }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
 pub struct ProfileQuery {
     pub from: Option<u32>,
     pub size: Option<u32>,
     pub name: String,
-    pub exclude: Option<String>,
+    #[cfg_attr(feature = "openapi", param(value_type = Option<String>))]
+    pub exclude: Option<Ids>,
+    pub category: Option<String>,
+    pub cursor: Option<String>,
 }
 
 impl From<ProfileQuery> for crate::ProfileQuery {
@@ -151,18 +399,16 @@ impl From<ProfileQuery> for crate::ProfileQuery {
             size,
             name,
             exclude,
+            category,
+            cursor,
         }: ProfileQuery,
     ) -> Self {
         Self {
             pagination: Pagination { from, size }.into(),
             name,
-            exclude: exclude
-                .map(|tags| {
-                    tags.split(',')
-                        .map(|tag| Uuid::parse_str(tag).unwrap())
-                        .collect()
-                })
-                .unwrap_or_default(),
+            exclude: exclude.map(Vec::from).unwrap_or_default(),
+            category,
+            cursor,
         }
     }
 }
@@ -173,6 +419,8 @@ impl From<crate::ProfileQuery> for ProfileQuery {
             pagination,
             name,
             exclude,
+            category,
+            cursor,
         }: crate::ProfileQuery,
     ) -> Self {
         let Pagination { from, size } = pagination.into();
@@ -181,22 +429,23 @@ impl From<crate::ProfileQuery> for ProfileQuery {
             from,
             size,
             name,
-            exclude: if exclude.is_empty() {
-                None
-            } else {
-                let tags = exclude
-                    .into_iter()
-                    .map(|id| id.to_string())
-                    .collect::<Vec<String>>()
-                    .join(",");
-
-                Some(tags)
-            },
+            exclude: Ids::new(&exclude),
+            category,
+            cursor,
         }
     }
 }
 
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct SimilarObjects {
+    pub distance: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
 pub struct SetProfileName {
     pub main: Option<bool>,
 }
@@ -208,6 +457,8 @@ impl SetProfileName {
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
 pub struct SignUp {
     pub invitation: Option<String>,
 }
@@ -0,0 +1,102 @@
+use crate::{Error, Event, Result};
+
+use bytes::{Buf, Bytes, BytesMut};
+use futures_core::Stream;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+type Inner = Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>;
+
+pub struct EventStream {
+    inner: Inner,
+    buffer: BytesMut,
+    data: String,
+    done: bool,
+}
+
+impl EventStream {
+    pub(super) fn new(inner: Inner) -> Self {
+        Self {
+            inner,
+            buffer: BytesMut::new(),
+            data: String::new(),
+            done: false,
+        }
+    }
+
+    fn next_line(&mut self) -> Option<String> {
+        let end = self.buffer.iter().position(|&byte| byte == b'\n')?;
+        let line = self.buffer.split_to(end);
+        self.buffer.advance(1);
+
+        let line = line.strip_suffix(b"\r").unwrap_or(&line);
+        Some(String::from_utf8_lossy(line).into_owned())
+    }
+
+    fn next_event(&mut self) -> Option<Result<Event>> {
+        while let Some(line) = self.next_line() {
+            if line.is_empty() {
+                if self.data.is_empty() {
+                    continue;
+                }
+
+                let data = std::mem::take(&mut self.data);
+                return Some(serde_json::from_str(&data).map_err(|err| {
+                    Error::other(format!(
+                        "received invalid event from server '{data}': {err}"
+                    ))
+                }));
+            }
+
+            let (field, value) = line.split_once(':').unwrap_or((&line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+
+            if field == "data" {
+                if !self.data.is_empty() {
+                    self.data.push('\n');
+                }
+
+                self.data.push_str(value);
+            }
+        }
+
+        None
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Result<Event>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(event) = this.next_event() {
+                return Poll::Ready(Some(event));
+            }
+
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            match this.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(bytes))) => {
+                    this.buffer.extend_from_slice(&bytes)
+                }
+                Poll::Ready(Some(Err(err))) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(Error::other(format!(
+                        "failed to read event stream: {err}"
+                    )))));
+                }
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -6,8 +6,9 @@ use crate::text;
 pub use url::Url;
 pub use uuid::Uuid;
 
-use chrono::Local;
+use chrono::{Days, Local, NaiveDate, NaiveTime};
 use std::{
+    collections::BTreeMap,
     error::Error,
     fmt::{self, Display},
     str::FromStr,
@@ -16,16 +17,43 @@ use std::{
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
 pub type DateTime = chrono::DateTime<Local>;
 
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct About {
     pub version: String,
 }
 
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ArchiveEntryError {
+    pub name: String,
+    pub message: String,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ArchiveUpload {
+    pub post_id: Uuid,
+    pub objects: Vec<ObjectPreview>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub errors: Vec<ArchiveEntryError>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct Comment {
     pub id: Uuid,
     pub user: Option<UserPreview>,
@@ -38,6 +66,7 @@ pub struct Comment {
 
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct CommentData {
     pub id: Uuid,
     pub user: Option<UserPreview>,
@@ -46,8 +75,119 @@ pub struct CommentData {
     pub created: DateTime,
 }
 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct DateRange {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub after: Option<DateTime>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub before: Option<DateTime>,
+}
+
+impl DateRange {
+    pub fn is_empty(&self) -> bool {
+        self.after.is_none() && self.before.is_none()
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseDateRangeError {
+    InvalidDate(String),
+    Unbounded,
+}
+
+impl Display for ParseDateRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDate(date) => write!(f, "invalid date '{date}'"),
+            Self::Unbounded => {
+                f.write_str("date range must have at least one bound")
+            }
+        }
+    }
+}
+
+impl Error for ParseDateRangeError {}
+
+impl FromStr for DateRange {
+    type Err = ParseDateRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn bound(date: &str) -> Result<Option<DateTime>, ParseDateRangeError> {
+            if date.is_empty() {
+                Ok(None)
+            } else {
+                parse_date(date, 0).map(Some)
+            }
+        }
+
+        let range = if let Some(date) = s.strip_prefix('>') {
+            Self {
+                after: Some(parse_date(date, 1)?),
+                before: None,
+            }
+        } else if let Some(date) = s.strip_prefix('<') {
+            Self {
+                after: None,
+                before: Some(parse_date(date, 0)?),
+            }
+        } else if let Some((after, before)) = s.split_once("..") {
+            Self {
+                after: bound(after)?,
+                before: bound(before)?,
+            }
+        } else {
+            let day = NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .map_err(|_| ParseDateRangeError::InvalidDate(s.into()))?;
+
+            Self {
+                after: Some(local_midnight(day, s)?),
+                before: Some(local_midnight(day + Days::new(1), s)?),
+            }
+        };
+
+        if range.is_empty() {
+            return Err(ParseDateRangeError::Unbounded);
+        }
+
+        Ok(range)
+    }
+}
+
+fn parse_date(date: &str, days: u64) -> Result<DateTime, ParseDateRangeError> {
+    if let Ok(date) = chrono::DateTime::parse_from_rfc3339(date) {
+        return Ok(date.with_timezone(&Local));
+    }
+
+    let day = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| ParseDateRangeError::InvalidDate(date.into()))?;
+
+    local_midnight(day + Days::new(days), date)
+}
+
+fn local_midnight(
+    day: NaiveDate,
+    input: &str,
+) -> Result<DateTime, ParseDateRangeError> {
+    day.and_time(NaiveTime::MIN)
+        .and_local_timezone(Local)
+        .earliest()
+        .ok_or_else(|| ParseDateRangeError::InvalidDate(input.into()))
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct DuplicateObject {
+    pub object: ObjectPreview,
+    pub posts: Vec<PostPreview>,
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct EntityProfile {
     pub name: String,
     pub aliases: Vec<String>,
@@ -87,15 +227,84 @@ impl EntityProfile {
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum Event {
+    CommentAdded { post: Uuid, comment: Uuid },
+    Lagged { skipped: u64 },
+    ObjectPreviewReady { object: Uuid },
+    PostCreated { post: Uuid },
+    PostDeleted { post: Uuid },
+    PostEdited { post: Uuid },
+    PostPublished { post: Uuid },
+    TagChanged { tag: Uuid },
+}
+
+impl Event {
+    pub fn name(&self) -> &'static str {
+        use Event::*;
+
+        match self {
+            CommentAdded { .. } => "comment_added",
+            Lagged { .. } => "lagged",
+            ObjectPreviewReady { .. } => "object_preview_ready",
+            PostCreated { .. } => "post_created",
+            PostDeleted { .. } => "post_deleted",
+            PostEdited { .. } => "post_edited",
+            PostPublished { .. } => "post_published",
+            TagChanged { .. } => "tag_changed",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct FrameRate {
+    pub num: u32,
+    pub den: u32,
+}
+
+impl Display for FrameRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rate = f64::from(self.num) / f64::from(self.den);
+        write!(f, "{}", (rate * 1000.0).round() / 1000.0)
+    }
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct Login {
     pub email: String,
     pub password: String,
 }
 
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct MediaMetadata {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub duration_ms: Option<u64>,
+    pub frame_rate: Option<FrameRate>,
+    pub video_codec: Option<String>,
+    pub audio_codec: Option<String>,
+    pub audio_channels: Option<u32>,
+}
+
+impl MediaMetadata {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct Modification<T> {
     pub date_modified: DateTime,
     pub new_value: T,
@@ -103,6 +312,110 @@ pub struct Modification<T> {
 
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct NewToken {
+    pub name: text::Name,
+    pub scopes: Vec<TokenScope>,
+    pub expiration: Option<DateTime>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct NumberRange {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min: Option<u64>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max: Option<u64>,
+}
+
+impl NumberRange {
+    pub fn is_empty(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+
+    pub fn scale(self, factor: u64) -> Self {
+        Self {
+            min: self.min.map(|min| min.saturating_mul(factor)),
+            max: self.max.map(|max| max.saturating_mul(factor)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseNumberRangeError {
+    InvalidNumber(String),
+    Unbounded,
+}
+
+impl Display for ParseNumberRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidNumber(number) => {
+                write!(f, "invalid number '{number}'")
+            }
+            Self::Unbounded => {
+                f.write_str("number range must have at least one bound")
+            }
+        }
+    }
+}
+
+impl Error for ParseNumberRangeError {}
+
+impl FromStr for NumberRange {
+    type Err = ParseNumberRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn number(s: &str) -> Result<u64, ParseNumberRangeError> {
+            s.parse()
+                .map_err(|_| ParseNumberRangeError::InvalidNumber(s.into()))
+        }
+
+        fn bound(s: &str) -> Result<Option<u64>, ParseNumberRangeError> {
+            if s.is_empty() {
+                Ok(None)
+            } else {
+                number(s).map(Some)
+            }
+        }
+
+        let range = if let Some(min) = s.strip_prefix('>') {
+            Self {
+                min: Some(number(min)?),
+                max: None,
+            }
+        } else if let Some(max) = s.strip_prefix('<') {
+            Self {
+                min: None,
+                max: Some(number(max)?),
+            }
+        } else if let Some((min, max)) = s.split_once("..") {
+            Self {
+                min: bound(min)?,
+                max: bound(max)?,
+            }
+        } else {
+            let number = number(s)?;
+
+            Self {
+                min: Some(number),
+                max: Some(number),
+            }
+        };
+
+        if range.is_empty() {
+            return Err(ParseNumberRangeError::Unbounded);
+        }
+
+        Ok(range)
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct Object {
     pub id: Uuid,
     pub hash: String,
@@ -112,11 +425,23 @@ pub struct Object {
     pub extension: Option<String>,
     pub added: DateTime,
     pub preview_id: Option<Uuid>,
+    pub previews: BTreeMap<String, Uuid>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: MediaMetadata,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub storyboard: Option<Storyboard>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub animation_id: Option<Uuid>,
+
     pub posts: Vec<PostPreview>,
 }
 
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct ObjectError {
     pub id: Uuid,
     pub message: String,
@@ -124,12 +449,16 @@ pub struct ObjectError {
 
 #[derive(Clone, Debug, Eq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct ObjectPreview {
     pub id: Uuid,
     pub preview_id: Option<Uuid>,
     pub r#type: String,
     pub subtype: String,
     pub extension: Option<String>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: MediaMetadata,
 }
 
 impl PartialEq for ObjectPreview {
@@ -140,13 +469,80 @@ impl PartialEq for ObjectPreview {
 
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ObjectUpload {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub object: ObjectPreview,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub preview_state: PreviewState,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub duplicates: Vec<DuplicateObject>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct ObjectSummary {
     pub media_type: String,
     pub size: u64,
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Orientation {
+    Landscape,
+    Portrait,
+    Square,
+}
+
+impl Display for Orientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use Orientation::*;
+
+        let string = match self {
+            Landscape => "landscape",
+            Portrait => "portrait",
+            Square => "square",
+        };
+
+        f.write_str(string)
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseOrientationError(String);
+
+impl Display for ParseOrientationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid orientation '{}'", self.0)
+    }
+}
+
+impl Error for ParseOrientationError {}
+
+impl FromStr for Orientation {
+    type Err = ParseOrientationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "landscape" => Ok(Self::Landscape),
+            "portrait" => Ok(Self::Portrait),
+            "square" => Ok(Self::Square),
+            _ => Err(ParseOrientationError(s.into())),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct Pagination {
     #[cfg_attr(feature = "serde", serde(default))]
     pub from: u32,
@@ -172,6 +568,7 @@ impl Default for Pagination {
 
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct Post {
     pub id: Uuid,
     pub poster: Option<UserPreview>,
@@ -188,6 +585,7 @@ pub struct Post {
 
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct PostParts {
     pub title: Option<text::PostTitle>,
     pub description: Option<text::Description>,
@@ -199,6 +597,7 @@ pub struct PostParts {
 
 #[derive(Clone, Debug, Eq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct PostPreview {
     pub id: Uuid,
     pub poster: Option<UserPreview>,
@@ -217,6 +616,7 @@ impl PartialEq for PostPreview {
 
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct PostQuery {
     #[cfg_attr(feature = "serde", serde(flatten))]
     pub pagination: Pagination,
@@ -230,15 +630,63 @@ pub struct PostQuery {
     #[cfg_attr(feature = "serde", serde(default))]
     pub tags: Vec<Uuid>,
 
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub exclude_tags: Vec<Uuid>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub any_tags: Vec<Vec<Uuid>>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub created: DateRange,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub modified: DateRange,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub media_type: Option<String>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub width: NumberRange,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub height: NumberRange,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub duration_ms: NumberRange,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub orientation: Option<Orientation>,
+
     #[cfg_attr(feature = "serde", serde(default, alias = "vis"))]
     pub visibility: Visibility,
 
     #[cfg_attr(feature = "serde", serde(default))]
     pub sort: PostSort,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub facets: u32,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cursor: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct PostSearchResult {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub posts: SearchResult<PostPreview>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub facets: Vec<TagFacet>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct PostSort {
     pub value: PostSortValue,
     pub order: SortOrder,
@@ -342,6 +790,7 @@ impl FromStr for PostSort {
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 #[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum PostSortValue {
     #[default]
@@ -362,8 +811,46 @@ impl PostSortValue {
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum PreviewState {
+    Pending,
+    Running,
+    #[default]
+    Done,
+    Failed,
+}
+
+impl Display for PreviewState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use PreviewState::*;
+
+        let string = match self {
+            Pending => "pending",
+            Running => "running",
+            Done => "done",
+            Failed => "failed",
+        };
+
+        f.write_str(string)
+    }
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct PreviewStatus {
+    pub id: Uuid,
+    pub state: PreviewState,
+    pub attempts: u32,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct ProfileName {
     pub name: String,
     pub aliases: Vec<String>,
@@ -371,6 +858,7 @@ pub struct ProfileName {
 
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct ProfileQuery {
     #[cfg_attr(feature = "serde", serde(flatten))]
     pub pagination: Pagination,
@@ -379,17 +867,39 @@ pub struct ProfileQuery {
 
     #[cfg_attr(feature = "serde", serde(default))]
     pub exclude: Vec<Uuid>,
+
+    pub category: Option<String>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cursor: Option<String>,
 }
 
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct SearchResult<T> {
     pub total: u32,
     pub hits: Vec<T>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub cursor: Option<String>,
 }
 
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct SimilarObject {
+    pub object: ObjectPreview,
+    pub distance: u32,
+    pub posts: Vec<PostPreview>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct SignUp {
     pub username: text::Name,
     pub email: text::Email,
@@ -398,6 +908,7 @@ pub struct SignUp {
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 #[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum SortOrder {
     #[cfg_attr(feature = "serde", serde(rename = "asc"))]
@@ -408,6 +919,7 @@ pub enum SortOrder {
 
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct Source {
     pub id: i64,
     pub url: Url,
@@ -420,21 +932,46 @@ impl Display for Source {
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct Storyboard {
+    pub id: Uuid,
+    pub frames: u32,
+    pub columns: u32,
+    pub rows: u32,
+    pub frame_width: u32,
+    pub frame_height: u32,
+    pub interval_ms: u64,
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct Tag {
     pub id: Uuid,
     pub profile: EntityProfile,
     pub creator: Option<UserPreview>,
+    pub category: Option<String>,
     pub post_count: u32,
 }
 
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct TagFacet {
+    pub tag: TagPreview,
+    pub count: u32,
+}
+
 #[derive(Clone, Debug, Eq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct TagPreview {
     pub id: Uuid,
     pub name: String,
     pub avatar: Option<Uuid>,
+    pub category: Option<String>,
 }
 
 impl PartialEq for TagPreview {
@@ -445,6 +982,85 @@ impl PartialEq for TagPreview {
 
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct Token {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<TokenScope>,
+    pub created: DateTime,
+    pub expiration: Option<DateTime>,
+    pub last_used: Option<DateTime>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum TokenScope {
+    Read,
+    Posts,
+    Tags,
+    Account,
+    Admin,
+}
+
+impl TokenScope {
+    pub fn as_str(self) -> &'static str {
+        use TokenScope::*;
+
+        match self {
+            Read => "read",
+            Posts => "posts",
+            Tags => "tags",
+            Account => "account",
+            Admin => "admin",
+        }
+    }
+}
+
+impl Display for TokenScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseTokenScopeError(String);
+
+impl Display for ParseTokenScopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid token scope '{}'", self.0)
+    }
+}
+
+impl Error for ParseTokenScopeError {}
+
+impl FromStr for TokenScope {
+    type Err = ParseTokenScopeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read" => Ok(Self::Read),
+            "posts" => Ok(Self::Posts),
+            "tags" => Ok(Self::Tags),
+            "account" => Ok(Self::Account),
+            "admin" => Ok(Self::Admin),
+            _ => Err(ParseTokenScopeError(s.into())),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct TokenSecret {
+    pub token: Token,
+    pub secret: String,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct User {
     pub id: Uuid,
     pub email: String,
@@ -457,6 +1073,7 @@ pub struct User {
 
 #[derive(Clone, Debug, Eq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct UserPreview {
     pub id: Uuid,
     pub name: String,
@@ -471,6 +1088,7 @@ impl PartialEq for UserPreview {
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
 #[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Visibility {
     Draft,
@@ -1,5 +1,8 @@
 use super::{DateTime, Deserialize, Serialize, Url, Uuid, Visibility};
 
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
 pub trait Profile {
     fn id(&self) -> Uuid;
 
@@ -7,13 +10,19 @@ pub trait Profile {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "openapi", schema(as = export::Data))]
 pub struct Data {
+    #[serde(default)]
+    pub categories: Vec<String>,
     pub posts: Vec<Post>,
     pub tags: Vec<Tag>,
     pub users: Vec<User>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "openapi", schema(as = export::Comment))]
 pub struct Comment {
     pub id: Uuid,
     pub user: Option<Uuid>,
@@ -24,6 +33,8 @@ pub struct Comment {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "openapi", schema(as = export::EntityProfile))]
 pub struct EntityProfile {
     pub name: String,
     pub aliases: Vec<String>,
@@ -35,6 +46,8 @@ pub struct EntityProfile {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "openapi", schema(as = export::Post))]
 pub struct Post {
     pub id: Uuid,
     pub poster: Option<Uuid>,
@@ -50,17 +63,25 @@ pub struct Post {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "openapi", schema(as = export::Source))]
 pub struct Source {
     pub url: Url,
     pub icon: Option<Uuid>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "openapi", schema(as = export::Tag))]
 pub struct Tag {
     pub id: Uuid,
     #[serde(flatten)]
     pub profile: EntityProfile,
     pub creator: Option<Uuid>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub implies: Vec<Uuid>,
 }
 
 impl Profile for Tag {
@@ -74,6 +95,8 @@ impl Profile for Tag {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[cfg_attr(feature = "openapi", schema(as = export::User))]
 pub struct User {
     pub id: Uuid,
     pub email: String,
@@ -0,0 +1,499 @@
+use crate::model::{
+    DateRange, NumberRange, Orientation, ParseDateRangeError,
+    ParseNumberRangeError, ParseOrientationError, ParsePostSortError,
+    PostQuery, PostSort, Uuid, Visibility,
+};
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    mem,
+    str::FromStr,
+};
+
+pub(crate) const KEYS: &[&str] = &[
+    "created",
+    "duration",
+    "height",
+    "media",
+    "modified",
+    "orientation",
+    "poster",
+    "sort",
+    "tag",
+    "text",
+    "vis",
+    "visibility",
+    "width",
+];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TagTerm {
+    Id(Uuid),
+    Name(String),
+    Categorized { category: String, name: String },
+}
+
+impl From<String> for TagTerm {
+    fn from(value: String) -> Self {
+        match Uuid::parse_str(&value) {
+            Ok(id) => Self::Id(id),
+            Err(_) => Self::Name(value),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PostTerms {
+    pub text: Vec<String>,
+    pub poster: Option<Uuid>,
+    pub tags: Vec<TagTerm>,
+    pub exclude_tags: Vec<TagTerm>,
+    pub any_tags: Vec<Vec<TagTerm>>,
+    pub created: Option<DateRange>,
+    pub modified: Option<DateRange>,
+    pub media_type: Option<String>,
+    pub width: Option<NumberRange>,
+    pub height: Option<NumberRange>,
+    pub duration: Option<NumberRange>,
+    pub orientation: Option<Orientation>,
+    pub visibility: Option<Visibility>,
+    pub sort: Option<PostSort>,
+}
+
+impl PostTerms {
+    pub fn parse(
+        s: &str,
+        categories: &[String],
+    ) -> Result<Self, ParsePostTermsError> {
+        let mut parser = Parser::new(s);
+        let mut terms = Self::default();
+
+        while let Some(group) = parser.group()? {
+            terms.add(group, categories)?;
+        }
+
+        Ok(terms)
+    }
+
+    pub fn tag_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .tags
+            .iter()
+            .chain(self.exclude_tags.iter())
+            .chain(self.any_tags.iter().flatten())
+            .filter_map(|tag| match tag {
+                TagTerm::Id(_) => None,
+                TagTerm::Name(name) | TagTerm::Categorized { name, .. } => {
+                    Some(name.as_str())
+                }
+            })
+            .collect();
+
+        names.sort_unstable();
+        names.dedup();
+
+        names
+    }
+
+    pub fn apply<F, E>(
+        self,
+        query: &mut PostQuery,
+        mut resolve: F,
+    ) -> Result<(), E>
+    where
+        F: FnMut(Option<&str>, &str) -> Result<Vec<Uuid>, E>,
+    {
+        let mut ids = |tag: TagTerm| match tag {
+            TagTerm::Id(id) => Ok(vec![id]),
+            TagTerm::Name(name) => resolve(None, &name),
+            TagTerm::Categorized { category, name } => {
+                resolve(Some(&category), &name)
+            }
+        };
+
+        if !self.text.is_empty() {
+            let text = self.text.join(" ");
+
+            if query.text.is_empty() {
+                query.text = text;
+            } else {
+                query.text = format!("{} {text}", query.text);
+            }
+        }
+
+        if let Some(poster) = self.poster {
+            query.poster = Some(poster);
+        }
+
+        for tag in self.tags {
+            match ids(tag)?.as_slice() {
+                [id] => query.tags.push(*id),
+                group => query.any_tags.push(group.to_vec()),
+            }
+        }
+
+        for tag in self.exclude_tags {
+            query.exclude_tags.extend(ids(tag)?);
+        }
+
+        for group in self.any_tags {
+            let mut tags = Vec::new();
+
+            for tag in group {
+                tags.extend(ids(tag)?);
+            }
+
+            query.any_tags.push(tags);
+        }
+
+        if let Some(created) = self.created {
+            query.created = created;
+        }
+
+        if let Some(modified) = self.modified {
+            query.modified = modified;
+        }
+
+        if let Some(media_type) = self.media_type {
+            query.media_type = Some(media_type);
+        }
+
+        if let Some(width) = self.width {
+            query.width = width;
+        }
+
+        if let Some(height) = self.height {
+            query.height = height;
+        }
+
+        if let Some(duration) = self.duration {
+            query.duration_ms = duration.scale(1000);
+        }
+
+        if let Some(orientation) = self.orientation {
+            query.orientation = Some(orientation);
+        }
+
+        if let Some(visibility) = self.visibility {
+            query.visibility = visibility;
+        }
+
+        if let Some(sort) = self.sort {
+            query.sort = sort;
+        }
+
+        Ok(())
+    }
+
+    fn add(
+        &mut self,
+        group: Group,
+        categories: &[String],
+    ) -> Result<(), ParsePostTermsError> {
+        let Group { negated, mut terms } = group;
+
+        if terms.len() > 1 {
+            let tags = terms
+                .into_iter()
+                .map(|term| term.into_tag(categories))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if negated {
+                self.exclude_tags.extend(tags);
+            } else {
+                self.any_tags.push(tags);
+            }
+
+            return Ok(());
+        }
+
+        let term = terms.pop().unwrap();
+
+        let key = match term.key.as_deref() {
+            Some(key) if KEYS.contains(&key) && key != "tag" => key.to_owned(),
+            _ => {
+                let tag = term.into_tag(categories)?;
+
+                if negated {
+                    self.exclude_tags.push(tag);
+                } else {
+                    self.tags.push(tag);
+                }
+
+                return Ok(());
+            }
+        };
+
+        if negated {
+            return Err(ParsePostTermsError::Negated(key));
+        }
+
+        let value = term.value;
+
+        match key.as_str() {
+            "text" => self.text.push(value),
+            "created" | "modified" => {
+                let range =
+                    value.parse().map_err(ParsePostTermsError::InvalidDate)?;
+                let field = if key == "created" {
+                    &mut self.created
+                } else {
+                    &mut self.modified
+                };
+                set_once(field, key, range)?;
+            }
+            "width" | "height" | "duration" => {
+                let range = value
+                    .parse()
+                    .map_err(ParsePostTermsError::InvalidNumber)?;
+                let field = match key.as_str() {
+                    "width" => &mut self.width,
+                    "height" => &mut self.height,
+                    _ => &mut self.duration,
+                };
+                set_once(field, key, range)?;
+            }
+            "media" => set_once(&mut self.media_type, key, value)?,
+            "orientation" => {
+                let orientation = value
+                    .parse()
+                    .map_err(ParsePostTermsError::InvalidOrientation)?;
+                set_once(&mut self.orientation, key, orientation)?;
+            }
+            "poster" => {
+                let poster = Uuid::parse_str(&value)
+                    .map_err(|_| ParsePostTermsError::InvalidPoster(value))?;
+                set_once(&mut self.poster, key, poster)?;
+            }
+            "sort" => {
+                let sort =
+                    value.parse().map_err(ParsePostTermsError::InvalidSort)?;
+                set_once(&mut self.sort, key, sort)?;
+            }
+            _ => {
+                let visibility = match value.as_str() {
+                    "draft" => Visibility::Draft,
+                    "public" => Visibility::Public,
+                    _ => {
+                        return Err(ParsePostTermsError::InvalidVisibility(
+                            value,
+                        ))
+                    }
+                };
+                set_once(&mut self.visibility, key, visibility)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for PostTerms {
+    type Err = ParsePostTermsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, &[])
+    }
+}
+
+fn set_once<T>(
+    field: &mut Option<T>,
+    key: String,
+    value: T,
+) -> Result<(), ParsePostTermsError> {
+    if field.is_some() {
+        return Err(ParsePostTermsError::Duplicate(key));
+    }
+
+    *field = Some(value);
+    Ok(())
+}
+
+#[derive(Debug)]
+pub enum ParsePostTermsError {
+    Duplicate(String),
+    EmptyTerm(usize),
+    Grouped(String),
+    InvalidDate(ParseDateRangeError),
+    InvalidKey(String),
+    InvalidNumber(ParseNumberRangeError),
+    InvalidOrientation(ParseOrientationError),
+    InvalidPoster(String),
+    InvalidSort(ParsePostSortError),
+    InvalidVisibility(String),
+    MissingValue(String),
+    Negated(String),
+    UnterminatedQuote(usize),
+}
+
+impl Display for ParsePostTermsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Duplicate(key) => {
+                write!(f, "'{key}' may only be specified once")
+            }
+            Self::EmptyTerm(position) => {
+                write!(f, "expected a search term at position {position}")
+            }
+            Self::Grouped(key) => {
+                write!(f, "'{key}' cannot be part of a tag group")
+            }
+            Self::InvalidDate(err) => err.fmt(f),
+            Self::InvalidKey(key) => write!(f, "unknown search key '{key}'"),
+            Self::InvalidNumber(err) => err.fmt(f),
+            Self::InvalidOrientation(err) => err.fmt(f),
+            Self::InvalidPoster(poster) => {
+                write!(f, "invalid poster ID '{poster}'")
+            }
+            Self::InvalidSort(err) => err.fmt(f),
+            Self::InvalidVisibility(visibility) => {
+                write!(f, "invalid visibility '{visibility}'")
+            }
+            Self::MissingValue(key) => {
+                write!(f, "missing value for '{key}'")
+            }
+            Self::Negated(key) => write!(f, "'{key}' cannot be negated"),
+            Self::UnterminatedQuote(position) => {
+                write!(f, "unterminated quote at position {position}")
+            }
+        }
+    }
+}
+
+impl Error for ParsePostTermsError {}
+
+struct Term {
+    key: Option<String>,
+    value: String,
+}
+
+impl Term {
+    fn into_tag(
+        self,
+        categories: &[String],
+    ) -> Result<TagTerm, ParsePostTermsError> {
+        match self.key {
+            None => Ok(self.value.into()),
+            Some(key) if key == "tag" => Ok(self.value.into()),
+            Some(key) if KEYS.contains(&key.as_str()) => {
+                Err(ParsePostTermsError::Grouped(key))
+            }
+            Some(category) if categories.contains(&category) => {
+                Ok(TagTerm::Categorized {
+                    category,
+                    name: self.value,
+                })
+            }
+            Some(key) => Err(ParsePostTermsError::InvalidKey(key)),
+        }
+    }
+}
+
+struct Group {
+    negated: bool,
+    terms: Vec<Term>,
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn new(s: &str) -> Self {
+        Self {
+            chars: s.chars().collect(),
+            position: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn group(&mut self) -> Result<Option<Group>, ParsePostTermsError> {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+
+        if self.peek().is_none() {
+            return Ok(None);
+        }
+
+        let negated = self.peek() == Some('-');
+        if negated {
+            self.position += 1;
+        }
+
+        let mut terms = vec![self.term()?];
+
+        while self.peek() == Some('|') {
+            self.position += 1;
+            terms.push(self.term()?);
+        }
+
+        Ok(Some(Group { negated, terms }))
+    }
+
+    fn term(&mut self) -> Result<Term, ParsePostTermsError> {
+        let start = self.position;
+        let mut key = None;
+        let mut value = String::new();
+        let mut quoted = false;
+
+        while let Some(c) = self.peek() {
+            match c {
+                c if c.is_whitespace() || c == '|' => break,
+                '"' => {
+                    self.quoted(&mut value)?;
+                    quoted = true;
+                }
+                ':' if key.is_none() && !quoted => {
+                    self.position += 1;
+                    key = Some(mem::take(&mut value));
+                }
+                c => {
+                    self.position += 1;
+                    value.push(c);
+                }
+            }
+        }
+
+        if value.is_empty() {
+            return Err(match key {
+                Some(key) => ParsePostTermsError::MissingValue(key),
+                None => ParsePostTermsError::EmptyTerm(start),
+            });
+        }
+
+        Ok(Term { key, value })
+    }
+
+    fn quoted(
+        &mut self,
+        value: &mut String,
+    ) -> Result<(), ParsePostTermsError> {
+        let start = self.position;
+        self.position += 1;
+
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(ParsePostTermsError::UnterminatedQuote(start));
+            };
+
+            self.position += 1;
+
+            match c {
+                '"' => return Ok(()),
+                '\\' => {
+                    if let Some(c) = self.peek() {
+                        self.position += 1;
+                        value.push(c);
+                    }
+                }
+                c => value.push(c),
+            }
+        }
+    }
+}
@@ -16,7 +16,7 @@ pub trait Repo: Sized {
         content: text::Comment,
     ) -> Result<CommentData>;
 
-    async fn add_object<S>(&self, stream: S) -> Result<ObjectPreview>
+    async fn add_object<S>(&self, stream: S) -> Result<ObjectUpload>
     where
         S: TryStream + Send + Sync + 'static,
         S::Error: Into<Box<dyn Error + Send + Sync>>,
@@ -44,6 +44,14 @@ pub trait Repo: Sized {
         alias: text::Name,
     ) -> Result<ProfileName>;
 
+    async fn add_tag_category(&self, name: text::Category) -> Result<()>;
+
+    async fn add_tag_implication(
+        &self,
+        tag_id: Uuid,
+        implied_tag_id: Uuid,
+    ) -> Result<()>;
+
     async fn add_tag_source(&self, tag_id: Uuid, url: &Url) -> Result<Source>;
 
     async fn add_user_alias(&self, alias: text::Name) -> Result<ProfileName>;
@@ -60,6 +68,18 @@ pub trait Repo: Sized {
 
     async fn create_post(&self, parts: &PostParts) -> Result<Uuid>;
 
+    async fn create_post_from_archive<S>(
+        &self,
+        parts: &PostParts,
+        stream: S,
+    ) -> Result<ArchiveUpload>
+    where
+        S: TryStream + Send + Sync + 'static,
+        S::Error: Into<Box<dyn Error + Send + Sync>>,
+        Bytes: From<S::Ok>;
+
+    async fn create_token(&self, token: &NewToken) -> Result<TokenSecret>;
+
     async fn delete_comment(&self, id: Uuid, recursive: bool) -> Result<()>;
 
     async fn delete_post(&self, id: Uuid) -> Result<()>;
@@ -86,6 +106,14 @@ pub trait Repo: Sized {
         alias: &str,
     ) -> Result<ProfileName>;
 
+    async fn delete_tag_category(&self, name: &str) -> Result<()>;
+
+    async fn delete_tag_implication(
+        &self,
+        tag_id: Uuid,
+        implied_tag_id: Uuid,
+    ) -> Result<()>;
+
     async fn delete_tag_source(
         &self,
         tag_id: Uuid,
@@ -126,19 +154,47 @@ pub trait Repo: Sized {
         id: Uuid,
     ) -> Result<(ObjectSummary, impl Stream<Item = io::Result<Bytes>>)>;
 
+    async fn get_object_data_from(
+        &self,
+        id: Uuid,
+        offset: u64,
+    ) -> Result<(ObjectSummary, u64, impl Stream<Item = io::Result<Bytes>>)>;
+
     async fn get_object_preview_errors(&self) -> Result<Vec<ObjectError>>;
 
+    async fn get_object_preview_status(
+        &self,
+        id: Uuid,
+    ) -> Result<PreviewStatus>;
+
+    async fn get_objects_by_hash(
+        &self,
+        hash: &str,
+    ) -> Result<Vec<DuplicateObject>>;
+
     async fn get_post(&self, id: Uuid) -> Result<Post>;
 
-    async fn get_posts(
+    async fn get_posts(&self, query: &PostQuery) -> Result<PostSearchResult>;
+
+    async fn get_similar_objects(
         &self,
-        query: &PostQuery,
-    ) -> Result<SearchResult<PostPreview>>;
+        id: Uuid,
+        distance: Option<u32>,
+    ) -> Result<Vec<SimilarObject>>;
 
     async fn get_tag(&self, id: Uuid) -> Result<Tag>;
 
+    async fn get_tag_categories(&self) -> Result<Vec<String>>;
+
+    async fn get_tag_implications(
+        &self,
+        tag_id: Uuid,
+    ) -> Result<Vec<TagPreview>>;
+
     async fn get_tags(&self, ids: &[Uuid]) -> Result<Vec<TagPreview>>;
 
+    async fn get_tokens(&self) -> Result<Vec<Token>>;
+
     async fn get_user(&self, id: Uuid) -> Result<User>;
 
     async fn get_users(
@@ -155,10 +211,20 @@ pub trait Repo: Sized {
         destination: Uuid,
     ) -> Result<DateTime>;
 
+    async fn merge_tag(&self, tag_id: Uuid, other: Uuid) -> Result<Tag>;
+
     async fn publish_post(&self, post_id: Uuid) -> Result<()>;
 
     async fn revoke_admin(&self, user_id: Uuid) -> Result<()>;
 
+    async fn revoke_token(&self, token_id: Uuid) -> Result<()>;
+
+    async fn search_posts(
+        &self,
+        terms: &str,
+        query: &PostQuery,
+    ) -> Result<PostSearchResult>;
+
     async fn search_tags(
         &self,
         query: &ProfileQuery,
@@ -182,6 +248,12 @@ pub trait Repo: Sized {
         title: text::PostTitle,
     ) -> Result<Modification<String>>;
 
+    async fn set_tag_category(
+        &self,
+        tag_id: Uuid,
+        category: Option<&str>,
+    ) -> Result<()>;
+
     async fn set_tag_description(
         &self,
         tag_id: Uuid,
@@ -212,4 +284,6 @@ pub trait Repo: Sized {
         info: &SignUp,
         invitation: Option<String>,
     ) -> Result<Uuid>;
+
+    async fn subscribe(&self) -> Result<impl Stream<Item = Result<Event>>>;
 }
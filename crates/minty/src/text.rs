@@ -1,6 +1,9 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
 use regex::Regex;
 use std::{
     error::Error as StdError,
@@ -83,6 +86,20 @@ impl Text {
         }
     }
 
+    fn valid_category(self) -> Result<Self> {
+        static REGEX: OnceLock<Regex> = OnceLock::new();
+        let re =
+            REGEX.get_or_init(|| Regex::new(r"^[a-z0-9][a-z0-9_-]*$").unwrap());
+
+        if re.is_match(&self.string)
+            && !crate::query::KEYS.contains(&self.string.as_str())
+        {
+            Ok(self)
+        } else {
+            Err(self.error(ErrorKind::Invalid))
+        }
+    }
+
     fn password_length(self) -> Result<Self> {
         const MIN: usize = 8;
 
@@ -128,6 +145,8 @@ macro_rules! text {
         #[derive(Clone, Debug)]
         #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
         #[cfg_attr(feature = "serde", serde(try_from = "String"))]
+        #[cfg_attr(feature = "openapi", derive(ToSchema))]
+        #[cfg_attr(feature = "openapi", schema(as = text::$name))]
         pub struct $name(String);
 
         impl $name {
@@ -172,6 +191,7 @@ macro_rules! text {
     };
 }
 
+text!(Category, "category", not_empty, valid_category);
 text!(Comment, "comment", not_empty);
 text!(Description, "description");
 text!(Email, "email address", valid_email);
@@ -60,6 +60,9 @@ pub enum Command {
     },
 
     /// Rebuild all search engine indices
+    ///
+    /// This has no effect when the PostgreSQL search backend is in use, as
+    /// it searches the database directly.
     Reindex {
         #[arg(short, long, default_value = "100", global = true)]
         /// Max items to upload to search engine in a single request
@@ -73,6 +76,13 @@ pub enum Command {
         command: Option<Reindex>,
     },
 
+    /// Add implied tags to existing posts
+    Imply {
+        #[arg(short, long)]
+        /// Do not display progress
+        quiet: bool,
+    },
+
     /// Grant administrator privileges to a user
     Admin {
         /// The user's ID
@@ -94,6 +94,9 @@ async fn run_command(
     match &args.command {
         Command::Admin { id } => repo.grant_admin(*id).await?,
         Command::Dump { filename } => repo.dump(filename).await?,
+        Command::Imply { quiet } => {
+            apply_tag_implications(repo, *quiet).await?
+        }
         Command::Init { overwrite } => {
             if *overwrite {
                 repo.reset().await?;
@@ -169,6 +172,46 @@ async fn run_command(
     Ok(())
 }
 
+async fn apply_tag_implications(repo: &Arc<Repo>, quiet: bool) -> Result {
+    let (task, handle) = repo.tasks().apply_tag_implications().await?;
+
+    let progress = if quiet || task.total() == 0 || !stdout().is_terminal() {
+        None
+    } else {
+        let title = "Applying tag implications".into();
+
+        match ProgressBarTask::new(title, task.clone()) {
+            Ok(progress) => Some(progress),
+            Err(err) => {
+                eprintln!("{err}");
+                None
+            }
+        }
+    };
+
+    let result = handle.await;
+
+    if let Some(progress) = progress {
+        if let Err(err) = progress.join().await {
+            eprintln!("{err}");
+        }
+    }
+
+    result??;
+
+    println!(
+        "Added implied tags to {} post{} in {}ms",
+        task.total(),
+        match task.total() {
+            1 => "",
+            _ => "s",
+        },
+        task.elapsed().num_milliseconds()
+    );
+
+    Ok(())
+}
+
 async fn regenerate_previews(
     repo: &Arc<Repo>,
     args: &RegenPreviewsAll,
@@ -14,6 +14,10 @@ struct AppState {
     repo: Arc<Repo>,
 }
 
+pub fn openapi() -> utoipa::openapi::OpenApi {
+    router::document()
+}
+
 pub async fn serve(
     config: &Http,
     repo: Arc<Repo>,
@@ -22,8 +26,10 @@ pub async fn serve(
     info!("minty version {} starting up", minty_core::VERSION);
 
     repo.prepare().await?;
+    repo.start_preview_workers();
 
-    let app = router::routes().with_state(AppState { repo });
+    let app = axum::Router::from(router::routes())
+        .with_state(AppState { repo: repo.clone() });
     let token = CancellationToken::new();
 
     let mut handles = Vec::new();
@@ -52,6 +58,7 @@ pub async fn serve(
 
     shutdown_signal().await;
     token.cancel();
+    repo.close_events();
     info!("Server shutting down");
 
     for handle in handles {
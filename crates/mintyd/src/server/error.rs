@@ -1,6 +1,9 @@
 use axum::{
     body::Body,
-    http::{header::WWW_AUTHENTICATE, StatusCode},
+    http::{
+        header::{CONTENT_RANGE, WWW_AUTHENTICATE},
+        StatusCode,
+    },
     response::{IntoResponse, Response},
 };
 use log::error;
@@ -53,6 +56,14 @@ impl IntoResponse for Error {
                 return (StatusCode::FORBIDDEN, error.to_string())
                     .into_response()
             }
+            RangeNotSatisfiable { size } => {
+                return (
+                    StatusCode::RANGE_NOT_SATISFIABLE,
+                    [(CONTENT_RANGE, format!("bytes */{size}"))],
+                    error.to_string(),
+                )
+                    .into_response()
+            }
             _ => error!("{error}"),
         }
 
@@ -1,11 +1,14 @@
+mod categories;
 mod comment;
 mod comments;
+mod events;
 mod invitation;
 mod login;
 mod object;
 mod objects;
 mod post;
 mod posts;
+mod scope;
 mod session;
 mod sign_up;
 mod tag;
@@ -15,16 +18,31 @@ mod timestamp;
 mod user;
 mod users;
 
-use session::OptionalUser;
+use session::{OptionalUser, SessionSecurity};
 
 use super::{error::Result, AppState};
 
-use axum::{extract::State, routing::get, Json};
+use axum::{extract::State, Json};
 use minty::model::export::Data;
 use minty_core::About;
+use utoipa::{
+    openapi::path::{Operation, PathItem},
+    Modify, OpenApi, ToSchema,
+};
+use utoipa_axum::{router::OpenApiRouter, routes};
 
-pub type Router = axum::Router<AppState>;
+pub type Router = OpenApiRouter<AppState>;
 
+#[allow(dead_code)]
+#[derive(ToSchema)]
+#[schema(value_type = String, format = Binary)]
+pub struct Binary(Vec<u8>);
+
+#[derive(OpenApi)]
+#[openapi(info(title = "minty"))]
+struct Api;
+
+#[utoipa::path(get, path = "/", responses((status = OK, body = minty::About)))]
 async fn about(
     State(AppState { repo }): State<AppState>,
     OptionalUser(user): OptionalUser,
@@ -32,27 +50,70 @@ async fn about(
     Ok(Json(repo.optional_user(user)?.about()))
 }
 
+#[utoipa::path(get, path = "/export", responses((status = OK, body = Data)))]
 async fn export(
     State(AppState { repo }): State<AppState>,
 ) -> Result<Json<Data>> {
     Ok(Json(repo.export().await?))
 }
 
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    responses((status = OK, description = "This OpenAPI document")),
+)]
+async fn openapi() -> Json<utoipa::openapi::OpenApi> {
+    Json(document())
+}
+
+pub fn document() -> utoipa::openapi::OpenApi {
+    let mut openapi = routes().into_openapi();
+    SessionSecurity.modify(&mut openapi);
+    openapi
+}
+
+fn operations(item: &mut PathItem) -> impl Iterator<Item = &mut Operation> {
+    [
+        &mut item.get,
+        &mut item.put,
+        &mut item.post,
+        &mut item.delete,
+        &mut item.patch,
+    ]
+    .into_iter()
+    .flatten()
+}
+
 pub fn routes() -> Router {
-    Router::new()
-        .route("/", get(about))
-        .route("/export", get(export))
-        .nest("/comment", comment::routes())
-        .nest("/comments", comments::routes())
-        .nest("/invitation", invitation::routes())
-        .nest("/login", login::routes())
-        .nest("/object", object::routes())
-        .nest("/objects", objects::routes())
-        .nest("/post", post::routes())
-        .nest("/posts", posts::routes())
-        .nest("/signup", sign_up::routes())
-        .nest("/tag", tag::routes())
-        .nest("/tags", tags::routes())
-        .nest("/user", user::routes())
-        .nest("/users", users::routes())
+    Router::with_openapi(Api::openapi())
+        .routes(routes!(about))
+        .routes(routes!(export))
+        .routes(routes!(openapi))
+        .nest("/categories", tagged(categories::routes(), "categories"))
+        .nest("/comment", tagged(comment::routes(), "comment"))
+        .nest("/comments", tagged(comments::routes(), "comments"))
+        .nest("/events", tagged(events::routes(), "events"))
+        .nest("/invitation", tagged(invitation::routes(), "invitation"))
+        .nest("/login", tagged(login::routes(), "login"))
+        .nest("/object", tagged(object::routes(), "object"))
+        .nest("/objects", tagged(objects::routes(), "objects"))
+        .nest("/post", tagged(post::routes(), "post"))
+        .nest("/posts", tagged(posts::routes(), "posts"))
+        .nest("/signup", tagged(sign_up::routes(), "sign_up"))
+        .nest("/tag", tagged(tag::routes(), "tag"))
+        .nest("/tags", tagged(tags::routes(), "tags"))
+        .nest("/user", tagged(user::routes(), "user"))
+        .nest("/users", tagged(users::routes(), "users"))
+}
+
+fn tagged(router: Router, tag: &str) -> Router {
+    let (router, mut openapi) = router.split_for_parts();
+
+    for item in openapi.paths.paths.values_mut() {
+        for operation in operations(item) {
+            operation.tags.get_or_insert_with(Vec::new).push(tag.into());
+        }
+    }
+
+    Router::with_openapi(openapi).merge(router.into())
 }
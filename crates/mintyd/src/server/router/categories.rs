@@ -0,0 +1,78 @@
+use super::{
+    session::{Admin, OptionalUser},
+    AppState, Result, Router,
+};
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use minty::text;
+use utoipa_axum::routes;
+
+#[utoipa::path(
+    put,
+    path = "/{category}",
+    params(("category" = String, Path)),
+    responses(
+        (status = CREATED),
+        (status = NO_CONTENT),
+    ),
+    security(("session" = [])),
+)]
+async fn add_category(
+    State(AppState { repo }): State<AppState>,
+    Admin(admin): Admin,
+    Path(category): Path<text::Category>,
+) -> Result<StatusCode> {
+    let status = if repo.admin(admin)?.categories().add(category).await? {
+        StatusCode::CREATED
+    } else {
+        StatusCode::NO_CONTENT
+    };
+
+    Ok(status)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{category}",
+    params(("category" = String, Path)),
+    responses(
+        (status = NO_CONTENT),
+        (status = NOT_FOUND),
+    ),
+    security(("session" = [])),
+)]
+async fn delete_category(
+    State(AppState { repo }): State<AppState>,
+    Admin(admin): Admin,
+    Path(category): Path<String>,
+) -> Result<StatusCode> {
+    let status = if repo.admin(admin)?.categories().delete(&category).await? {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    };
+
+    Ok(status)
+}
+
+#[utoipa::path(
+    get,
+    path = "",
+    responses((status = OK, body = Vec<String>)),
+)]
+async fn get_categories(
+    State(AppState { repo }): State<AppState>,
+    OptionalUser(user): OptionalUser,
+) -> Result<Json<Vec<String>>> {
+    Ok(Json(repo.optional_user(user)?.tags().categories().await?))
+}
+
+pub fn routes() -> Router {
+    Router::new()
+        .routes(routes!(get_categories))
+        .routes(routes!(add_category, delete_category))
+}
@@ -1,4 +1,5 @@
 use super::{
+    scope,
     session::{OptionalUser, User},
     text::Text,
     AppState, Result, Router,
@@ -7,25 +8,40 @@ use super::{
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    routing::get,
     Json,
 };
 use minty::{http::query::DeleteComment, text, Comment, CommentData, Uuid};
+use utoipa_axum::routes;
 
+#[utoipa::path(
+    post,
+    path = "/{id}",
+    params(("id" = Uuid, Path)),
+    request_body(content = String, content_type = "text/plain"),
+    responses((status = OK, body = CommentData)),
+    security(("session" = [])),
+)]
 async fn add_reply(
     State(AppState { repo }): State<AppState>,
     Path(id): Path<Uuid>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
     Text(content): Text<text::Comment>,
 ) -> Result<Json<CommentData>> {
     Ok(Json(repo.with_user(user).comment(id).reply(content).await?))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    params(("id" = Uuid, Path), DeleteComment),
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
 async fn delete_comment(
     State(AppState { repo }): State<AppState>,
     Path(id): Path<Uuid>,
     Query(DeleteComment { recursive }): Query<DeleteComment>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
 ) -> Result<StatusCode> {
     let recursive = recursive.unwrap_or(false);
     repo.with_user(user).comment(id).delete(recursive).await?;
@@ -33,6 +49,12 @@ async fn delete_comment(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    params(("id" = Uuid, Path)),
+    responses((status = OK, body = Comment)),
+)]
 async fn get_comment(
     State(AppState { repo }): State<AppState>,
     Path(id): Path<Uuid>,
@@ -41,10 +63,18 @@ async fn get_comment(
     Ok(Json(repo.optional_user(user)?.comment(id).get().await?))
 }
 
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    params(("id" = Uuid, Path)),
+    request_body(content = String, content_type = "text/plain"),
+    responses((status = OK, body = String)),
+    security(("session" = [])),
+)]
 async fn set_comment_content(
     State(AppState { repo }): State<AppState>,
     Path(id): Path<Uuid>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
     Text(content): Text<text::Comment>,
 ) -> Result<String> {
     Ok(repo
@@ -55,11 +85,10 @@ async fn set_comment_content(
 }
 
 pub fn routes() -> Router {
-    Router::new().route(
-        "/:id",
-        get(get_comment)
-            .post(add_reply)
-            .put(set_comment_content)
-            .delete(delete_comment),
-    )
+    Router::new().routes(routes!(
+        get_comment,
+        add_reply,
+        set_comment_content,
+        delete_comment
+    ))
 }
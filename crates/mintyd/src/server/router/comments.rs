@@ -1,4 +1,5 @@
 use super::{
+    scope,
     session::{OptionalUser, User},
     text::Text,
     AppState, Result, Router,
@@ -6,14 +7,22 @@ use super::{
 
 use axum::{
     extract::{Path, State},
-    routing::get,
     Json,
 };
 use minty::{text, CommentData, Uuid};
+use utoipa_axum::routes;
 
+#[utoipa::path(
+    post,
+    path = "/{post}",
+    params(("post" = Uuid, Path)),
+    request_body(content = String, content_type = "text/plain"),
+    responses((status = OK, body = CommentData)),
+    security(("session" = [])),
+)]
 async fn add_comment(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
     Path(post): Path<Uuid>,
     Text(content): Text<text::Comment>,
 ) -> Result<Json<CommentData>> {
@@ -26,6 +35,12 @@ async fn add_comment(
     ))
 }
 
+#[utoipa::path(
+    get,
+    path = "/{post}",
+    params(("post" = Uuid, Path)),
+    responses((status = OK, body = Vec<CommentData>)),
+)]
 async fn get_comments(
     State(AppState { repo }): State<AppState>,
     Path(post): Path<Uuid>,
@@ -41,5 +56,5 @@ async fn get_comments(
 }
 
 pub fn routes() -> Router {
-    Router::new().route("/:post", get(get_comments).post(add_comment))
+    Router::new().routes(routes!(get_comments, add_comment))
 }
@@ -0,0 +1,30 @@
+use super::{session::OptionalUser, AppState, Result, Router};
+
+use axum::{
+    extract::State,
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures::{Stream, StreamExt};
+use std::result;
+use utoipa_axum::routes;
+
+#[utoipa::path(
+    get,
+    path = "",
+    responses((status = OK, body = minty::Event, content_type = "text/event-stream")),
+)]
+async fn subscribe(
+    State(AppState { repo }): State<AppState>,
+    OptionalUser(user): OptionalUser,
+) -> Result<Sse<impl Stream<Item = result::Result<Event, axum::Error>>>> {
+    let events = repo
+        .optional_user(user)?
+        .events()
+        .map(|event| Event::default().event(event.name()).json_data(event));
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+pub fn routes() -> Router {
+    Router::new().routes(routes!(subscribe))
+}
@@ -1,18 +1,30 @@
-use super::{session::User, AppState, Result, Router};
+use super::{scope, session::User, AppState, Result, Router};
 
 use axum::{
     extract::{Path, State},
-    routing::get,
     Json,
 };
+use utoipa_axum::routes;
 
+#[utoipa::path(
+    get,
+    path = "",
+    responses((status = OK, description = "Invitation token", body = String)),
+    security(("session" = [])),
+)]
 async fn generate_invitation(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Read>,
 ) -> Result<String> {
     Ok(repo.with_user(user).invite()?)
 }
 
+#[utoipa::path(
+    get,
+    path = "/{token}",
+    params(("token" = String, Path)),
+    responses((status = OK, body = minty::User)),
+)]
 async fn get_inviter(
     State(AppState { repo }): State<AppState>,
     Path(token): Path<String>,
@@ -22,6 +34,6 @@ async fn get_inviter(
 
 pub fn routes() -> Router {
     Router::new()
-        .route("/", get(generate_invitation))
-        .route("/:token", get(get_inviter))
+        .routes(routes!(generate_invitation))
+        .routes(routes!(get_inviter))
 }
@@ -3,10 +3,20 @@ use super::{
     AppState, Result, Router,
 };
 
-use axum::{extract::State, routing::post, Form};
+use axum::{extract::State, Form};
 use axum_extra::extract::cookie::CookieJar;
 use minty::Login;
+use utoipa_axum::routes;
 
+#[utoipa::path(
+    post,
+    path = "",
+    request_body(
+        content = Login,
+        content_type = "application/x-www-form-urlencoded"
+    ),
+    responses((status = OK, description = "ID of the authenticated user", body = String)),
+)]
 async fn login(
     State(AppState { repo }): State<AppState>,
     jar: CookieJar,
@@ -22,5 +32,5 @@ async fn login(
 }
 
 pub fn routes() -> Router {
-    Router::new().route("/", post(login))
+    Router::new().routes(routes!(login))
 }
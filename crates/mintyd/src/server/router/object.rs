@@ -1,25 +1,43 @@
 use super::{
+    scope,
     session::{OptionalUser, User},
-    AppState, Result, Router,
+    AppState, Binary, Result, Router,
 };
 
 use axum::{
     extract::{Path, Request, State},
-    http::header::{CONTENT_LENGTH, CONTENT_TYPE},
+    http::{
+        header::{
+            ACCEPT_RANGES, CACHE_CONTROL, CONTENT_LENGTH, CONTENT_RANGE,
+            CONTENT_TYPE, ETAG, IF_NONE_MATCH, IF_RANGE, LAST_MODIFIED, RANGE,
+        },
+        HeaderMap, HeaderValue, StatusCode,
+    },
     response::{IntoResponse, Response},
-    routing::{get, post},
     Json,
 };
 use axum_extra::body::AsyncReadBody;
-use minty::{Object, ObjectPreview, ObjectSummary, Uuid};
+use chrono::Utc;
+use minty::{DateTime, Object, ObjectSummary, ObjectUpload, Uuid};
+use minty_core::{ByteRange, ObjectInfo};
 use sync_wrapper::SyncStream;
 use tokio_util::io::StreamReader;
+use utoipa_axum::routes;
 
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+
+#[utoipa::path(
+    post,
+    path = "",
+    request_body(content = inline(Binary), content_type = "application/octet-stream"),
+    responses((status = OK, body = ObjectUpload)),
+    security(("session" = [])),
+)]
 async fn add_object(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
     request: Request,
-) -> Result<Json<ObjectPreview>> {
+) -> Result<Json<ObjectUpload>> {
     let stream = request.into_body().into_data_stream();
     let objects = repo.with_user(user).objects();
 
@@ -28,6 +46,12 @@ async fn add_object(
     Ok(Json(object))
 }
 
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    params(("id" = Uuid, Path)),
+    responses((status = OK, body = Object)),
+)]
 async fn get_object(
     State(AppState { repo }): State<AppState>,
     OptionalUser(user): OptionalUser,
@@ -36,28 +60,129 @@ async fn get_object(
     Ok(Json(repo.optional_user(user)?.object(id).get().await?))
 }
 
+fn http_date(date: DateTime) -> String {
+    date.with_timezone(&Utc)
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string()
+}
+
+fn etag_matches(value: &HeaderValue, etag: &str) -> bool {
+    value.to_str().is_ok_and(|value| {
+        value.split(',').map(str::trim).any(|tag| {
+            tag == "*" || tag.strip_prefix("W/").unwrap_or(tag) == etag
+        })
+    })
+}
+
+fn parse_range(value: &str) -> Option<ByteRange> {
+    let spec = value.trim().strip_prefix("bytes=")?;
+
+    // Multiple ranges are not supported; the full body is sent instead.
+    if spec.contains(',') {
+        return None;
+    }
+
+    let (start, end) = spec.split_once('-')?;
+
+    match (start.trim(), end.trim()) {
+        ("", "") => None,
+        ("", length) => Some(ByteRange::Suffix(length.parse().ok()?)),
+        (start, "") => Some(ByteRange::From(start.parse().ok()?)),
+        (start, end) => {
+            let start = start.parse().ok()?;
+            let end = end.parse().ok()?;
+            (start <= end).then_some(ByteRange::Inclusive(start, end))
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}/{name}",
+    params(("id" = Uuid, Path), ("name" = String, Path)),
+    responses(
+        (status = OK, body = inline(Binary), content_type = "application/octet-stream"),
+        (status = PARTIAL_CONTENT, body = inline(Binary), content_type = "application/octet-stream"),
+        (status = NOT_MODIFIED),
+        (status = RANGE_NOT_SATISFIABLE),
+    ),
+)]
 async fn get_object_data(
     State(AppState { repo }): State<AppState>,
     OptionalUser(user): OptionalUser,
     Path((id, _name)): Path<(Uuid, String)>,
+    headers: HeaderMap,
 ) -> Result<Response> {
-    let (ObjectSummary { media_type, size }, stream) =
-        repo.optional_user(user)?.object(id).get_data().await?;
+    let object = repo.optional_user(user)?.object(id);
+
+    let etag = format!(r#""{}""#, object.hash().await?);
+
+    let not_modified = headers
+        .get(IF_NONE_MATCH)
+        .is_some_and(|value| etag_matches(value, &etag));
+
+    let range = headers
+        .get(RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_range)
+        .filter(|_| {
+            headers
+                .get(IF_RANGE)
+                .is_none_or(|value| value == etag.as_str())
+        });
+
+    let validators = [
+        (ACCEPT_RANGES, "bytes".to_owned()),
+        (CACHE_CONTROL, IMMUTABLE.to_owned()),
+        (ETAG, etag),
+    ];
+
+    if not_modified {
+        return Ok((StatusCode::NOT_MODIFIED, validators).into_response());
+    }
+
+    let ObjectInfo { added, .. } = object.info().await?;
+    let validators = (validators, [(LAST_MODIFIED, http_date(added))]);
+
+    let Some(range) = range else {
+        let (ObjectSummary { media_type, size }, stream) =
+            object.get_data().await?;
+
+        let headers = [
+            (CONTENT_LENGTH, size.to_string()),
+            (CONTENT_TYPE, media_type),
+        ];
+
+        let reader = StreamReader::new(stream);
+        let body = AsyncReadBody::new(reader);
+
+        return Ok((validators, headers, body).into_response());
+    };
+
+    let (ObjectSummary { media_type, size }, range, stream) =
+        object.get_data_range(range).await?;
 
     let headers = [
-        (CONTENT_LENGTH, size.to_string()),
+        (CONTENT_LENGTH, (range.end - range.start).to_string()),
+        (
+            CONTENT_RANGE,
+            format!("bytes {}-{}/{size}", range.start, range.end - 1),
+        ),
         (CONTENT_TYPE, media_type),
     ];
 
     let reader = StreamReader::new(stream);
     let body = AsyncReadBody::new(reader);
 
-    Ok((headers, body).into_response())
+    Ok(
+        (StatusCode::PARTIAL_CONTENT, validators, headers, body)
+            .into_response(),
+    )
 }
 
 pub fn routes() -> Router {
     Router::new()
-        .route("/", post(add_object))
-        .route("/:id", get(get_object))
-        .route("/:id/:name", get(get_object_data))
+        .routes(routes!(add_object))
+        .routes(routes!(get_object))
+        .routes(routes!(get_object_data))
 }
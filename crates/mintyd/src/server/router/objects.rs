@@ -1,17 +1,95 @@
-use super::{session::User, AppState, Result, Router};
+use super::{
+    session::{Admin, OptionalUser},
+    AppState, Result, Router,
+};
 
-use axum::{extract::State, routing::get, Json};
-use minty::ObjectError;
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use minty::{
+    http::query::{ObjectHash, SimilarObjects},
+    DuplicateObject, ObjectError, PreviewStatus, SimilarObject, Uuid,
+};
+use utoipa_axum::routes;
 
+#[utoipa::path(
+    get,
+    path = "",
+    params(ObjectHash),
+    responses((status = OK, body = Vec<DuplicateObject>)),
+)]
+async fn get_objects_by_hash(
+    State(AppState { repo }): State<AppState>,
+    OptionalUser(user): OptionalUser,
+    Query(ObjectHash { hash }): Query<ObjectHash>,
+) -> Result<Json<Vec<DuplicateObject>>> {
+    Ok(Json(
+        repo.optional_user(user)?
+            .objects()
+            .find_by_hash(&hash)
+            .await?,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/errors",
+    responses((status = OK, body = Vec<ObjectError>)),
+    security(("session" = [])),
+)]
 async fn get_preview_errors(
     State(AppState { repo }): State<AppState>,
-    User(admin): User,
+    Admin(admin): Admin,
 ) -> Result<Json<Vec<ObjectError>>> {
     Ok(Json(
         repo.admin(admin)?.objects().get_preview_errors().await?,
     ))
 }
 
+#[utoipa::path(
+    get,
+    path = "/{id}/preview",
+    params(("id" = Uuid, Path)),
+    responses((status = OK, body = PreviewStatus)),
+)]
+async fn get_preview_status(
+    State(AppState { repo }): State<AppState>,
+    OptionalUser(user): OptionalUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PreviewStatus>> {
+    Ok(Json(
+        repo.optional_user(user)?
+            .object(id)
+            .preview_status()
+            .await?,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}/similar",
+    params(("id" = Uuid, Path), SimilarObjects),
+    responses((status = OK, body = Vec<SimilarObject>)),
+)]
+async fn get_similar_objects(
+    State(AppState { repo }): State<AppState>,
+    OptionalUser(user): OptionalUser,
+    Path(id): Path<Uuid>,
+    Query(SimilarObjects { distance }): Query<SimilarObjects>,
+) -> Result<Json<Vec<SimilarObject>>> {
+    Ok(Json(
+        repo.optional_user(user)?
+            .object(id)
+            .similar(distance)
+            .await?,
+    ))
+}
+
 pub fn routes() -> Router {
-    Router::new().route("/errors", get(get_preview_errors))
+    Router::new()
+        .routes(routes!(get_objects_by_hash))
+        .routes(routes!(get_preview_errors))
+        .routes(routes!(get_preview_status))
+        .routes(routes!(get_similar_objects))
 }
@@ -1,22 +1,35 @@
 use super::{
+    scope,
     session::{OptionalUser, User},
     text::Text,
     timestamp::Timestamp,
-    AppState, Result, Router,
+    AppState, Binary, Result, Router,
 };
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, Request, State},
     http::StatusCode,
-    routing::{get, post, put},
     Json,
 };
-use minty::{text, Modification, Post, PostParts, Uuid};
+use minty::{
+    http::query::PostArchive, text, ArchiveUpload, Modification, Post,
+    PostParts, Uuid,
+};
+use sync_wrapper::SyncStream;
+use utoipa_axum::routes;
 
+#[utoipa::path(
+    post,
+    path = "/{id}/objects/{destination}",
+    params(("id" = Uuid, Path), ("destination" = Uuid, Path)),
+    request_body = Vec<Uuid>,
+    responses((status = OK, description = "RFC 3339 modification timestamp", body = String)),
+    security(("session" = [])),
+)]
 async fn add_objects(
     State(AppState { repo }): State<AppState>,
     Path((id, destination)): Path<(Uuid, Uuid)>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
     Json(objects): Json<Vec<Uuid>>,
 ) -> Result<Timestamp> {
     Ok(repo
@@ -29,10 +42,18 @@ async fn add_objects(
         .into())
 }
 
+#[utoipa::path(
+    post,
+    path = "/{id}/objects",
+    params(("id" = Uuid, Path)),
+    request_body = Vec<Uuid>,
+    responses((status = OK, description = "RFC 3339 modification timestamp", body = String)),
+    security(("session" = [])),
+)]
 async fn append_objects(
     State(AppState { repo }): State<AppState>,
     Path(id): Path<Uuid>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
     Json(objects): Json<Vec<Uuid>>,
 ) -> Result<Timestamp> {
     Ok(repo
@@ -45,10 +66,17 @@ async fn append_objects(
         .into())
 }
 
+#[utoipa::path(
+    put,
+    path = "/{id}/related/{related}",
+    params(("id" = Uuid, Path), ("related" = Uuid, Path)),
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
 async fn add_related_post(
     State(AppState { repo }): State<AppState>,
     Path((id, related)): Path<(Uuid, Uuid)>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
 ) -> Result<StatusCode> {
     repo.with_user(user)
         .post(id)
@@ -59,10 +87,18 @@ async fn add_related_post(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    put,
+    operation_id = "add_post_tag",
+    path = "/{id}/tag/{tag}",
+    params(("id" = Uuid, Path), ("tag" = Uuid, Path)),
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
 async fn add_tag(
     State(AppState { repo }): State<AppState>,
     Path((id, tag)): Path<(Uuid, Uuid)>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
 ) -> Result<StatusCode> {
     repo.with_user(user)
         .post(id)
@@ -73,9 +109,16 @@ async fn add_tag(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    post,
+    path = "",
+    request_body = PostParts,
+    responses((status = OK, description = "ID of the new post", body = String)),
+    security(("session" = [])),
+)]
 async fn create_post(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
     Json(parts): Json<PostParts>,
 ) -> Result<String> {
     Ok(repo
@@ -87,10 +130,42 @@ async fn create_post(
         .to_string())
 }
 
+#[utoipa::path(
+    post,
+    path = "/archive",
+    params(PostArchive),
+    request_body(content = inline(Binary), content_type = "application/octet-stream"),
+    responses((status = OK, body = ArchiveUpload)),
+    security(("session" = [])),
+)]
+async fn create_post_from_archive(
+    State(AppState { repo }): State<AppState>,
+    User(user, _): User<scope::Posts>,
+    Query(query): Query<PostArchive>,
+    request: Request,
+) -> Result<Json<ArchiveUpload>> {
+    let stream = request.into_body().into_data_stream();
+
+    Ok(Json(
+        repo.with_user(user)
+            .posts()
+            .add_archive(query.into(), SyncStream::new(stream))
+            .await?,
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}/objects",
+    params(("id" = Uuid, Path)),
+    request_body = Vec<Uuid>,
+    responses((status = OK, description = "RFC 3339 modification timestamp", body = String)),
+    security(("session" = [])),
+)]
 async fn delete_objects(
     State(AppState { repo }): State<AppState>,
     Path(id): Path<Uuid>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
     Json(objects): Json<Vec<Uuid>>,
 ) -> Result<Timestamp> {
     Ok(repo
@@ -103,10 +178,17 @@ async fn delete_objects(
         .into())
 }
 
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    params(("id" = Uuid, Path)),
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
 async fn delete_post(
     State(AppState { repo }): State<AppState>,
     Path(id): Path<Uuid>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
 ) -> Result<StatusCode> {
     repo.with_user(user)
         .post(id)
@@ -117,10 +199,17 @@ async fn delete_post(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    delete,
+    path = "/{id}/related/{related}",
+    params(("id" = Uuid, Path), ("related" = Uuid, Path)),
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
 async fn delete_related_post(
     State(AppState { repo }): State<AppState>,
     Path((id, related)): Path<(Uuid, Uuid)>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
 ) -> Result<StatusCode> {
     repo.with_user(user)
         .post(id)
@@ -131,10 +220,21 @@ async fn delete_related_post(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    delete,
+    operation_id = "delete_post_tag",
+    path = "/{id}/tag/{tag}",
+    params(("id" = Uuid, Path), ("tag" = Uuid, Path)),
+    responses(
+        (status = NO_CONTENT),
+        (status = NOT_FOUND),
+    ),
+    security(("session" = [])),
+)]
 async fn delete_tag(
     State(AppState { repo }): State<AppState>,
     Path((id, tag)): Path<(Uuid, Uuid)>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
 ) -> Result<StatusCode> {
     let status = if repo
         .with_user(user)
@@ -152,6 +252,12 @@ async fn delete_tag(
     Ok(status)
 }
 
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    params(("id" = Uuid, Path)),
+    responses((status = OK, body = Post)),
+)]
 async fn get_post(
     State(AppState { repo }): State<AppState>,
     Path(id): Path<Uuid>,
@@ -160,10 +266,17 @@ async fn get_post(
     Ok(Json(repo.optional_user(user)?.post(id).await?.get().await?))
 }
 
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    params(("id" = Uuid, Path)),
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
 async fn publish_post(
     State(AppState { repo }): State<AppState>,
     Path(id): Path<Uuid>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
 ) -> Result<StatusCode> {
     repo.with_user(user)
         .post(id)
@@ -174,10 +287,19 @@ async fn publish_post(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    put,
+    operation_id = "set_post_description",
+    path = "/{id}/description",
+    params(("id" = Uuid, Path)),
+    request_body(content = String, content_type = "text/plain"),
+    responses((status = OK, body = Modification<String>)),
+    security(("session" = [])),
+)]
 async fn set_description(
     State(AppState { repo }): State<AppState>,
     Path(id): Path<Uuid>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
     Text(description): Text<text::Description>,
 ) -> Result<Json<Modification<String>>> {
     Ok(Json(
@@ -190,10 +312,19 @@ async fn set_description(
     ))
 }
 
+#[utoipa::path(
+    put,
+    operation_id = "set_post_title",
+    path = "/{id}/title",
+    params(("id" = Uuid, Path)),
+    request_body(content = String, content_type = "text/plain"),
+    responses((status = OK, body = Modification<String>)),
+    security(("session" = [])),
+)]
 async fn set_title(
     State(AppState { repo }): State<AppState>,
     Path(id): Path<Uuid>,
-    User(user): User,
+    User(user, _): User<scope::Posts>,
     Text(title): Text<text::PostTitle>,
 ) -> Result<Json<Modification<String>>> {
     Ok(Json(
@@ -208,15 +339,13 @@ async fn set_title(
 
 pub fn routes() -> Router {
     Router::new()
-        .route("/", post(create_post))
-        .route("/:id", get(get_post).put(publish_post).delete(delete_post))
-        .route("/:id/description", put(set_description))
-        .route("/:id/objects", post(append_objects).delete(delete_objects))
-        .route("/:id/objects/:destination", post(add_objects))
-        .route(
-            "/:id/related/:related",
-            put(add_related_post).delete(delete_related_post),
-        )
-        .route("/:id/tag/:tag", put(add_tag).delete(delete_tag))
-        .route("/:id/title", put(set_title))
+        .routes(routes!(create_post))
+        .routes(routes!(create_post_from_archive))
+        .routes(routes!(get_post, publish_post, delete_post))
+        .routes(routes!(set_description))
+        .routes(routes!(append_objects, delete_objects))
+        .routes(routes!(add_objects))
+        .routes(routes!(add_related_post, delete_related_post))
+        .routes(routes!(add_tag, delete_tag))
+        .routes(routes!(set_title))
 }
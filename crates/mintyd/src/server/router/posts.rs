@@ -2,21 +2,34 @@ use super::{session::OptionalUser, AppState, Result, Router};
 
 use axum::{
     extract::{Query, State},
-    routing::get,
     Json,
 };
-use minty::{http::query::PostQuery, PostPreview, SearchResult};
+use minty::{http::query::PostQuery, PostSearchResult};
+use utoipa_axum::routes;
 
+#[utoipa::path(
+    get,
+    path = "",
+    params(PostQuery),
+    responses((status = OK, body = PostSearchResult)),
+)]
 async fn get_posts(
     State(AppState { repo }): State<AppState>,
     OptionalUser(user): OptionalUser,
-    Query(query): Query<PostQuery>,
-) -> Result<Json<SearchResult<PostPreview>>> {
-    Ok(Json(
-        repo.optional_user(user)?.posts().find(query.into()).await?,
-    ))
+    Query(mut query): Query<PostQuery>,
+) -> Result<Json<PostSearchResult>> {
+    let posts = repo.optional_user(user)?.posts();
+    let terms = query.search.take();
+    let query = query.into();
+
+    let result = match terms {
+        Some(terms) => posts.search(&terms, query).await?,
+        None => posts.find(query).await?,
+    };
+
+    Ok(Json(result))
 }
 
 pub fn routes() -> Router {
-    Router::new().route("/", get(get_posts))
+    Router::new().routes(routes!(get_posts))
 }
@@ -0,0 +1,35 @@
+use minty::TokenScope;
+
+pub trait Scope: Send + Sync + 'static {
+    const SCOPE: TokenScope;
+}
+
+pub struct Account;
+
+impl Scope for Account {
+    const SCOPE: TokenScope = TokenScope::Account;
+}
+
+pub struct Admin;
+
+impl Scope for Admin {
+    const SCOPE: TokenScope = TokenScope::Admin;
+}
+
+pub struct Posts;
+
+impl Scope for Posts {
+    const SCOPE: TokenScope = TokenScope::Posts;
+}
+
+pub struct Read;
+
+impl Scope for Read {
+    const SCOPE: TokenScope = TokenScope::Read;
+}
+
+pub struct Tags;
+
+impl Scope for Tags {
+    const SCOPE: TokenScope = TokenScope::Tags;
+}
@@ -1,18 +1,37 @@
+use super::{operations, scope::Scope};
+
 use crate::server::{error::Error, AppState};
 
 use axum::{
     async_trait,
     extract::FromRequestParts,
-    http::{request::Parts, StatusCode},
+    http::{header::AUTHORIZATION, request::Parts, StatusCode},
     response::{IntoResponse, Response},
 };
 use axum_extra::extract::cookie::{Cookie, CookieJar};
 use cookie::time::Duration;
-use minty_core::{Cached, Error::Unauthenticated, SessionId, SessionInfo};
-use std::sync::Arc;
+use minty::TokenScope;
+use minty_core::{
+    AccessToken, Cached,
+    Error::{Unauthenticated, Unauthorized},
+    SessionId, SessionInfo,
+};
+use std::{marker::PhantomData, sync::Arc};
+use utoipa::{
+    openapi::{
+        security::{
+            ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder,
+            SecurityRequirement, SecurityScheme,
+        },
+        OpenApi,
+    },
+    Modify,
+};
 
 const COOKIE: &str = "mtyid";
 
+const INVALID_TOKEN: Option<&str> = Some("invalid token");
+
 pub trait SessionCookie {
     fn cookie(&self) -> Cookie<'static>;
 }
@@ -28,6 +47,35 @@ impl SessionCookie for SessionInfo {
     }
 }
 
+pub struct SessionSecurity;
+
+impl Modify for SessionSecurity {
+    fn modify(&self, openapi: &mut OpenApi) {
+        let cookie = ApiKey::Cookie(ApiKeyValue::new(COOKIE));
+        let bearer = HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build();
+
+        let components =
+            openapi.components.get_or_insert_with(Default::default);
+        components
+            .add_security_scheme("session", SecurityScheme::ApiKey(cookie));
+        components.add_security_scheme("token", SecurityScheme::Http(bearer));
+
+        // Every operation that accepts a session also accepts a token.
+        let session = SecurityRequirement::new("session", Vec::<String>::new());
+        let token = SecurityRequirement::new("token", Vec::<String>::new());
+
+        for item in openapi.paths.paths.values_mut() {
+            for operation in operations(item) {
+                if let Some(security) = &mut operation.security {
+                    if security.contains(&session) {
+                        security.push(token.clone());
+                    }
+                }
+            }
+        }
+    }
+}
+
 pub trait CookieSession {
     fn session(&self) -> Option<SessionId>;
 }
@@ -78,6 +126,40 @@ impl IntoResponse for InvalidSession {
     }
 }
 
+fn bearer_token(parts: &Parts) -> Result<Option<AccessToken>, InvalidSession> {
+    let Some(header) = parts.headers.get(AUTHORIZATION) else {
+        return Ok(None);
+    };
+
+    header
+        .to_str()
+        .ok()
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .and_then(|token| token.trim().parse().ok())
+        .map(Some)
+        .ok_or_else(|| {
+            InvalidSession::Other(Unauthenticated(INVALID_TOKEN).into())
+        })
+}
+
+async fn token_user(
+    state: &AppState,
+    token: AccessToken,
+    scope: TokenScope,
+) -> Result<Arc<Cached<minty_core::User>>, InvalidSession> {
+    let Some(token) = state.repo.tokens().get(token).await? else {
+        return Err(InvalidSession::Other(
+            Unauthenticated(INVALID_TOKEN).into(),
+        ));
+    };
+
+    if !token.allows(scope) {
+        return Err(InvalidSession::Other(Unauthorized.into()));
+    }
+
+    Ok(token.user)
+}
+
 pub struct Session(pub Arc<Cached<minty_core::Session>>);
 
 #[async_trait]
@@ -109,18 +191,43 @@ impl FromRequestParts<AppState> for Session {
     }
 }
 
-pub struct User(pub Arc<Cached<minty_core::User>>);
+pub struct User<S>(pub Arc<Cached<minty_core::User>>, pub PhantomData<S>);
 
 #[async_trait]
-impl FromRequestParts<AppState> for User {
+impl<S: Scope> FromRequestParts<AppState> for User<S> {
     type Rejection = InvalidSession;
 
     async fn from_request_parts(
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
+        if let Some(token) = bearer_token(parts)? {
+            let user = token_user(state, token, S::SCOPE).await?;
+            return Ok(User(user, PhantomData));
+        }
+
         let session = Session::from_request_parts(parts, state).await?.0;
-        Ok(User(session.user()))
+        Ok(User(session.user(), PhantomData))
+    }
+}
+
+pub struct Admin(pub Arc<Cached<minty_core::User>>);
+
+#[async_trait]
+impl FromRequestParts<AppState> for Admin {
+    type Rejection = InvalidSession;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(token) = bearer_token(parts)? {
+            let user = token_user(state, token, TokenScope::Admin).await?;
+            return Ok(Admin(user));
+        }
+
+        let session = Session::from_request_parts(parts, state).await?.0;
+        Ok(Admin(session.user()))
     }
 }
 
@@ -134,6 +241,11 @@ impl FromRequestParts<AppState> for OptionalUser {
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
+        if let Some(token) = bearer_token(parts)? {
+            let user = token_user(state, token, TokenScope::Read).await?;
+            return Ok(Self(Some(user)));
+        }
+
         let jar = match CookieJar::from_request_parts(parts, state).await {
             Ok(jar) => jar,
             Err(err) => match err {},
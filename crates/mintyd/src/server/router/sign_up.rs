@@ -5,12 +5,22 @@ use super::{
 
 use axum::{
     extract::{Query, State},
-    routing::post,
     Form,
 };
 use axum_extra::extract::cookie::CookieJar;
 use minty::{http::query, SignUp};
+use utoipa_axum::routes;
 
+#[utoipa::path(
+    post,
+    path = "",
+    params(query::SignUp),
+    request_body(
+        content = SignUp,
+        content_type = "application/x-www-form-urlencoded"
+    ),
+    responses((status = OK, description = "ID of the new user", body = String)),
+)]
 async fn sign_up(
     State(AppState { repo }): State<AppState>,
     Query(query): Query<query::SignUp>,
@@ -28,5 +38,5 @@ async fn sign_up(
 }
 
 pub fn routes() -> Router {
-    Router::new().route("/", post(sign_up))
+    Router::new().routes(routes!(sign_up))
 }
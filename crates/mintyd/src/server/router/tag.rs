@@ -1,5 +1,6 @@
 use super::{
-    session::{OptionalUser, User},
+    scope,
+    session::{Admin, OptionalUser, User},
     text::Text,
     AppState, Result, Router,
 };
@@ -7,16 +8,46 @@ use super::{
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    routing::{delete, get, post, put},
     Json,
 };
 use minty::{
-    http::query::SetProfileName, text, ProfileName, Source, Tag, Url, Uuid,
+    http::query::SetProfileName, text, ProfileName, Source, Tag, TagPreview,
+    Url, Uuid,
 };
+use utoipa_axum::routes;
 
+#[utoipa::path(
+    put,
+    path = "/{tag}/implies/{implied}",
+    params(("tag" = Uuid, Path), ("implied" = Uuid, Path)),
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
+async fn add_implication(
+    State(AppState { repo }): State<AppState>,
+    Admin(admin): Admin,
+    Path((tag, implied)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode> {
+    repo.admin(admin)?
+        .tag(tag)
+        .await?
+        .add_implication(implied)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    operation_id = "add_tag_source",
+    path = "/{tag}/source",
+    params(("tag" = Uuid, Path)),
+    request_body = Url,
+    responses((status = OK, body = Source)),
+    security(("session" = [])),
+)]
 async fn add_source(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Tags>,
     Path(tag): Path<Uuid>,
     Json(url): Json<Url>,
 ) -> Result<Json<Source>> {
@@ -29,17 +60,32 @@ async fn add_source(
     ))
 }
 
+#[utoipa::path(
+    post,
+    path = "/{tag}",
+    params(("tag" = String, Path)),
+    responses((status = OK, description = "ID of the new tag", body = String)),
+    security(("session" = [])),
+)]
 async fn add_tag(
     State(AppState { repo }): State<AppState>,
     Path(tag): Path<text::Name>,
-    User(user): User,
+    User(user, _): User<scope::Tags>,
 ) -> Result<String> {
     Ok(repo.with_user(user).tags().add(tag).await?.id().to_string())
 }
 
+#[utoipa::path(
+    delete,
+    operation_id = "delete_tag_alias",
+    path = "/{tag}/name/{name}",
+    params(("tag" = Uuid, Path), ("name" = String, Path)),
+    responses((status = OK, body = ProfileName)),
+    security(("session" = [])),
+)]
 async fn delete_alias(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Tags>,
     Path((tag, name)): Path<(Uuid, String)>,
 ) -> Result<Json<ProfileName>> {
     Ok(Json(
@@ -51,9 +97,71 @@ async fn delete_alias(
     ))
 }
 
+#[utoipa::path(
+    delete,
+    operation_id = "delete_tag_category",
+    path = "/{tag}/category",
+    params(("tag" = Uuid, Path)),
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
+async fn delete_category(
+    State(AppState { repo }): State<AppState>,
+    User(user, _): User<scope::Tags>,
+    Path(tag): Path<Uuid>,
+) -> Result<StatusCode> {
+    repo.with_user(user)
+        .tag(tag)
+        .await?
+        .set_category(None)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{tag}/implies/{implied}",
+    params(("tag" = Uuid, Path), ("implied" = Uuid, Path)),
+    responses(
+        (status = NO_CONTENT),
+        (status = NOT_FOUND),
+    ),
+    security(("session" = [])),
+)]
+async fn delete_implication(
+    State(AppState { repo }): State<AppState>,
+    Admin(admin): Admin,
+    Path((tag, implied)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode> {
+    let status = if repo
+        .admin(admin)?
+        .tag(tag)
+        .await?
+        .delete_implication(implied)
+        .await?
+    {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    };
+
+    Ok(status)
+}
+
+#[utoipa::path(
+    delete,
+    operation_id = "delete_tag_source",
+    path = "/{tag}/source/{source}",
+    params(("tag" = Uuid, Path), ("source" = i64, Path)),
+    responses(
+        (status = NO_CONTENT),
+        (status = NOT_FOUND),
+    ),
+    security(("session" = [])),
+)]
 async fn delete_source(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Tags>,
     Path((tag, source)): Path<(Uuid, i64)>,
 ) -> Result<StatusCode> {
     let status = if repo
@@ -71,9 +179,18 @@ async fn delete_source(
     Ok(status)
 }
 
+#[utoipa::path(
+    delete,
+    operation_id = "delete_tag_sources",
+    path = "/{tag}/source",
+    params(("tag" = Uuid, Path)),
+    request_body = Vec<String>,
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
 async fn delete_sources(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Tags>,
     Path(tag): Path<Uuid>,
     Json(sources): Json<Vec<String>>,
 ) -> Result<StatusCode> {
@@ -85,15 +202,28 @@ async fn delete_sources(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    delete,
+    path = "/{tag}",
+    params(("tag" = Uuid, Path)),
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
 async fn delete_tag(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Tags>,
     Path(tag): Path<Uuid>,
 ) -> Result<StatusCode> {
     repo.with_user(user).tag(tag).await?.delete().await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    get,
+    path = "/{tag}",
+    params(("tag" = Uuid, Path)),
+    responses((status = OK, body = Tag)),
+)]
 async fn get_tag(
     State(AppState { repo }): State<AppState>,
     OptionalUser(user): OptionalUser,
@@ -102,9 +232,78 @@ async fn get_tag(
     Ok(Json(repo.optional_user(user)?.tag(tag).await?.get()?))
 }
 
+#[utoipa::path(
+    get,
+    path = "/{tag}/implies",
+    params(("tag" = Uuid, Path)),
+    responses((status = OK, body = Vec<TagPreview>)),
+)]
+async fn get_implications(
+    State(AppState { repo }): State<AppState>,
+    OptionalUser(user): OptionalUser,
+    Path(tag): Path<Uuid>,
+) -> Result<Json<Vec<TagPreview>>> {
+    Ok(Json(
+        repo.optional_user(user)?
+            .tag(tag)
+            .await?
+            .implications()
+            .await?,
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{tag}/merge",
+    params(("tag" = Uuid, Path)),
+    request_body = Uuid,
+    responses((status = OK, body = Tag)),
+    security(("session" = [])),
+)]
+async fn merge_tag(
+    State(AppState { repo }): State<AppState>,
+    User(user, _): User<scope::Tags>,
+    Path(tag): Path<Uuid>,
+    Json(other): Json<Uuid>,
+) -> Result<Json<Tag>> {
+    Ok(Json(
+        repo.with_user(user).tag(tag).await?.merge(other).await?,
+    ))
+}
+
+#[utoipa::path(
+    put,
+    operation_id = "set_tag_category",
+    path = "/{tag}/category/{category}",
+    params(("tag" = Uuid, Path), ("category" = String, Path)),
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
+async fn set_category(
+    State(AppState { repo }): State<AppState>,
+    User(user, _): User<scope::Tags>,
+    Path((tag, category)): Path<(Uuid, String)>,
+) -> Result<StatusCode> {
+    repo.with_user(user)
+        .tag(tag)
+        .await?
+        .set_category(Some(&category))
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    put,
+    operation_id = "set_tag_description",
+    path = "/{tag}/description",
+    params(("tag" = Uuid, Path)),
+    request_body(content = String, content_type = "text/plain"),
+    responses((status = OK, body = String)),
+    security(("session" = [])),
+)]
 async fn set_description(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Tags>,
     Path(tag): Path<Uuid>,
     Text(description): Text<text::Description>,
 ) -> Result<String> {
@@ -116,9 +315,17 @@ async fn set_description(
         .await?)
 }
 
+#[utoipa::path(
+    put,
+    operation_id = "set_tag_name",
+    path = "/{tag}/name/{name}",
+    params(("tag" = Uuid, Path), ("name" = String, Path), SetProfileName),
+    responses((status = OK, body = ProfileName)),
+    security(("session" = [])),
+)]
 async fn set_name(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Tags>,
     Path((tag, name)): Path<(Uuid, text::Name)>,
     Query(SetProfileName { main }): Query<SetProfileName>,
 ) -> Result<Json<ProfileName>> {
@@ -136,9 +343,14 @@ async fn set_name(
 
 pub fn routes() -> Router {
     Router::new()
-        .route("/:tag", get(get_tag).post(add_tag).delete(delete_tag))
-        .route("/:tag/name/:name", put(set_name).delete(delete_alias))
-        .route("/:tag/description", put(set_description))
-        .route("/:tag/source", post(add_source).delete(delete_sources))
-        .route("/:tag/source/:source", delete(delete_source))
+        .routes(routes!(get_tag, add_tag, delete_tag))
+        .routes(routes!(merge_tag))
+        .routes(routes!(set_name, delete_alias))
+        .routes(routes!(delete_category))
+        .routes(routes!(set_category))
+        .routes(routes!(set_description))
+        .routes(routes!(get_implications))
+        .routes(routes!(add_implication, delete_implication))
+        .routes(routes!(add_source, delete_sources))
+        .routes(routes!(delete_source))
 }
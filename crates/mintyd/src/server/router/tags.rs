@@ -4,11 +4,17 @@ use crate::server::error::Error;
 
 use axum::{
     extract::{Path, Query, State},
-    routing::get,
     Json,
 };
 use minty::{http::query::ProfileQuery, SearchResult, TagPreview, Uuid};
+use utoipa_axum::routes;
 
+#[utoipa::path(
+    get,
+    path = "/{tags}",
+    params(("tags" = String, Path, description = "Comma-separated tag IDs")),
+    responses((status = OK, body = Vec<TagPreview>)),
+)]
 async fn get_tags(
     State(AppState { repo }): State<AppState>,
     Path(tags): Path<String>,
@@ -27,6 +33,13 @@ async fn get_tags(
     Ok(Json(repo.optional_user(user)?.tags().get(&ids).await?))
 }
 
+#[utoipa::path(
+    get,
+    operation_id = "search_tags",
+    path = "",
+    params(ProfileQuery),
+    responses((status = OK, body = SearchResult<TagPreview>)),
+)]
 async fn search(
     State(AppState { repo }): State<AppState>,
     Query(query): Query<ProfileQuery>,
@@ -39,6 +52,6 @@ async fn search(
 
 pub fn routes() -> Router {
     Router::new()
-        .route("/", get(search))
-        .route("/:tags", get(get_tags))
+        .routes(routes!(search))
+        .routes(routes!(get_tags))
 }
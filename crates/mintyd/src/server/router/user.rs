@@ -1,6 +1,7 @@
 use super::{
+    scope,
+    session::{Admin, OptionalUser, User},
     session::{CookieJarSession, SessionCookie},
-    session::{OptionalUser, User},
     text::Text,
     AppState, Result, Router,
 };
@@ -8,17 +9,26 @@ use super::{
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    routing::{delete, get, post, put},
     Json,
 };
 use axum_extra::extract::cookie::CookieJar;
 use minty::{
-    http::query::SetProfileName, text, Login, ProfileName, Source, Url, Uuid,
+    http::query::SetProfileName, text, Login, NewToken, ProfileName, Source,
+    Token, TokenSecret, Url, Uuid,
 };
+use utoipa_axum::routes;
 
+#[utoipa::path(
+    post,
+    operation_id = "add_user_source",
+    path = "/source",
+    request_body = Url,
+    responses((status = OK, body = Source)),
+    security(("session" = [])),
+)]
 async fn add_source(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Account>,
     Json(url): Json<Url>,
 ) -> Result<Json<Source>> {
     Ok(Json(
@@ -26,6 +36,12 @@ async fn add_source(
     ))
 }
 
+#[utoipa::path(
+    post,
+    path = "/session",
+    request_body = Login,
+    responses((status = OK, description = "ID of the authenticated user", body = String)),
+)]
 async fn create_session(
     State(AppState { repo }): State<AppState>,
     jar: CookieJar,
@@ -40,16 +56,44 @@ async fn create_session(
     Ok((jar.add(session.cookie()), session.user_id.to_string()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/token",
+    request_body = NewToken,
+    responses((status = OK, body = TokenSecret)),
+    security(("session" = [])),
+)]
+async fn create_token(
+    State(AppState { repo }): State<AppState>,
+    User(user, _): User<scope::Admin>,
+    Json(token): Json<NewToken>,
+) -> Result<Json<TokenSecret>> {
+    Ok(Json(repo.with_user(user).create_token(&token).await?))
+}
+
+#[utoipa::path(
+    delete,
+    operation_id = "delete_user_alias",
+    path = "/name/{name}",
+    params(("name" = String, Path)),
+    responses((status = OK, body = ProfileName)),
+    security(("session" = [])),
+)]
 async fn delete_alias(
     State(AppState { repo }): State<AppState>,
     Path(name): Path<String>,
-    User(user): User,
+    User(user, _): User<scope::Account>,
 ) -> Result<Json<ProfileName>> {
     Ok(Json(
         repo.with_user(user).edit_self().delete_alias(&name).await?,
     ))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/session",
+    responses((status = NO_CONTENT)),
+)]
 async fn delete_session(
     State(AppState { repo }): State<AppState>,
     jar: CookieJar,
@@ -61,10 +105,21 @@ async fn delete_session(
     Ok((StatusCode::NO_CONTENT, jar.remove_session_cookie()))
 }
 
+#[utoipa::path(
+    delete,
+    operation_id = "delete_user_source",
+    path = "/source/{source}",
+    params(("source" = i64, Path)),
+    responses(
+        (status = NO_CONTENT),
+        (status = NOT_FOUND),
+    ),
+    security(("session" = [])),
+)]
 async fn delete_source(
     State(AppState { repo }): State<AppState>,
     Path(source): Path<i64>,
-    User(user): User,
+    User(user, _): User<scope::Account>,
 ) -> Result<StatusCode> {
     let status = if repo
         .with_user(user)
@@ -80,9 +135,17 @@ async fn delete_source(
     Ok(status)
 }
 
+#[utoipa::path(
+    delete,
+    operation_id = "delete_user_sources",
+    path = "/source",
+    request_body = Vec<String>,
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
 async fn delete_sources(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Account>,
     Json(sources): Json<Vec<String>>,
 ) -> Result<StatusCode> {
     repo.with_user(user)
@@ -92,6 +155,15 @@ async fn delete_sources(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    delete,
+    path = "",
+    responses(
+        (status = NO_CONTENT),
+        (status = UNAUTHORIZED),
+    ),
+    security(("session" = [])),
+)]
 async fn delete_user(
     State(AppState { repo }): State<AppState>,
     jar: CookieJar,
@@ -108,13 +180,38 @@ async fn delete_user(
     Ok((status, jar.remove_session_cookie()))
 }
 
+#[utoipa::path(
+    get,
+    path = "",
+    responses((status = OK, body = minty::User)),
+    security(("session" = [])),
+)]
 async fn get_authenticated_user(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Read>,
 ) -> Result<Json<minty::User>> {
     Ok(Json(repo.with_user(user).get_self()?))
 }
 
+#[utoipa::path(
+    get,
+    path = "/token",
+    responses((status = OK, body = Vec<Token>)),
+    security(("session" = [])),
+)]
+async fn get_tokens(
+    State(AppState { repo }): State<AppState>,
+    User(user, _): User<scope::Read>,
+) -> Result<Json<Vec<Token>>> {
+    Ok(Json(repo.with_user(user).get_tokens().await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{user}",
+    params(("user" = Uuid, Path)),
+    responses((status = OK, body = minty::User)),
+)]
 async fn get_user(
     State(AppState { repo }): State<AppState>,
     Path(user): Path<Uuid>,
@@ -125,18 +222,32 @@ async fn get_user(
     ))
 }
 
+#[utoipa::path(
+    put,
+    path = "/{user}/admin",
+    params(("user" = Uuid, Path)),
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
 async fn grant_admin(
     State(AppState { repo }): State<AppState>,
-    User(admin): User,
+    Admin(admin): Admin,
     Path(user): Path<Uuid>,
 ) -> Result<StatusCode> {
     repo.admin(admin)?.user(user).await?.set_admin(true).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    delete,
+    path = "/{user}/admin",
+    params(("user" = Uuid, Path)),
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
 async fn revoke_admin(
     State(AppState { repo }): State<AppState>,
-    User(admin): User,
+    Admin(admin): Admin,
     Path(user): Path<Uuid>,
 ) -> Result<StatusCode> {
     repo.admin(admin)?
@@ -147,9 +258,33 @@ async fn revoke_admin(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    delete,
+    path = "/token/{token}",
+    params(("token" = Uuid, Path)),
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
+async fn revoke_token(
+    State(AppState { repo }): State<AppState>,
+    Path(token): Path<Uuid>,
+    User(user, _): User<scope::Account>,
+) -> Result<StatusCode> {
+    repo.with_user(user).revoke_token(token).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    put,
+    operation_id = "set_user_description",
+    path = "/description",
+    request_body(content = String, content_type = "text/plain"),
+    responses((status = OK, body = String)),
+    security(("session" = [])),
+)]
 async fn set_description(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Account>,
     Text(description): Text<text::Description>,
 ) -> Result<String> {
     Ok(repo
@@ -159,20 +294,35 @@ async fn set_description(
         .await?)
 }
 
+#[utoipa::path(
+    put,
+    path = "/email",
+    request_body(content = String, content_type = "text/plain"),
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
 async fn set_email(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Admin>,
     Text(email): Text<text::Email>,
 ) -> Result<StatusCode> {
     repo.with_user(user).edit_self().set_email(email).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    put,
+    operation_id = "set_user_name",
+    path = "/name/{name}",
+    params(("name" = String, Path), SetProfileName),
+    responses((status = OK, body = ProfileName)),
+    security(("session" = [])),
+)]
 async fn set_name(
     State(AppState { repo }): State<AppState>,
     Path(name): Path<text::Name>,
     Query(SetProfileName { main }): Query<SetProfileName>,
-    User(user): User,
+    User(user, _): User<scope::Account>,
 ) -> Result<Json<ProfileName>> {
     let main = main.unwrap_or(false);
     let user = repo.with_user(user).edit_self();
@@ -186,9 +336,16 @@ async fn set_name(
     Ok(Json(names))
 }
 
+#[utoipa::path(
+    put,
+    path = "/password",
+    request_body(content = String, content_type = "text/plain"),
+    responses((status = NO_CONTENT)),
+    security(("session" = [])),
+)]
 async fn set_password(
     State(AppState { repo }): State<AppState>,
-    User(user): User,
+    User(user, _): User<scope::Admin>,
     Text(password): Text<text::Password>,
 ) -> Result<StatusCode> {
     repo.with_user(user)
@@ -200,14 +357,16 @@ async fn set_password(
 
 pub fn routes() -> Router {
     Router::new()
-        .route("/", get(get_authenticated_user).delete(delete_user))
-        .route("/description", put(set_description))
-        .route("/email", put(set_email))
-        .route("/name/:name", put(set_name).delete(delete_alias))
-        .route("/password", put(set_password))
-        .route("/session", post(create_session).delete(delete_session))
-        .route("/source", post(add_source).delete(delete_sources))
-        .route("/source/:source", delete(delete_source))
-        .route("/:user", get(get_user))
-        .route("/:user/admin", put(grant_admin).delete(revoke_admin))
+        .routes(routes!(get_authenticated_user, delete_user))
+        .routes(routes!(set_description))
+        .routes(routes!(set_email))
+        .routes(routes!(set_name, delete_alias))
+        .routes(routes!(set_password))
+        .routes(routes!(create_session, delete_session))
+        .routes(routes!(add_source, delete_sources))
+        .routes(routes!(delete_source))
+        .routes(routes!(get_tokens, create_token))
+        .routes(routes!(revoke_token))
+        .routes(routes!(get_user))
+        .routes(routes!(grant_admin, revoke_admin))
 }
@@ -2,11 +2,17 @@ use super::{session::OptionalUser, AppState, Result, Router};
 
 use axum::{
     extract::{Query, State},
-    routing::get,
     Json,
 };
 use minty::{http::query::ProfileQuery, SearchResult, UserPreview};
+use utoipa_axum::routes;
 
+#[utoipa::path(
+    get,
+    path = "",
+    params(ProfileQuery),
+    responses((status = OK, body = SearchResult<UserPreview>)),
+)]
 async fn get_users(
     State(AppState { repo }): State<AppState>,
     Query(query): Query<ProfileQuery>,
@@ -21,5 +27,5 @@ async fn get_users(
 }
 
 pub fn routes() -> Router {
-    Router::new().route("/", get(get_users))
+    Router::new().routes(routes!(get_users))
 }
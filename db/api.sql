@@ -28,8 +28,17 @@ CREATE VIEW object AS
 SELECT
     object_id,
     preview_id,
-    array_remove(array_agg(post_id ORDER BY date_added DESC), NULL) AS posts
-FROM data.object
+    array_remove(array_agg(post_id ORDER BY date_added DESC), NULL) AS posts,
+    (
+        SELECT coalesce(jsonb_object_agg(variant, preview_id), '{}')
+        FROM data.object_preview p
+        WHERE p.object_id = object.object_id
+    ) AS previews,
+    metadata,
+    storyboard_id,
+    storyboard,
+    animation_id
+FROM data.object object
 LEFT JOIN data.post_object USING (object_id)
 GROUP BY object_id;
 
@@ -39,11 +48,32 @@ SELECT
     message
 FROM data.object_preview_error;
 
+CREATE VIEW preview_status AS
+SELECT
+    object_id,
+    CASE
+        WHEN job.object_id IS NULL AND error.object_id IS NULL THEN 'done'
+        WHEN job.object_id IS NULL THEN 'failed'
+        WHEN job.locked_until > NOW() THEN 'running'
+        ELSE 'pending'
+    END AS state,
+    coalesce(job.attempts, 0) AS attempts,
+    CASE
+        WHEN job.object_id IS NULL THEN error.message
+        ELSE job.last_error
+    END AS error
+FROM data.object object
+LEFT JOIN data.preview_job job USING (object_id)
+LEFT JOIN data.object_preview_error error USING (object_id);
+
 CREATE VIEW object_ref_view AS
 SELECT
     object_id,
     count(objects) +
     count(previews) +
+    count(variants) +
+    count(storyboards) +
+    count(animations) +
     count(icons) +
     count(avatars) +
     count(banners)
@@ -57,6 +87,18 @@ LEFT JOIN (
     SELECT preview_id AS object_id
     FROM data.object
 ) previews USING (object_id)
+LEFT JOIN (
+    SELECT preview_id AS object_id
+    FROM data.object_preview
+) variants USING (object_id)
+LEFT JOIN (
+    SELECT storyboard_id AS object_id
+    FROM data.object
+) storyboards USING (object_id)
+LEFT JOIN (
+    SELECT animation_id AS object_id
+    FROM data.object
+) animations USING (object_id)
 LEFT JOIN (
     SELECT icon AS object_id
     FROM data.site
@@ -159,6 +201,17 @@ LEFT JOIN (
     GROUP BY creator
 ) tags USING (user_id);
 
+CREATE VIEW user_token AS
+SELECT
+    token_id,
+    user_id,
+    name,
+    scopes,
+    date_created,
+    expiration,
+    last_used
+FROM data.user_token;
+
 CREATE VIEW tag AS
 SELECT
     tag_id,
@@ -170,6 +223,7 @@ SELECT
     banner,
     created,
     creator,
+    category,
     coalesce(post_count, 0) AS post_count
 FROM data.tag t
 JOIN entity_profile entity ON tag_id = profile_id
@@ -223,6 +277,32 @@ FROM data.object
 LEFT JOIN data.post_object post_objects USING (object_id)
 GROUP BY object_id;
 
+CREATE VIEW post_object_search AS
+SELECT
+    post_id,
+    coalesce(
+        jsonb_agg(jsonb_strip_nulls(jsonb_build_object(
+            'type', media_type,
+            'subtype', media_subtype,
+            'width', (metadata->>'width')::integer,
+            'height', (metadata->>'height')::integer,
+            'duration_ms', (metadata->>'duration_ms')::bigint,
+            'orientation', CASE
+                WHEN (metadata->>'width')::integer >
+                    (metadata->>'height')::integer THEN 'landscape'
+                WHEN (metadata->>'width')::integer <
+                    (metadata->>'height')::integer THEN 'portrait'
+                WHEN (metadata->>'width')::integer =
+                    (metadata->>'height')::integer THEN 'square'
+            END
+        )) ORDER BY date_added) FILTER (WHERE object_id IS NOT NULL),
+        '[]'
+    ) AS objects
+FROM data.post
+LEFT JOIN data.post_object USING (post_id)
+LEFT JOIN data.object USING (object_id)
+GROUP BY post_id;
+
 CREATE VIEW post_search AS
 SELECT
     post_id,
@@ -232,8 +312,13 @@ SELECT
     visibility,
     date_created,
     date_modified,
-    coalesce(array_agg(tag_id) FILTER (WHERE tag_id IS NOT NULL), '{}') AS tags
-FROM data.post
+    coalesce(array_agg(tag_id) FILTER (WHERE tag_id IS NOT NULL), '{}') AS tags,
+    (
+        SELECT objects
+        FROM post_object_search o
+        WHERE o.post_id = post.post_id
+    ) AS objects
+FROM data.post post
 LEFT JOIN data.post_tag USING (post_id)
 GROUP BY post_id;
 
@@ -257,7 +342,8 @@ GROUP BY source_id;
 CREATE VIEW tag_search AS
 SELECT
     tag_id,
-    array_agg(name) AS names
+    array_agg(name) AS names,
+    category
 FROM data.tag
 JOIN data.entity_name ON tag_id = profile_id
 GROUP BY tag_id, profile_id;
@@ -293,6 +379,30 @@ RETURNS anyarray AS $$
     WHERE element.value <> ALL(array2);
 $$ LANGUAGE sql;
 
+CREATE FUNCTION claim_preview_job(a_lease_seconds integer)
+RETURNS TABLE (object_id uuid, attempts smallint) AS $$
+    UPDATE data.preview_job job
+    SET
+        attempts = job.attempts + 1,
+        locked_until = NOW() + make_interval(secs => a_lease_seconds)
+    WHERE job.object_id = (
+        SELECT next.object_id
+        FROM data.preview_job next
+        WHERE
+            next.run_after <= NOW() AND
+            (next.locked_until IS NULL OR next.locked_until <= NOW())
+        ORDER BY next.priority DESC, next.run_after
+        LIMIT 1
+        FOR UPDATE SKIP LOCKED
+    )
+    RETURNING job.object_id, job.attempts;
+$$ LANGUAGE SQL;
+
+CREATE FUNCTION complete_preview_job(a_object_id uuid) RETURNS void AS $$
+    DELETE FROM data.preview_job
+    WHERE object_id = a_object_id;
+$$ LANGUAGE SQL;
+
 CREATE FUNCTION create_comment(a_user_id uuid, a_post_id uuid, a_content text)
 RETURNS SETOF data.post_comment AS $$
     INSERT INTO data.post_comment (
@@ -340,31 +450,126 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE FUNCTION create_implied_post_tags()
+RETURNS TABLE (post_id uuid, tags uuid[]) AS $$
+    WITH RECURSIVE implied (post_id, tag_id) AS (
+        SELECT post_id, tag_id
+        FROM data.post_tag
+        UNION
+        SELECT implied.post_id, implication.implied_tag_id
+        FROM implied
+        JOIN data.tag_implication implication USING (tag_id)
+    ), inserted AS (
+        INSERT INTO data.post_tag (post_id, tag_id)
+        SELECT post_id, tag_id
+        FROM implied
+        ON CONFLICT DO NOTHING
+        RETURNING post_tag.post_id, post_tag.tag_id
+    )
+    SELECT inserted.post_id, array_agg(inserted.tag_id)
+    FROM inserted
+    GROUP BY inserted.post_id;
+$$ LANGUAGE SQL;
+
 CREATE FUNCTION create_object(
     a_object_id     uuid,
+    a_hash          text,
+    a_media_type    text,
+    a_media_subtype text,
     a_preview_id    uuid,
-    a_source_id     bigint
+    a_source_id     bigint,
+    a_phash         bigint,
+    a_variants      text[],
+    a_previews      uuid[],
+    a_metadata      jsonb,
+    a_storyboard_id uuid,
+    a_storyboard    jsonb,
+    a_animation_id  uuid
 ) RETURNS void AS $$
 BEGIN
-    PERFORM create_object_refs(ARRAY[a_object_id, a_preview_id]);
+    PERFORM create_object_refs(ARRAY[
+        a_object_id,
+        a_preview_id,
+        a_storyboard_id,
+        a_animation_id
+    ]);
 
     INSERT INTO data.object (
         object_id,
+        hash,
+        media_type,
+        media_subtype,
         preview_id,
-        source_id
+        source_id,
+        phash,
+        metadata,
+        storyboard_id,
+        storyboard,
+        animation_id
     ) VALUES (
         a_object_id,
+        a_hash,
+        a_media_type,
+        a_media_subtype,
         a_preview_id,
-        a_source_id
+        a_source_id,
+        a_phash,
+        a_metadata,
+        a_storyboard_id,
+        a_storyboard,
+        a_animation_id
     ) ON CONFLICT (object_id) DO UPDATE SET
+        hash = a_hash,
+        media_type = a_media_type,
+        media_subtype = a_media_subtype,
         preview_id = a_preview_id,
-        source_id = a_source_id;
+        source_id = a_source_id,
+        phash = a_phash,
+        metadata = a_metadata,
+        storyboard_id = a_storyboard_id,
+        storyboard = a_storyboard,
+        animation_id = a_animation_id;
+
+    PERFORM create_object_previews(a_object_id, a_variants, a_previews);
 
     DELETE FROM data.object_preview_error
     WHERE object_id = a_object_id;
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE FUNCTION create_pending_object(
+    a_object_id     uuid,
+    a_hash          text,
+    a_media_type    text,
+    a_media_subtype text,
+    a_priority      smallint,
+    a_uploader      uuid
+) RETURNS void AS $$
+BEGIN
+    PERFORM create_object(
+        a_object_id,
+        a_hash,
+        a_media_type,
+        a_media_subtype,
+        NULL,
+        NULL,
+        NULL,
+        '{}',
+        '{}',
+        NULL,
+        NULL,
+        NULL,
+        NULL
+    );
+
+    INSERT INTO data.object_uploader (object_id, user_id)
+    VALUES (a_object_id, a_uploader)
+    ON CONFLICT DO NOTHING;
+
+    PERFORM create_preview_job(a_object_id, a_priority);
+END;
+$$ LANGUAGE plpgsql;
+
 CREATE FUNCTION create_object_preview_error(
     a_object_id     uuid,
     a_message       text
@@ -381,6 +586,23 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE FUNCTION create_object_previews(
+    a_object_id     uuid,
+    a_variants      text[],
+    a_previews      uuid[]
+) RETURNS void AS $$
+BEGIN
+    PERFORM create_object_refs(a_previews);
+
+    DELETE FROM data.object_preview
+    WHERE object_id = a_object_id;
+
+    INSERT INTO data.object_preview (object_id, variant, preview_id)
+    SELECT a_object_id, variant, preview_id
+    FROM unnest(a_variants, a_previews) AS previews (variant, preview_id);
+END;
+$$ LANGUAGE plpgsql;
+
 CREATE FUNCTION create_object_refs(
     a_objects       uuid[]
 ) RETURNS void AS $$
@@ -441,7 +663,7 @@ BEGIN
     SELECT l_post_id, tag_id
     FROM (
         SELECT unnest AS tag_id
-        FROM unnest(tags)
+        FROM unnest(read_implied_tags(tags))
     ) t;
 
     RETURN QUERY
@@ -488,14 +710,35 @@ $$ LANGUAGE plpgsql;
 CREATE FUNCTION create_post_tag(
     a_post_id       uuid,
     a_tag_id        uuid
-) RETURNS void AS $$
+) RETURNS uuid[] AS $$
+DECLARE l_tags uuid[];
 BEGIN
-    INSERT INTO data.post_tag (post_id, tag_id)
-    VALUES (a_post_id, a_tag_id)
-    ON CONFLICT DO NOTHING;
+    WITH inserted AS (
+        INSERT INTO data.post_tag (post_id, tag_id)
+        SELECT a_post_id, unnest(read_implied_tags(ARRAY[a_tag_id]))
+        ON CONFLICT DO NOTHING
+        RETURNING tag_id
+    )
+    SELECT coalesce(array_agg(tag_id), '{}')
+    INTO l_tags
+    FROM inserted;
+
+    RETURN l_tags;
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE FUNCTION create_preview_job(a_object_id uuid, a_priority smallint)
+RETURNS void AS $$
+    INSERT INTO data.preview_job (object_id, priority)
+    VALUES (a_object_id, a_priority)
+    ON CONFLICT (object_id) DO UPDATE SET
+        priority = greatest(data.preview_job.priority, a_priority),
+        attempts = 0,
+        run_after = NOW(),
+        locked_until = NULL,
+        last_error = NULL;
+$$ LANGUAGE SQL;
+
 CREATE FUNCTION create_related_post(a_post_id uuid, a_related uuid)
 RETURNS uuid[] AS $$
 BEGIN
@@ -584,6 +827,35 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE FUNCTION create_tag_category(a_name text) RETURNS boolean AS $$
+BEGIN
+    INSERT INTO data.tag_category (name)
+    VALUES (a_name)
+    ON CONFLICT DO NOTHING;
+
+    RETURN FOUND;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION create_tag_implication(
+    a_tag_id        uuid,
+    a_implied_tag_id uuid
+) RETURNS boolean AS $$
+BEGIN
+    LOCK TABLE data.tag_implication IN SHARE ROW EXCLUSIVE MODE;
+
+    IF a_tag_id = ANY(read_implied_tags(ARRAY[a_implied_tag_id])) THEN
+        RETURN false;
+    END IF;
+
+    INSERT INTO data.tag_implication (tag_id, implied_tag_id)
+    VALUES (a_tag_id, a_implied_tag_id)
+    ON CONFLICT DO NOTHING;
+
+    RETURN true;
+END;
+$$ LANGUAGE plpgsql;
+
 CREATE FUNCTION create_user(
     a_name text,
     a_email text,
@@ -609,6 +881,27 @@ CREATE FUNCTION create_user_session(
     VALUES (a_session_id, a_user_id, a_expiration);
 $$ LANGUAGE SQL;
 
+CREATE FUNCTION create_user_token(
+    a_user_id       uuid,
+    a_name          text,
+    a_digest        bytea,
+    a_scopes        text[],
+    a_expiration    timestamptz
+) RETURNS SETOF user_token AS $$
+DECLARE
+    l_token_id      uuid;
+BEGIN
+    INSERT INTO data.user_token (user_id, name, digest, scopes, expiration)
+    VALUES (a_user_id, a_name, a_digest, a_scopes, a_expiration)
+    RETURNING token_id INTO l_token_id;
+
+    RETURN QUERY
+    SELECT *
+    FROM user_token
+    WHERE token_id = l_token_id;
+END;
+$$ LANGUAGE plpgsql;
+
 CREATE FUNCTION delete_comment(a_comment_id uuid, recursive boolean)
 RETURNS boolean AS $$
 DECLARE
@@ -723,17 +1016,149 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE FUNCTION delete_tag_category(a_name text) RETURNS uuid[] AS $$
+DECLARE l_tags uuid[];
+BEGIN
+    SELECT coalesce(array_agg(tag_id), '{}') INTO l_tags
+    FROM data.tag
+    WHERE category = a_name;
+
+    DELETE FROM data.tag_category
+    WHERE name = a_name;
+
+    IF NOT FOUND THEN
+        RETURN NULL;
+    END IF;
+
+    RETURN l_tags;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION delete_tag_implication(
+    a_tag_id        uuid,
+    a_implied_tag_id uuid
+) RETURNS boolean AS $$
+BEGIN
+    DELETE FROM data.tag_implication
+    WHERE tag_id = a_tag_id AND implied_tag_id = a_implied_tag_id;
+
+    RETURN FOUND;
+END;
+$$ LANGUAGE plpgsql;
+
 CREATE FUNCTION delete_user_session(a_session_id bytea) RETURNS void AS $$
     DELETE FROM data.user_session
     WHERE session_id = a_session_id;
 $$ LANGUAGE SQL;
 
+CREATE FUNCTION delete_user_token(a_user_id uuid, a_token_id uuid)
+RETURNS boolean AS $$
+BEGIN
+    DELETE FROM data.user_token
+    WHERE user_id = a_user_id AND token_id = a_token_id;
+
+    RETURN FOUND;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION fail_preview_job(
+    a_object_id     uuid,
+    a_message       text,
+    a_retry_seconds integer
+) RETURNS void AS $$
+BEGIN
+    IF a_retry_seconds IS NULL THEN
+        DELETE FROM data.preview_job
+        WHERE object_id = a_object_id;
+
+        PERFORM create_object_preview_error(a_object_id, a_message);
+    ELSE
+        UPDATE data.preview_job
+        SET
+            run_after = NOW() + make_interval(secs => a_retry_seconds),
+            locked_until = NULL,
+            last_error = a_message
+        WHERE object_id = a_object_id;
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION merge_tag(a_tag_id uuid, a_other uuid) RETURNS uuid[] AS $$
+DECLARE
+    l_posts uuid[];
+    l_implication record;
+BEGIN
+    SELECT coalesce(array_agg(post_id), '{}') INTO l_posts
+    FROM data.post_tag
+    WHERE tag_id = a_other;
+
+    INSERT INTO data.post_tag (post_id, tag_id)
+    SELECT unnest(l_posts), a_tag_id
+    ON CONFLICT DO NOTHING;
+
+    INSERT INTO data.entity_name (profile_id, name)
+    SELECT a_tag_id, name
+    FROM data.entity_name
+    WHERE profile_id = a_other
+    ON CONFLICT DO NOTHING;
+
+    INSERT INTO data.entity_link (profile_id, source_id)
+    SELECT a_tag_id, source_id
+    FROM data.entity_link
+    WHERE profile_id = a_other
+    ON CONFLICT DO NOTHING;
+
+    UPDATE data.entity_profile tag
+    SET
+        description = CASE
+            WHEN other.description = '' THEN tag.description
+            WHEN tag.description = '' THEN other.description
+            ELSE tag.description || E'\n\n' || other.description
+        END,
+        avatar = coalesce(tag.avatar, other.avatar),
+        banner = coalesce(tag.banner, other.banner)
+    FROM data.entity_profile other
+    WHERE tag.profile_id = a_tag_id AND other.profile_id = a_other;
+
+    FOR l_implication IN
+        DELETE FROM data.tag_implication
+        WHERE a_other IN (tag_id, implied_tag_id)
+        RETURNING
+            CASE tag_id
+                WHEN a_other THEN a_tag_id
+                ELSE tag_id
+            END AS tag_id,
+            CASE implied_tag_id
+                WHEN a_other THEN a_tag_id
+                ELSE implied_tag_id
+            END AS implied_tag_id
+    LOOP
+        CONTINUE WHEN l_implication.tag_id = l_implication.implied_tag_id;
+
+        IF NOT create_tag_implication(
+            l_implication.tag_id,
+            l_implication.implied_tag_id
+        ) THEN
+            RAISE 'Merging tags (%) and (%) creates an implication cycle',
+                a_tag_id, a_other
+            USING CONSTRAINT = 'tag_implication_cycle';
+        END IF;
+    END LOOP;
+
+    DELETE FROM data.entity_profile
+    WHERE profile_id = a_other;
+
+    RETURN l_posts;
+END;
+$$ LANGUAGE plpgsql;
+
 CREATE FUNCTION prune() RETURNS void AS $$
 BEGIN
     PERFORM prune_post_objects();
     PERFORM prune_sessions();
     PERFORM prune_sources();
     PERFORM prune_sites();
+    PERFORM prune_tokens();
 END;
 $$ LANGUAGE plpgsql;
 
@@ -793,6 +1218,10 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE FUNCTION prune_tokens() RETURNS void AS $$
+    DELETE FROM data.user_token WHERE expiration <= now();
+$$ LANGUAGE SQL;
+
 CREATE FUNCTION publish_post(draft_id uuid) RETURNS timestamptz AS $$
 DECLARE
     created CONSTANT timestamptz = now();
@@ -840,6 +1269,18 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE FUNCTION read_implied_tags(a_tags uuid[]) RETURNS uuid[] AS $$
+    WITH RECURSIVE implied (tag_id) AS (
+        SELECT unnest(a_tags)
+        UNION
+        SELECT implication.implied_tag_id
+        FROM implied
+        JOIN data.tag_implication implication USING (tag_id)
+    )
+    SELECT coalesce(array_agg(tag_id), '{}')
+    FROM implied;
+$$ LANGUAGE SQL;
+
 CREATE FUNCTION read_object_preview_errors()
 RETURNS SETOF object_preview_error AS $$
 BEGIN
@@ -850,6 +1291,15 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE FUNCTION read_object_post_search(a_object_id uuid)
+RETURNS TABLE (post_id uuid, modified timestamptz, objects jsonb) AS $$
+    SELECT post_id, post.date_modified, search.objects
+    FROM data.post_object
+    JOIN data.post post USING (post_id)
+    JOIN post_object_search search USING (post_id)
+    WHERE object_id = a_object_id;
+$$ LANGUAGE SQL STABLE;
+
 CREATE FUNCTION read_post(
     a_post_id       uuid
 ) RETURNS SETOF post AS $$
@@ -891,6 +1341,21 @@ CREATE FUNCTION read_posts(a_posts uuid[]) RETURNS SETOF post AS $$
     ORDER BY ordinality;
 $$ LANGUAGE SQL;
 
+CREATE FUNCTION read_post_objects_search(
+    a_post_id       uuid
+) RETURNS jsonb AS $$
+    SELECT objects
+    FROM post_object_search
+    WHERE post_id = a_post_id;
+$$ LANGUAGE SQL STABLE;
+
+CREATE FUNCTION read_preview_status(a_object_id uuid)
+RETURNS SETOF preview_status AS $$
+    SELECT *
+    FROM preview_status
+    WHERE object_id = a_object_id;
+$$ LANGUAGE SQL STABLE;
+
 CREATE FUNCTION read_post_search() RETURNS SETOF post_search AS $$
 BEGIN
     RETURN QUERY
@@ -900,6 +1365,20 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE FUNCTION read_similar_objects(
+    a_object_id     uuid,
+    a_distance      integer
+) RETURNS TABLE (object_id uuid, distance integer) AS $$
+    SELECT
+        other.object_id,
+        bit_count((other.phash # object.phash)::bit(64))::integer AS distance
+    FROM data.object object
+    JOIN data.object other ON other.object_id <> object.object_id
+    WHERE object.object_id = a_object_id
+        AND bit_count((other.phash # object.phash)::bit(64)) <= a_distance
+    ORDER BY distance, other.object_id;
+$$ LANGUAGE SQL STABLE;
+
 CREATE FUNCTION read_site(
     a_scheme        text,
     a_host          text
@@ -926,6 +1405,18 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE FUNCTION read_tag_categories() RETURNS SETOF text AS $$
+    SELECT name
+    FROM data.tag_category
+    ORDER BY name;
+$$ LANGUAGE SQL;
+
+CREATE FUNCTION read_tag_implications(a_tag_id uuid) RETURNS uuid[] AS $$
+    SELECT coalesce(array_agg(implied_tag_id), '{}')
+    FROM data.tag_implication
+    WHERE tag_id = a_tag_id;
+$$ LANGUAGE SQL;
+
 CREATE FUNCTION read_tag_search()
 RETURNS SETOF tag_search AS $$
 BEGIN
@@ -948,6 +1439,20 @@ CREATE FUNCTION read_tags(a_tags uuid[]) RETURNS SETOF tag AS $$
     ORDER BY ordinality;
 $$ LANGUAGE SQL;
 
+CREATE FUNCTION read_tags_by_name(a_names text[])
+RETURNS TABLE (name text, category text, tags uuid[]) AS $$
+    SELECT
+        lower(entity_name.name),
+        category,
+        array_agg(DISTINCT tag_id)
+    FROM data.tag
+    JOIN data.entity_name ON profile_id = tag_id
+    WHERE lower(entity_name.name) IN (
+        SELECT lower(unnest) FROM unnest(a_names)
+    )
+    GROUP BY lower(entity_name.name), category;
+$$ LANGUAGE SQL;
+
 CREATE FUNCTION read_user(a_user_id uuid) RETURNS SETOF user_account AS $$
     SELECT * FROM user_account WHERE user_id = a_user_id;
 $$ LANGUAGE SQL;
@@ -973,6 +1478,37 @@ RETURNS SETOF user_session AS $$
     WHERE session_id = a_session_id AND expiration > now();
 $$ LANGUAGE SQL;
 
+CREATE FUNCTION read_user_token(a_digest bytea)
+RETURNS SETOF user_token AS $$
+BEGIN
+    UPDATE data.user_token
+    SET last_used = now()
+    WHERE
+        digest = a_digest AND
+        (last_used IS NULL OR last_used < now() - interval '1 minute');
+
+    RETURN QUERY
+    SELECT
+        token_id,
+        user_id,
+        name,
+        scopes,
+        date_created,
+        expiration,
+        last_used
+    FROM data.user_token
+    WHERE digest = a_digest AND (expiration IS NULL OR expiration > now());
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION read_user_tokens(a_user_id uuid)
+RETURNS SETOF user_token AS $$
+    SELECT *
+    FROM user_token
+    WHERE user_id = a_user_id
+    ORDER BY date_created;
+$$ LANGUAGE SQL STABLE;
+
 CREATE FUNCTION read_users(a_users uuid[]) RETURNS SETOF user_account AS $$
     SELECT user_account.*
     FROM (
@@ -994,12 +1530,46 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE FUNCTION read_object_audience(a_object_id uuid)
+RETURNS TABLE (public boolean, users uuid[]) AS $$
+    SELECT
+        EXISTS (
+            SELECT
+            FROM data.post_object
+            JOIN data.post USING (post_id)
+            WHERE object_id = a_object_id AND visibility = 'public'
+        ),
+        ARRAY(
+            SELECT poster
+            FROM data.post_object
+            JOIN data.post USING (post_id)
+            WHERE object_id = a_object_id AND poster IS NOT NULL
+            UNION
+            SELECT user_id
+            FROM data.object_uploader
+            WHERE object_id = a_object_id
+        );
+$$ LANGUAGE SQL STABLE;
+
+CREATE FUNCTION read_object_hash(a_object_id uuid) RETURNS SETOF text AS $$
+    SELECT hash
+    FROM data.object
+    WHERE object_id = a_object_id AND hash IS NOT NULL;
+$$ LANGUAGE SQL STABLE;
+
 CREATE FUNCTION read_object_total() RETURNS int8 AS $$
 BEGIN
     RETURN (SELECT count(*) FROM data.object);
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE FUNCTION read_objects_by_hash(a_hash text) RETURNS SETOF uuid AS $$
+    SELECT object_id
+    FROM data.object
+    WHERE hash = a_hash
+    ORDER BY object_id;
+$$ LANGUAGE SQL STABLE;
+
 CREATE FUNCTION read_objects(a_objects uuid[]) RETURNS SETOF object AS $$
     SELECT object.*
     FROM (
@@ -1030,6 +1600,319 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE FUNCTION search_is_after(
+    a_value         anyelement,
+    a_id            uuid,
+    a_after         anyelement,
+    a_after_id      uuid,
+    a_ascending     boolean
+) RETURNS boolean AS $$
+    SELECT CASE
+        WHEN a_value = a_after THEN a_id > a_after_id
+        WHEN a_ascending THEN a_value > a_after
+        ELSE a_value < a_after
+    END;
+$$ LANGUAGE SQL IMMUTABLE;
+
+CREATE FUNCTION search_in_range(
+    a_value         bigint,
+    a_range         jsonb
+) RETURNS boolean AS $$
+    SELECT
+        (a_range->>'min' IS NULL OR a_value >= (a_range->>'min')::bigint) AND
+        (a_range->>'max' IS NULL OR a_value <= (a_range->>'max')::bigint);
+$$ LANGUAGE SQL IMMUTABLE;
+
+CREATE FUNCTION search_name_pattern(a_name text) RETURNS text AS $$
+    SELECT '\m(' || string_agg(word, '|') || ')'
+    FROM regexp_split_to_table(lower(a_name), '[^[:alnum:]]+') AS word
+    WHERE word <> '';
+$$ LANGUAGE SQL IMMUTABLE;
+
+CREATE FUNCTION search_posts(
+    a_text              text,
+    a_visibility        data.visibility,
+    a_poster            uuid,
+    a_tags              uuid[],
+    a_any_tags          jsonb,
+    a_exclude_tags      uuid[],
+    a_created_after     timestamptz,
+    a_created_before    timestamptz,
+    a_modified_after    timestamptz,
+    a_modified_before   timestamptz,
+    a_media             jsonb,
+    a_sort              text,
+    a_ascending         boolean,
+    a_after             jsonb,
+    a_from              bigint,
+    a_size              bigint,
+    a_facets            bigint
+) RETURNS TABLE (
+    total               bigint,
+    hits                uuid[],
+    cursor              jsonb,
+    facet_tags          uuid[],
+    facet_counts        bigint[]
+) AS $$
+    WITH query AS (
+        SELECT CASE
+            WHEN a_text = '' THEN NULL
+            ELSE websearch_to_tsquery('english', a_text)
+        END AS query
+    ),
+    matches AS (
+        SELECT
+            post_id,
+            tags,
+            title,
+            date_created,
+            date_modified,
+            coalesce(ts_rank(search_document, query), 0) AS rank
+        FROM data.post post, query, LATERAL (
+            SELECT coalesce(array_agg(tag_id), '{}') AS tags
+            FROM data.post_tag
+            WHERE post_tag.post_id = post.post_id
+        ) post_tags
+        WHERE visibility = a_visibility
+            AND (a_poster IS NULL OR poster = a_poster)
+            AND tags @> a_tags
+            AND NOT tags && a_exclude_tags
+            AND (query IS NULL OR search_document @@ query)
+            AND (a_created_after IS NULL OR date_created >= a_created_after)
+            AND (a_created_before IS NULL OR date_created < a_created_before)
+            AND (a_modified_after IS NULL OR date_modified >= a_modified_after)
+            AND (
+                a_modified_before IS NULL OR
+                date_modified < a_modified_before
+            )
+            AND NOT EXISTS (
+                SELECT
+                FROM jsonb_array_elements(a_any_tags) AS tag_group
+                WHERE jsonb_array_length(tag_group) > 0 AND NOT tags && ARRAY(
+                    SELECT jsonb_array_elements_text(tag_group)::uuid
+                )
+            )
+            AND (a_media IS NULL OR EXISTS (
+                SELECT
+                FROM
+                    post_object_search o,
+                    jsonb_array_elements(o.objects) AS object
+                WHERE o.post_id = post.post_id
+                AND (
+                    a_media->>'type' IS NULL OR
+                    object->>'type' = a_media->>'type'
+                )
+                AND (
+                    a_media->>'subtype' IS NULL OR
+                    object->>'subtype' = a_media->>'subtype'
+                )
+                AND (
+                    a_media->>'orientation' IS NULL OR
+                    object->>'orientation' = a_media->>'orientation'
+                )
+                AND search_in_range(
+                    (object->>'width')::bigint,
+                    a_media->'width'
+                )
+                AND search_in_range(
+                    (object->>'height')::bigint,
+                    a_media->'height'
+                )
+                AND search_in_range(
+                    (object->>'duration_ms')::bigint,
+                    a_media->'duration_ms'
+                )
+            ))
+    ),
+    remaining AS (
+        SELECT
+            post_id,
+            CASE a_sort
+                WHEN 'created' THEN to_jsonb(date_created)
+                WHEN 'modified' THEN to_jsonb(date_modified)
+                WHEN 'relevance' THEN to_jsonb(rank)
+                WHEN 'title' THEN to_jsonb(title)
+            END AS sort_value,
+            row_number() OVER (
+                ORDER BY
+                    CASE WHEN a_sort = 'created' AND a_ascending
+                        THEN date_created END,
+                    CASE WHEN a_sort = 'created' AND NOT a_ascending
+                        THEN date_created END DESC,
+                    CASE WHEN a_sort = 'modified' AND a_ascending
+                        THEN date_modified END,
+                    CASE WHEN a_sort = 'modified' AND NOT a_ascending
+                        THEN date_modified END DESC,
+                    CASE WHEN a_sort = 'relevance' AND a_ascending
+                        THEN rank END,
+                    CASE WHEN a_sort = 'relevance' AND NOT a_ascending
+                        THEN rank END DESC,
+                    CASE WHEN a_sort = 'title' AND a_ascending
+                        THEN title END,
+                    CASE WHEN a_sort = 'title' AND NOT a_ascending
+                        THEN title END DESC,
+                    post_id
+            ) AS position
+        FROM matches
+        WHERE a_after IS NULL OR CASE a_sort
+            WHEN 'created' THEN search_is_after(
+                date_created,
+                post_id,
+                (a_after->>0)::timestamptz,
+                (a_after->>1)::uuid,
+                a_ascending
+            )
+            WHEN 'modified' THEN search_is_after(
+                date_modified,
+                post_id,
+                (a_after->>0)::timestamptz,
+                (a_after->>1)::uuid,
+                a_ascending
+            )
+            WHEN 'relevance' THEN search_is_after(
+                rank,
+                post_id,
+                (a_after->>0)::real,
+                (a_after->>1)::uuid,
+                a_ascending
+            )
+            WHEN 'title' THEN search_is_after(
+                title,
+                post_id,
+                a_after->>0,
+                (a_after->>1)::uuid,
+                a_ascending
+            )
+        END
+    ),
+    page AS (
+        SELECT post_id, sort_value, position
+        FROM remaining
+        WHERE position > a_from
+        ORDER BY position
+        LIMIT a_size
+    ),
+    facets AS (
+        SELECT tag_id, count(*) AS count
+        FROM matches, unnest(tags) AS tag_id
+        WHERE tag_id <> ALL(a_tags)
+        GROUP BY tag_id
+        ORDER BY count DESC, tag_id
+        LIMIT a_facets
+    )
+    SELECT
+        (SELECT count(*) FROM matches),
+        ARRAY(SELECT post_id FROM page ORDER BY position),
+        (
+            SELECT jsonb_build_array(sort_value, post_id)
+            FROM page
+            ORDER BY position DESC
+            LIMIT 1
+        ),
+        ARRAY(SELECT tag_id FROM facets ORDER BY count DESC, tag_id),
+        ARRAY(SELECT count FROM facets ORDER BY count DESC, tag_id);
+$$ LANGUAGE SQL STABLE;
+
+CREATE FUNCTION search_tags(
+    a_name          text,
+    a_category      text,
+    a_exclude       uuid[],
+    a_after         jsonb,
+    a_from          bigint,
+    a_size          bigint
+) RETURNS TABLE (total bigint, hits uuid[], cursor jsonb) AS $$
+    WITH matches AS (
+        SELECT tag_id, max(data.similarity(name, a_name)) AS rank
+        FROM
+            data.tag
+            JOIN data.entity_name ON profile_id = tag_id,
+            search_name_pattern(a_name) AS pattern
+        WHERE (pattern IS NULL OR name ~* pattern)
+            AND (a_category IS NULL OR category = a_category)
+            AND tag_id <> ALL(a_exclude)
+        GROUP BY tag_id
+    ),
+    remaining AS (
+        SELECT
+            tag_id,
+            rank,
+            row_number() OVER (ORDER BY rank DESC, tag_id) AS position
+        FROM matches
+        WHERE a_after IS NULL OR search_is_after(
+            rank,
+            tag_id,
+            (a_after->>0)::real,
+            (a_after->>1)::uuid,
+            false
+        )
+    ),
+    page AS (
+        SELECT tag_id, rank, position
+        FROM remaining
+        WHERE position > a_from
+        ORDER BY position
+        LIMIT a_size
+    )
+    SELECT
+        (SELECT count(*) FROM matches),
+        ARRAY(SELECT tag_id FROM page ORDER BY position),
+        (
+            SELECT jsonb_build_array(rank, tag_id)
+            FROM page
+            ORDER BY position DESC
+            LIMIT 1
+        );
+$$ LANGUAGE SQL STABLE;
+
+CREATE FUNCTION search_users(
+    a_name          text,
+    a_exclude       uuid[],
+    a_after         jsonb,
+    a_from          bigint,
+    a_size          bigint
+) RETURNS TABLE (total bigint, hits uuid[], cursor jsonb) AS $$
+    WITH matches AS (
+        SELECT user_id, max(data.similarity(name, a_name)) AS rank
+        FROM
+            data.user_account
+            JOIN data.entity_name ON profile_id = user_id,
+            search_name_pattern(a_name) AS pattern
+        WHERE (pattern IS NULL OR name ~* pattern)
+            AND user_id <> ALL(a_exclude)
+        GROUP BY user_id
+    ),
+    remaining AS (
+        SELECT
+            user_id,
+            rank,
+            row_number() OVER (ORDER BY rank DESC, user_id) AS position
+        FROM matches
+        WHERE a_after IS NULL OR search_is_after(
+            rank,
+            user_id,
+            (a_after->>0)::real,
+            (a_after->>1)::uuid,
+            false
+        )
+    ),
+    page AS (
+        SELECT user_id, rank, position
+        FROM remaining
+        WHERE position > a_from
+        ORDER BY position
+        LIMIT a_size
+    )
+    SELECT
+        (SELECT count(*) FROM matches),
+        ARRAY(SELECT user_id FROM page ORDER BY position),
+        (
+            SELECT jsonb_build_array(rank, user_id)
+            FROM page
+            ORDER BY position DESC
+            LIMIT 1
+        );
+$$ LANGUAGE SQL STABLE;
+
 CREATE FUNCTION stream_objects()
 RETURNS SETOF object AS $$
 BEGIN
@@ -1116,15 +1999,40 @@ $$ LANGUAGE plpgsql;
 
 CREATE FUNCTION update_object_preview(
     a_object_id     uuid,
-    a_preview_id    uuid
+    a_hash          text,
+    a_media_type    text,
+    a_media_subtype text,
+    a_preview_id    uuid,
+    a_phash         bigint,
+    a_variants      text[],
+    a_previews      uuid[],
+    a_metadata      jsonb,
+    a_storyboard_id uuid,
+    a_storyboard    jsonb,
+    a_animation_id  uuid
 ) RETURNS void AS $$
 BEGIN
-    PERFORM create_object_refs(ARRAY[a_preview_id]);
+    PERFORM create_object_refs(ARRAY[
+        a_preview_id,
+        a_storyboard_id,
+        a_animation_id
+    ]);
 
     UPDATE data.object
-    SET preview_id = a_preview_id
+    SET
+        hash = a_hash,
+        media_type = a_media_type,
+        media_subtype = a_media_subtype,
+        preview_id = a_preview_id,
+        phash = a_phash,
+        metadata = a_metadata,
+        storyboard_id = a_storyboard_id,
+        storyboard = a_storyboard,
+        animation_id = a_animation_id
     WHERE object_id = a_object_id;
 
+    PERFORM create_object_previews(a_object_id, a_variants, a_previews);
+
     DELETE FROM data.object_preview_error
     WHERE object_id = a_object_id;
 END;
@@ -1164,6 +2072,17 @@ BEGIN
 END;
 $$ LANGUAGE plpgsql;
 
+CREATE FUNCTION update_tag_category(a_tag_id uuid, a_category text)
+RETURNS boolean AS $$
+BEGIN
+    UPDATE data.tag
+    SET category = a_category
+    WHERE tag_id = a_tag_id;
+
+    RETURN FOUND;
+END;
+$$ LANGUAGE plpgsql;
+
 CREATE FUNCTION update_user_email(a_user_id uuid, a_email text)
 RETURNS bool AS $$
 BEGIN
@@ -1230,11 +2149,22 @@ BEGIN
         admin boolean
     );
 
+    INSERT INTO data.tag_category (name)
+    SELECT jsonb_array_elements_text(data -> 'categories');
+
     PERFORM import_entity(data -> 'tags');
 
-    INSERT INTO data.tag (tag_id, creator)
-    SELECT id, creator
-    FROM jsonb_to_recordset(data -> 'tags') AS (id uuid, creator uuid);
+    INSERT INTO data.tag (tag_id, creator, category)
+    SELECT id, creator, category
+    FROM jsonb_to_recordset(data -> 'tags') AS (
+        id uuid,
+        creator uuid,
+        category text
+    );
+
+    INSERT INTO data.tag_implication (tag_id, implied_tag_id)
+    SELECT id, unnest(implies)
+    FROM jsonb_to_recordset(data -> 'tags') AS (id uuid, implies uuid[]);
 
     INSERT INTO data.post (
         post_id,
@@ -1298,6 +2228,10 @@ $$ LANGUAGE plpgsql;
 
 CREATE FUNCTION export() RETURNS json AS $$
 SELECT json_build_object(
+    'categories', (
+        SELECT coalesce(json_agg(name ORDER BY name), '[]'::json)
+        FROM data.tag_category
+    ),
     'posts', (
         SELECT (coalesce((
             SELECT json_agg(p)
@@ -1364,7 +2298,13 @@ SELECT json_build_object(
                     avatar,
                     banner,
                     created,
-                    creator
+                    creator,
+                    category,
+                    coalesce((
+                        SELECT json_agg(implied_tag_id)
+                        FROM data.tag_implication i
+                        WHERE i.tag_id = tag.tag_id
+                    ), '[]'::json) AS implies
                 FROM tag
                 ORDER BY name
             ) t
@@ -1,3 +1,5 @@
+CREATE EXTENSION pg_trgm WITH SCHEMA data;
+
 CREATE TYPE visibility AS ENUM (
     'draft',
     'public'
@@ -25,14 +27,43 @@ CREATE TABLE source (
 CREATE TABLE object (
     object_id       uuid PRIMARY KEY REFERENCES object_ref ON DELETE NO ACTION,
     preview_id      uuid REFERENCES object_ref ON DELETE NO ACTION,
-    source_id       bigint REFERENCES source ON DELETE NO ACTION
+    source_id       bigint REFERENCES source ON DELETE NO ACTION,
+    phash           bigint,
+    hash            text,
+    media_type      text,
+    media_subtype   text,
+    metadata        jsonb,
+    storyboard_id   uuid REFERENCES object_ref ON DELETE NO ACTION,
+    storyboard      jsonb,
+    animation_id    uuid REFERENCES object_ref ON DELETE NO ACTION
 );
 
+CREATE INDEX object_hash_index ON object (hash);
+
 CREATE TABLE object_preview_error (
     object_id       uuid PRIMARY KEY REFERENCES object ON DELETE CASCADE,
     message         text NOT NULL
 );
 
+CREATE TABLE object_preview (
+    object_id       uuid NOT NULL REFERENCES object ON DELETE CASCADE,
+    variant         text NOT NULL,
+    preview_id      uuid NOT NULL REFERENCES object_ref ON DELETE NO ACTION,
+
+    PRIMARY KEY (object_id, variant)
+);
+
+CREATE TABLE preview_job (
+    object_id       uuid PRIMARY KEY REFERENCES object ON DELETE CASCADE,
+    priority        smallint NOT NULL DEFAULT 0,
+    attempts        smallint NOT NULL DEFAULT 0,
+    run_after       timestamptz NOT NULL DEFAULT NOW(),
+    locked_until    timestamptz,
+    last_error      text
+);
+
+CREATE INDEX preview_job_queue_index ON preview_job (priority DESC, run_after);
+
 CREATE TABLE entity_profile (
     profile_id      uuid DEFAULT gen_random_uuid() PRIMARY KEY,
     description     text NOT NULL DEFAULT '',
@@ -49,6 +80,9 @@ CREATE TABLE entity_name (
     PRIMARY KEY (profile_id, name)
 );
 
+CREATE INDEX entity_name_trigram_index ON entity_name
+USING gin (name gin_trgm_ops);
+
 CREATE TABLE entity_link (
     profile_id      uuid NOT NULL REFERENCES entity_profile ON DELETE CASCADE,
     source_id       bigint NOT NULL REFERENCES source ON DELETE NO ACTION,
@@ -63,9 +97,19 @@ CREATE TABLE user_account (
     admin           boolean NOT NULL DEFAULT false
 );
 
+CREATE TABLE object_uploader (
+    object_id       uuid PRIMARY KEY REFERENCES object ON DELETE CASCADE,
+    user_id         uuid NOT NULL REFERENCES user_account ON DELETE CASCADE
+);
+
+CREATE TABLE tag_category (
+    name            text PRIMARY KEY
+);
+
 CREATE TABLE tag (
     tag_id         uuid PRIMARY KEY REFERENCES entity_profile ON DELETE CASCADE,
-    creator        uuid REFERENCES user_account ON DELETE SET NULL
+    creator        uuid REFERENCES user_account ON DELETE SET NULL,
+    category       text REFERENCES tag_category ON DELETE SET NULL
 );
 
 CREATE TABLE post (
@@ -76,9 +120,15 @@ CREATE TABLE post (
     objects         uuid[] NOT NULL DEFAULT '{}',
     visibility      visibility NOT NULL,
     date_created    timestamptz NOT NULL DEFAULT NOW(),
-    date_modified   timestamptz NOT NULL DEFAULT NOW()
+    date_modified   timestamptz NOT NULL DEFAULT NOW(),
+    search_document tsvector NOT NULL GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', title), 'A') ||
+        setweight(to_tsvector('english', description), 'B')
+    ) STORED
 );
 
+CREATE INDEX post_search_index ON post USING gin (search_document);
+
 CREATE TABLE post_object (
     post_id         uuid NOT NULL REFERENCES post ON DELETE CASCADE,
     object_id       uuid NOT NULL REFERENCES object ON DELETE CASCADE,
@@ -111,8 +161,26 @@ CREATE TABLE post_tag (
     PRIMARY KEY (post_id, tag_id)
 );
 
+CREATE TABLE tag_implication (
+    tag_id          uuid NOT NULL REFERENCES tag ON DELETE CASCADE,
+    implied_tag_id  uuid NOT NULL REFERENCES tag ON DELETE CASCADE,
+
+    PRIMARY KEY (tag_id, implied_tag_id)
+);
+
 CREATE TABLE user_session (
     session_id      bytea PRIMARY KEY,
     user_id         uuid NOT NULL REFERENCES user_account ON DELETE CASCADE,
     expiration      timestamptz NOT NULL
 );
+
+CREATE TABLE user_token (
+    token_id        uuid DEFAULT gen_random_uuid() PRIMARY KEY,
+    user_id         uuid NOT NULL REFERENCES user_account ON DELETE CASCADE,
+    name            text NOT NULL,
+    digest          bytea NOT NULL UNIQUE,
+    scopes          text[] NOT NULL,
+    date_created    timestamptz NOT NULL DEFAULT NOW(),
+    expiration      timestamptz,
+    last_used       timestamptz
+);
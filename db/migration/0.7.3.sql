@@ -0,0 +1,100 @@
+CREATE TABLE tag_implication (
+    tag_id          uuid NOT NULL REFERENCES tag ON DELETE CASCADE,
+    implied_tag_id  uuid NOT NULL REFERENCES tag ON DELETE CASCADE,
+
+    PRIMARY KEY (tag_id, implied_tag_id)
+);
+
+CREATE TABLE tag_category (
+    name            text PRIMARY KEY
+);
+
+ALTER TABLE tag
+ADD COLUMN category text REFERENCES tag_category ON DELETE SET NULL;
+
+CREATE EXTENSION pg_trgm WITH SCHEMA data;
+
+ALTER TABLE post
+ADD COLUMN search_document tsvector NOT NULL GENERATED ALWAYS AS (
+    setweight(to_tsvector('english', title), 'A') ||
+    setweight(to_tsvector('english', description), 'B')
+) STORED;
+
+CREATE INDEX post_search_index ON post USING gin (search_document);
+
+CREATE INDEX entity_name_trigram_index ON entity_name
+USING gin (name gin_trgm_ops);
+
+ALTER TABLE object
+ADD COLUMN phash bigint;
+
+ALTER TABLE object
+ADD COLUMN hash text;
+
+CREATE INDEX object_hash_index ON object (hash);
+
+ALTER TABLE object
+ADD COLUMN metadata jsonb;
+
+CREATE TABLE object_preview (
+    object_id       uuid NOT NULL REFERENCES object ON DELETE CASCADE,
+    variant         text NOT NULL,
+    preview_id      uuid NOT NULL REFERENCES object_ref ON DELETE NO ACTION,
+
+    PRIMARY KEY (object_id, variant)
+);
+
+INSERT INTO object_preview (object_id, variant, preview_id)
+SELECT object_id, 'thumbnail', preview_id
+FROM object
+WHERE preview_id IS NOT NULL;
+
+ALTER TABLE object
+ADD COLUMN media_type text;
+
+ALTER TABLE object
+ADD COLUMN media_subtype text;
+
+ALTER TABLE object
+ADD COLUMN storyboard_id uuid REFERENCES object_ref ON DELETE NO ACTION;
+
+ALTER TABLE object
+ADD COLUMN storyboard jsonb;
+
+ALTER TABLE object
+ADD COLUMN animation_id uuid REFERENCES object_ref ON DELETE NO ACTION;
+
+CREATE TABLE preview_job (
+    object_id       uuid PRIMARY KEY REFERENCES object ON DELETE CASCADE,
+    priority        smallint NOT NULL DEFAULT 0,
+    attempts        smallint NOT NULL DEFAULT 0,
+    run_after       timestamptz NOT NULL DEFAULT NOW(),
+    locked_until    timestamptz,
+    last_error      text
+);
+
+CREATE INDEX preview_job_queue_index ON preview_job (priority DESC, run_after);
+
+-- Objects uploaded before this version have no hash, metadata or media
+-- type. Queue them for preview regeneration, which fills in those columns
+-- and updates the search index. New uploads are processed first.
+INSERT INTO preview_job (object_id, priority)
+SELECT object_id, -1
+FROM object
+WHERE hash IS NULL;
+
+CREATE TABLE object_uploader (
+    object_id       uuid PRIMARY KEY REFERENCES object ON DELETE CASCADE,
+    user_id         uuid NOT NULL REFERENCES user_account ON DELETE CASCADE
+);
+
+CREATE TABLE user_token (
+    token_id        uuid DEFAULT gen_random_uuid() PRIMARY KEY,
+    user_id         uuid NOT NULL REFERENCES user_account ON DELETE CASCADE,
+    name            text NOT NULL,
+    digest          bytea NOT NULL UNIQUE,
+    scopes          text[] NOT NULL,
+    date_created    timestamptz NOT NULL DEFAULT NOW(),
+    expiration      timestamptz,
+    last_used       timestamptz
+);